@@ -48,6 +48,32 @@ pub enum SfsError {
     TeamCompositionRulesViolation,
     #[error("Score already updated cannot update again")]
     ScoreAlreadyUpdated,
+    #[error("Lineup does not match league position slots")]
+    PositionRulesViolation,
+    #[error("Score correction window is closed")]
+    ScoreCorrectionWindowClosed,
+    #[error("League vault balance does not match the bids")]
+    VaultBalanceMismatch,
+    #[error("Not allowed to join the league")]
+    JoinNotAllowed,
+    #[error("League is not initialized")]
+    LeagueNotInitialized,
+    #[error("League has no room for more users")]
+    LeagueFull,
+    #[error("User already joined the league")]
+    AlreadyJoined,
+    #[error("Leagues can no longer be joined this season")]
+    JoinClosed,
+    #[error("Current pick is still on the clock")]
+    PickNotExpired,
+    #[error("Bid must be higher than the current one")]
+    BidTooLow,
+    #[error("Auction of the nominated player is closed")]
+    AuctionClosed,
+    #[error("Another player is up for auction")]
+    AuctionInProgress,
+    #[error("League fill deadline has passed")]
+    FillDeadlinePassed,
 }
 impl From<SfsError> for ProgramError {
     fn from(e: SfsError) -> Self {
@@ -79,6 +105,19 @@ impl PrintProgramError for SfsError {
             SfsError::ItemNotFound => info!("Item not found in the list"),
             SfsError::TeamCompositionRulesViolation => info!("Team composition rules are not met"),
             SfsError::ScoreAlreadyUpdated => info!("Score already updated cannot update again"),
+            SfsError::PositionRulesViolation => info!("Lineup does not match league position slots"),
+            SfsError::ScoreCorrectionWindowClosed => info!("Score correction window is closed"),
+            SfsError::VaultBalanceMismatch => info!("League vault balance does not match the bids"),
+            SfsError::JoinNotAllowed => info!("Not allowed to join the league"),
+            SfsError::LeagueNotInitialized => info!("League is not initialized"),
+            SfsError::LeagueFull => info!("League has no room for more users"),
+            SfsError::AlreadyJoined => info!("User already joined the league"),
+            SfsError::JoinClosed => info!("Leagues can no longer be joined this season"),
+            SfsError::PickNotExpired => info!("Current pick is still on the clock"),
+            SfsError::BidTooLow => info!("Bid must be higher than the current one"),
+            SfsError::AuctionClosed => info!("Auction of the nominated player is closed"),
+            SfsError::AuctionInProgress => info!("Another player is up for auction"),
+            SfsError::FillDeadlinePassed => info!("League fill deadline has passed"),
         }
     }
 }
@@ -89,10 +128,6 @@ impl PrintProgramError for SfsError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use solana_program::{
-        account::Account as SolanaAccount, account_info::create_is_signer_account_infos,
-        clock::Epoch, instruction::Instruction, sysvar::rent,
-    };
 
     fn return_sfs_error_as_program_error() -> ProgramError {
         SfsError::InvalidInstruction.into()
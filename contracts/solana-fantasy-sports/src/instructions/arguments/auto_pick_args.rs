@@ -0,0 +1,50 @@
+//! State transition types
+
+use crate::instructions::*;
+use arrayref::{array_mut_ref, array_ref};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct AutoPickArgs<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> AutoPickArgs<'a> {
+    pub const LEN: usize = 2;
+    fn slice<'b>(&self, data: &'b [u8]) -> &'b [u8; 2] {
+        array_ref![data, self.offset, AutoPickArgs::LEN]
+    }
+
+    pub fn get_league_index(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&self.data.borrow()))
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, AutoPickArgs::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            AutoPickArgs::LEN
+        ]);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<AutoPickArgs<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(AutoPickArgs { data, offset })
+    }
+}
+impl Clone for AutoPickArgs<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            offset: self.offset,
+        }
+    }
+}
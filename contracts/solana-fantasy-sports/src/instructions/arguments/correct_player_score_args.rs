@@ -0,0 +1,71 @@
+//! State transition types
+
+use crate::instructions::*;
+use crate::state::Stat;
+use arrayref::{array_mut_ref, array_ref, array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use num_enum::TryFromPrimitive;
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct CorrectPlayerScoreArgs<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> CorrectPlayerScoreArgs<'a> {
+    pub const LEN: usize = 2 + 1 + 1 + 2;
+    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; 2], &'b [u8; 1], &'b [u8; 1], &'b [u8; 2]) {
+        array_refs![
+            array_ref![data, self.offset, CorrectPlayerScoreArgs::LEN],
+            2,
+            1,
+            1,
+            2
+        ]
+    }
+
+    pub fn get_player_id(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&self.data.borrow()).0)
+    }
+
+    pub fn get_week(&self) -> u8 {
+        self.slice(&self.data.borrow()).1[0]
+    }
+
+    pub fn get_stat(&self) -> Result<Stat, ProgramError> {
+        Stat::try_from_primitive(self.slice(&self.data.borrow()).2[0])
+            .or(Err(ProgramError::InvalidInstructionData))
+    }
+
+    pub fn get_value(&self) -> i16 {
+        LittleEndian::read_i16(self.slice(&self.data.borrow()).3)
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, CorrectPlayerScoreArgs::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            CorrectPlayerScoreArgs::LEN
+        ]);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<CorrectPlayerScoreArgs<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(CorrectPlayerScoreArgs { data, offset })
+    }
+}
+impl Clone for CorrectPlayerScoreArgs<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            offset: self.offset,
+        }
+    }
+}
@@ -2,8 +2,12 @@
 
 use crate::instructions::*;
 use crate::state::consts::*;
+use crate::state::{
+    CompositionRules, DraftType, JoinAccess, LeagueType, PositionOptions, ScoringRules,
+};
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use byteorder::{ByteOrder, LittleEndian};
+use num_enum::TryFromPrimitive;
 use solana_program::pubkey::Pubkey;
 use solana_program::{
     program_error::ProgramError,
@@ -17,7 +21,7 @@ pub struct CreateLeagueArgs<'a> {
     offset: usize,
 }
 impl<'a> CreateLeagueArgs<'a> {
-    pub const LEN: usize = LEAGUE_NAME_LEN + 8 + 1 + TEAM_NAME_LEN + NUM_POSITIONS as usize -1;
+    pub const LEN: usize = LEAGUE_NAME_LEN + 8 + 1 + TEAM_NAME_LEN + PositionOptions::LEN + 1 + 1 + CompositionRules::LEN + ScoringRules::LEN + 1 + 1 + PAYOUTS_LEN + PUB_KEY_LEN + 8 + 1 + PUB_KEY_LEN + ALLOW_LIST_LEN + 4 + 1 + 4 + 4;
     fn slice<'b>(
         &self,
         data: &'b [u8],
@@ -26,7 +30,23 @@ impl<'a> CreateLeagueArgs<'a> {
         &'b [u8; 8],
         &'b [u8; 1],
         &'b [u8; TEAM_NAME_LEN],
-        &'b [u8;NUM_POSITIONS as usize -1],
+        &'b [u8; PositionOptions::LEN],
+        &'b [u8; 1],
+        &'b [u8; 1],
+        &'b [u8; CompositionRules::LEN],
+        &'b [u8; ScoringRules::LEN],
+        &'b [u8; 1],
+        &'b [u8; 1],
+        &'b [u8; PAYOUTS_LEN],
+        &'b [u8; PUB_KEY_LEN],
+        &'b [u8; 8],
+        &'b [u8; 1],
+        &'b [u8; PUB_KEY_LEN],
+        &'b [u8; ALLOW_LIST_LEN],
+        &'b [u8; 4],
+        &'b [u8; 1],
+        &'b [u8; 4],
+        &'b [u8; 4],
     ) {
         array_refs![
             array_ref![data, self.offset, CreateLeagueArgs::LEN],
@@ -34,7 +54,23 @@ impl<'a> CreateLeagueArgs<'a> {
             8,
             1,
             TEAM_NAME_LEN,
-            NUM_POSITIONS as usize - 1
+            PositionOptions::LEN,
+            1,
+            1,
+            CompositionRules::LEN,
+            ScoringRules::LEN,
+            1,
+            1,
+            PAYOUTS_LEN,
+            PUB_KEY_LEN,
+            8,
+            1,
+            PUB_KEY_LEN,
+            ALLOW_LIST_LEN,
+            4,
+            1,
+            4,
+            4
         ]
     }
 
@@ -63,10 +99,92 @@ impl<'a> CreateLeagueArgs<'a> {
         ]);
     }
 
-    pub fn get_positions(&self)-> &[u8;NUM_POSITIONS as usize-1] {
+    pub fn get_positions(&self) -> &[u8; PositionOptions::LEN] {
         self.slice(&self.data.borrow()).4
     }
 
+    pub fn get_active_players_count(&self) -> u8 {
+        self.slice(&self.data.borrow()).5[0]
+    }
+
+    pub fn get_bench_players_count(&self) -> u8 {
+        self.slice(&self.data.borrow()).6[0]
+    }
+
+    pub fn get_composition_rules(&self) -> &[u8; CompositionRules::LEN] {
+        self.slice(&self.data.borrow()).7
+    }
+
+    /// Fantasy points per unit of every stat in hundredths of a point
+    pub fn get_scoring_rules(&self) -> &[u8; ScoringRules::LEN] {
+        self.slice(&self.data.borrow()).8
+    }
+
+    pub fn get_league_type(&self) -> Result<LeagueType, ProgramError> {
+        LeagueType::try_from_primitive(self.slice(&self.data.borrow()).9[0])
+            .or(Err(ProgramError::InvalidInstructionData))
+    }
+
+    /// Number of top teams advancing to the playoff, 0 for no playoff
+    pub fn get_playoff_teams_count(&self) -> u8 {
+        self.slice(&self.data.borrow()).10[0]
+    }
+
+    /// Share of the pot for every place in basis points, e.g. 6000, 3000, 1000
+    pub fn get_payouts(&self) -> &[u8; PAYOUTS_LEN] {
+        self.slice(&self.data.borrow()).11
+    }
+
+    /// SPL token mint of the bid, default for native lamports
+    pub fn get_mint(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&self.data.borrow()).12)
+    }
+    /// Unix timestamp after which an unfilled league can be refunded, zero for none
+    pub fn get_fill_deadline(&self) -> i64 {
+        LittleEndian::read_i64(self.slice(&self.data.borrow()).13)
+    }
+
+    pub fn get_join_access(&self) -> Result<JoinAccess, ProgramError> {
+        JoinAccess::try_from_primitive(self.slice(&self.data.borrow()).14[0])
+            .or(Err(ProgramError::InvalidInstructionData))
+    }
+
+    /// Public key of the keypair derived from the join password of a league with
+    /// `JoinAccess::Password`
+    pub fn get_password_key(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&self.data.borrow()).15)
+    }
+
+    /// Wallets allowed to join a league with `JoinAccess::AllowList`, padded with default pubkeys
+    pub fn get_allow_list(&self) -> &[u8; ALLOW_LIST_LEN] {
+        self.slice(&self.data.borrow()).16
+    }
+
+    /// Seconds a user has to make a pick before it can be auto-picked, zero for no timer
+    pub fn get_pick_time_limit(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&self.data.borrow()).17)
+    }
+
+    pub fn get_draft_type(&self) -> Result<DraftType, ProgramError> {
+        DraftType::try_from_primitive(self.slice(&self.data.borrow()).18[0])
+            .or(Err(ProgramError::InvalidInstructionData))
+    }
+
+    /// Budget every user of an auction draft starts with
+    pub fn get_auction_budget(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&self.data.borrow()).19)
+    }
+
+    /// Seconds without a higher bid after which the nominated player is won
+    pub fn get_auction_window(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&self.data.borrow()).20)
+    }
+
+    pub fn get_payout(&self, place: u8) -> u16 {
+        let index = 2 * (place as usize - 1);
+        LittleEndian::read_u16(&self.get_payouts()[index..index + 2])
+    }
+
     pub fn new(
         data: &'a RefCell<&'a [u8]>,
         offset: usize,
@@ -2,7 +2,9 @@
 
 use crate::instructions::*;
 use crate::state::consts::*;
+use crate::state::Sport;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_enum::TryFromPrimitive;
 use solana_program::pubkey::Pubkey;
 use solana_program::{
     program_error::ProgramError,
@@ -16,11 +18,24 @@ pub struct InitializeRootArgs<'a> {
     offset: usize,
 }
 impl<'a> InitializeRootArgs<'a> {
-    pub const LEN: usize = PUB_KEY_LEN + 1;
-    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; PUB_KEY_LEN], &'b [u8; 1]) {
+    pub const LEN: usize = PUB_KEY_LEN + 1 + PUB_KEY_LEN + 1 + 1;
+    #[allow(clippy::type_complexity)]
+    fn slice<'b>(
+        &self,
+        data: &'b [u8],
+    ) -> (
+        &'b [u8; PUB_KEY_LEN],
+        &'b [u8; 1],
+        &'b [u8; PUB_KEY_LEN],
+        &'b [u8; 1],
+        &'b [u8; 1],
+    ) {
         array_refs![
             array_ref![data, self.offset, InitializeRootArgs::LEN],
             PUB_KEY_LEN,
+            1,
+            PUB_KEY_LEN,
+            1,
             1
         ]
     }
@@ -33,6 +48,19 @@ impl<'a> InitializeRootArgs<'a> {
         self.slice(&mut self.data.borrow()).1[0]
     }
 
+    pub fn get_admin_authority(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&self.data.borrow()).2)
+    }
+
+    pub fn get_sport(&self) -> Result<Sport, ProgramError> {
+        Sport::try_from_primitive(self.slice(&self.data.borrow()).3[0])
+            .or(Err(ProgramError::InvalidInstructionData))
+    }
+
+    pub fn get_score_correction_weeks(&self) -> u8 {
+        self.slice(&self.data.borrow()).4[0]
+    }
+
     pub fn copy_to(&self, to: &mut [u8]) {
         let src = self.data.borrow();
         array_mut_ref![to, self.offset, InitializeRootArgs::LEN].copy_from_slice(array_ref![
@@ -8,8 +8,6 @@ pub mod accept_swap_args;
 pub use accept_swap_args::*;
 pub mod pick_player_args;
 pub use pick_player_args::*;
-pub mod seed_draft_selection_args;
-pub use seed_draft_selection_args::*;
 pub mod update_lineup_args;
 pub use update_lineup_args::*;
 pub mod create_league_args;
@@ -21,4 +19,32 @@ pub use update_player_score_args::*;
 pub mod claim_reward_args;
 pub use claim_reward_args::*;
 pub mod reject_swap_args;
-pub use reject_swap_args::*;
\ No newline at end of file
+pub use reject_swap_args::*;
+pub mod update_player_scores_args;
+pub use update_player_scores_args::*;
+pub mod correct_player_score_args;
+pub use correct_player_score_args::*;
+pub mod settle_week_args;
+pub use settle_week_args::*;
+pub mod settle_playoff_round_args;
+pub use settle_playoff_round_args::*;
+pub mod set_protocol_fee_args;
+pub use set_protocol_fee_args::*;
+pub mod leave_league_args;
+pub use leave_league_args::*;
+pub mod cancel_league_args;
+pub use cancel_league_args::*;
+pub mod refund_unfilled_league_args;
+pub use refund_unfilled_league_args::*;
+pub mod auto_pick_args;
+pub use auto_pick_args::*;
+pub mod set_draft_queue_args;
+pub use set_draft_queue_args::*;
+pub mod nominate_player_args;
+pub use nominate_player_args::*;
+pub mod place_bid_args;
+pub use place_bid_args::*;
+pub mod add_drop_player_args;
+pub use add_drop_player_args::*;
+pub mod draw_pick_order_args;
+pub use draw_pick_order_args::*;
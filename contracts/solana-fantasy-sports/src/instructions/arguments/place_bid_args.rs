@@ -0,0 +1,56 @@
+//! State transition types
+
+use crate::instructions::*;
+use arrayref::{array_mut_ref, array_ref, array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct PlaceBidArgs<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> PlaceBidArgs<'a> {
+    pub const LEN: usize = 2 + 1 + 4;
+    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; 2], &'b [u8; 1], &'b [u8; 4]) {
+        array_refs![array_ref![data, self.offset, PlaceBidArgs::LEN], 2, 1, 4]
+    }
+
+    pub fn get_league_index(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&self.data.borrow()).0)
+    }
+    pub fn get_user_id(&self) -> u8 {
+        self.slice(&self.data.borrow()).1[0]
+    }
+    pub fn get_bid(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&self.data.borrow()).2)
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, PlaceBidArgs::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            PlaceBidArgs::LEN
+        ]);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<PlaceBidArgs<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(PlaceBidArgs { data, offset })
+    }
+}
+impl Clone for PlaceBidArgs<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            offset: self.offset,
+        }
+    }
+}
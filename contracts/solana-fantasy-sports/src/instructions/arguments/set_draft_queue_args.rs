@@ -0,0 +1,66 @@
+//! State transition types
+
+use crate::instructions::*;
+use crate::state::DraftQueueList;
+use arrayref::{array_mut_ref, array_ref, array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct SetDraftQueueArgs<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> SetDraftQueueArgs<'a> {
+    pub const LEN: usize = 2 + 1 + DraftQueueList::LEN;
+    fn slice<'b>(
+        &self,
+        data: &'b [u8],
+    ) -> (&'b [u8; 2], &'b [u8; 1], &'b [u8; DraftQueueList::LEN]) {
+        array_refs![
+            array_ref![data, self.offset, SetDraftQueueArgs::LEN],
+            2,
+            1,
+            DraftQueueList::LEN
+        ]
+    }
+
+    pub fn get_league_index(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&self.data.borrow()).0)
+    }
+    pub fn get_user_id(&self) -> u8 {
+        self.slice(&self.data.borrow()).1[0]
+    }
+    /// Player ids from the most wanted, zero padded
+    pub fn get_draft_queue(&self) -> &[u8; DraftQueueList::LEN] {
+        self.slice(&self.data.borrow()).2
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, SetDraftQueueArgs::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            SetDraftQueueArgs::LEN
+        ]);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<SetDraftQueueArgs<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(SetDraftQueueArgs { data, offset })
+    }
+}
+impl Clone for SetDraftQueueArgs<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            offset: self.offset,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! State transition types
+
+use crate::instructions::*;
+use crate::state::consts::*;
+use arrayref::{array_mut_ref, array_ref, array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct SetProtocolFeeArgs<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> SetProtocolFeeArgs<'a> {
+    pub const LEN: usize = 2 + PUB_KEY_LEN;
+    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; 2], &'b [u8; PUB_KEY_LEN]) {
+        array_refs![
+            array_ref![data, self.offset, SetProtocolFeeArgs::LEN],
+            2,
+            PUB_KEY_LEN
+        ]
+    }
+
+    /// Fee in basis points of the pot
+    pub fn get_fee_bps(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&self.data.borrow()).0)
+    }
+
+    pub fn get_treasury(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&self.data.borrow()).1)
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, SetProtocolFeeArgs::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            SetProtocolFeeArgs::LEN
+        ]);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<SetProtocolFeeArgs<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(SetProtocolFeeArgs { data, offset })
+    }
+}
+impl Clone for SetProtocolFeeArgs<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            offset: self.offset,
+        }
+    }
+}
@@ -17,12 +17,16 @@ pub struct UpdatePlayerScoreArgs<'a> {
     pub offset: usize,
 }
 impl<'a> UpdatePlayerScoreArgs<'a> {
-    pub const LEN: usize = 2 + 2;
-    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; 2], &'b [u8; 2]) {
+    pub const STATS_LEN: usize = 2 * STATS_COUNT as usize;
+    pub const LEN: usize = 2 + UpdatePlayerScoreArgs::STATS_LEN;
+    fn slice<'b>(
+        &self,
+        data: &'b [u8],
+    ) -> (&'b [u8; 2], &'b [u8; UpdatePlayerScoreArgs::STATS_LEN]) {
         array_refs![
             array_ref![data, self.offset, UpdatePlayerScoreArgs::LEN],
             2,
-            2
+            UpdatePlayerScoreArgs::STATS_LEN
         ]
     }
 
@@ -30,8 +34,11 @@ impl<'a> UpdatePlayerScoreArgs<'a> {
         LittleEndian::read_u16(self.slice(&mut self.data.borrow()).0)
     }
 
-    pub fn get_player_score(&self) -> u16 {
-        LittleEndian::read_u16(self.slice(&mut self.data.borrow()).1)
+    /// Raw stat line for the current week, indexed by `Stat`
+    pub fn get_stats(&self) -> [i16; STATS_COUNT as usize] {
+        let mut stats = [0i16; STATS_COUNT as usize];
+        LittleEndian::read_i16_into(self.slice(&self.data.borrow()).1, &mut stats);
+        stats
     }
 
     pub fn copy_to(&self, to: &mut [u8]) {
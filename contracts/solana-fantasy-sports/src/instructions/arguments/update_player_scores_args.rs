@@ -0,0 +1,46 @@
+//! State transition types
+
+use crate::instructions::*;
+use arrayref::{array_mut_ref, array_ref};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct UpdatePlayerScoresArgs<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> UpdatePlayerScoresArgs<'a> {
+    pub const LEN: usize = PlayerScoreList::LEN;
+
+    pub fn get_scores(&self) -> Result<PlayerScoreList<'a>, ProgramError> {
+        PlayerScoreList::new(self.data, self.offset)
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, UpdatePlayerScoresArgs::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            UpdatePlayerScoresArgs::LEN
+        ]);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<UpdatePlayerScoresArgs<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(UpdatePlayerScoresArgs { data, offset })
+    }
+}
+impl Clone for UpdatePlayerScoresArgs<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            offset: self.offset,
+        }
+    }
+}
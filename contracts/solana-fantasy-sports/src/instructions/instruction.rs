@@ -1,6 +1,6 @@
 //! State transition types
 
-use crate::{error::SfsError, instructions::*};
+use crate::{error::SfsError, instructions::*, spl_token};
 use arrayref::{array_mut_ref, array_ref};
 use byteorder::{ByteOrder, LittleEndian};
 use solana_program::{
@@ -9,7 +9,7 @@ use solana_program::{
     program_option::COption,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::Pubkey,
-    sysvar,
+    system_program, sysvar,
 };
 use std::cell::RefCell;
 use std::convert::TryInto;
@@ -20,6 +20,13 @@ use std::mem::size_of;
 pub enum SfsInstruction<'a> {
     /// Not yet initialized
     Uninitialized,
+    /// Adds players to the root player pool.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The admin authority.
+    ///
     AddPlayers {
         args: AddPlayersArgs<'a>,
     },
@@ -37,26 +44,304 @@ pub enum SfsInstruction<'a> {
     InitializeRoot {
         args: InitializeRootArgs<'a>,
     },
-    SeedDraftSelection {
-        args: SeedDraftSelectionArgs<'a>,
-    },
+    /// Starts the season.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The admin authority.
+    ///
     StartSeason,
+    /// Creates a new league in its own account derived from the root and the league index.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[writable]` The league account, derived from the root and `Root::get_leagues_count`.
+    ///   2. `[signer, writable]` The league creator paying the bid and the league rent.
+    ///   3. `[writable]` The league vault, derived from the root and the league index,
+    ///      funded rent exempt by the creator for lamport bids.
+    ///   4. `[]` System program
+    ///   5. `[]` Rent sysvar
+    ///   6. `[]` Clock sysvar, the fill deadline must be in the future.
+    ///
+    ///   Leagues with a token bid also expect:
+    ///
+    ///   7. `[]` Token program
+    ///   8. `[writable]` The token account of the creator paying the bid.
+    ///   9. `[writable]` The token account of the league mint owned by the league vault.
+    ///
     CreateLeague {
         args: CreateLeagueArgs<'a>,
     },
+    /// Joins an existing league before its fill deadline. Password leagues are joined by
+    /// signing with the keypair derived from the password, allow-list leagues restrict who
+    /// can join.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer, writable]` The user paying the bid.
+    ///   3. `[writable]` The league vault.
+    ///   4. `[]` System program
+    ///   5. `[]` Clock sysvar, the slot a full league draws its pick order after.
+    ///
+    ///   Password leagues also expect:
+    ///
+    ///   6. `[signer]` The password key of the league.
+    ///
+    ///   Leagues with a token bid also expect, after the password key if any:
+    ///
+    ///   6. `[]` Token program
+    ///   7. `[writable]` The token account of the user paying the bid.
+    ///   8. `[writable]` The token account of the league vault.
+    ///
     JoinLeague {
         args: JoinLeagueArgs<'a>,
     },
-    // CompleteSeason,
+    /// Records raw stat lines of many players for the current week.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The oracle authority.
+    ///
+    UpdatePlayerScores {
+        args: UpdatePlayerScoresArgs<'a>,
+    },
+    /// Corrects a stat of an already recorded score within the root's correction window,
+    /// keeping the value of the stat before its last correction on the score.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The oracle authority.
+    ///
+    CorrectPlayerScore {
+        args: CorrectPlayerScoreArgs<'a>,
+    },
+    /// Settles the next unsettled week of a head-to-head league, updating the records
+    /// of every matchup. Anyone can crank it once the week is over.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///
+    SettleWeek {
+        args: SettleWeekArgs<'a>,
+    },
+    /// Settles the next playoff round of a league from the scores of its week, seeding
+    /// the bracket from the regular season standings first. Anyone can crank it once
+    /// the week is over.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///
+    SettlePlayoffRound {
+        args: SettlePlayoffRoundArgs<'a>,
+    },
+    /// Sets the protocol fee taken from the pot of leagues created from now on and
+    /// the treasury receiving it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The admin authority.
+    ///
+    SetProtocolFee {
+        args: SetProtocolFeeArgs<'a>,
+    },
+    /// Moves the protocol fees accrued by claims out of the bank to the treasury.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The admin authority.
+    ///   2. `[writable]` The bank.
+    ///   3. `[writable]` The treasury.
+    ///   4. `[]` System program
+    ///
+    WithdrawFees,
+    /// Leaves a league before its pick order is drawn and refunds the bid of the user, the
+    /// ids of the users after it shift down by one. The creator cancels the league instead.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer, writable]` The user receiving the refund.
+    ///   3. `[writable]` The league vault.
+    ///   4. `[]` System program
+    ///
+    ///   Leagues with a token bid also expect:
+    ///
+    ///   5. `[]` Token program
+    ///   6. `[writable]` The token account of the league vault.
+    ///   7. `[writable]` The token account of the user receiving the refund.
+    ///
+    LeaveLeague {
+        args: LeaveLeagueArgs<'a>,
+    },
+    /// Cancels a league before its draft starts and refunds the bids of every user,
+    /// the league is left uninitialized.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The league creator.
+    ///   3. `[writable]` The league vault.
+    ///   4. `[]` System program
+    ///   5. ..5+N `[writable]` The users of the league by their ids.
+    ///
+    ///   Leagues with a token bid pass the token accounts of the users instead and expect:
+    ///
+    ///   5. `[]` Token program
+    ///   6. `[writable]` The token account of the league vault.
+    ///   7. ..7+N `[writable]` The token accounts of the users of the league by their ids.
+    ///
+    CancelLeague {
+        args: CancelLeagueArgs<'a>,
+    },
+    /// Refunds the bids of every user of a league that did not fill up by its deadline, or
+    /// can no longer draft this season, the league is left uninitialized. Can be called by anyone.
+    /// A full league is refunded once its regular season ran out of weeks before every lineup
+    /// was set.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[writable]` The league vault.
+    ///   3. `[]` System program
+    ///   4. `[]` Clock sysvar
+    ///   5. ..5+N `[writable]` The users of the league by their ids.
+    ///
+    ///   Leagues with a token bid pass the token accounts of the users instead and expect:
+    ///
+    ///   5. `[]` Token program
+    ///   6. `[writable]` The token account of the league vault.
+    ///   7. ..7+N `[writable]` The token accounts of the users of the league by their ids.
+    ///
+    RefundUnfilledLeague {
+        args: RefundUnfilledLeagueArgs<'a>,
+    },
+    /// Picks for the user on the clock once their pick time ran out, taking the first player of
+    /// their draft queue, then the best ranked one, nobody in the league owns that fits the team
+    /// composition. In an auction draft gives the nominated player to the highest bidder once
+    /// the auction window is over. Can be called by anyone.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[]` Clock sysvar
+    ///
+    ///   Snake drafts also expect:
+    ///
+    ///   3. `[]` The draft queue of the user on the clock, derived from the league and the user.
+    ///
+    AutoPick {
+        args: AutoPickArgs<'a>,
+    },
+    /// Replaces the draft queue of a user, the player ids the auto-pick tries first.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[]` The league account.
+    ///   2. `[signer, writable]` The user paying the rent of the draft queue.
+    ///   3. `[writable]` The draft queue, derived from the league and the user.
+    ///   4. `[]` System program
+    ///   5. `[]` Rent sysvar
+    ///
+    SetDraftQueue {
+        args: SetDraftQueueArgs<'a>,
+    },
+    /// Puts a player up for auction in an auction draft with the opening bid of the user,
+    /// giving the previous player to its highest bidder once their auction window is over.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The user.
+    ///   3. `[]` Clock sysvar
+    ///
+    NominatePlayer {
+        args: NominatePlayerArgs<'a>,
+    },
+    /// Outbids the highest bid on the nominated player of an auction draft, restarting the
+    /// auction window.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The user.
+    ///   3. `[]` Clock sysvar
+    ///
+    PlaceBid {
+        args: PlaceBidArgs<'a>,
+    },
+    /// Drops a rostered player for a free agent nobody in the league owns once the draft is
+    /// over. Players in the lineup of the current week cannot be dropped.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The user.
+    ///
+    AddDropPlayer {
+        args: AddDropPlayerArgs<'a>,
+    },
+    /// Draws the pick order of a full league from the hash of the first slot after the one
+    /// it filled up in. Anyone can crank it. When that hash already left the slot hashes
+    /// sysvar, the order is drawn after the current slot by the next crank instead.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[]` Slot hashes sysvar
+    ///   3. `[]` Clock sysvar, starting the first pick.
+    ///
+    DrawPickOrder {
+        args: DrawPickOrderArgs<'a>,
+    },
+    /// Completes the season once all weeks of the root's sport are over.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The admin authority.
+    ///
+    CompleteSeason,
     ///
     /// Updates lineup of a user
     ///
     /// Accounts expected by this instruction:
-    ///   0. `[user address]` // check if this is correct
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The user.
     ///
     UpdateLineup {
         args: UpdateLineupArgs<'a>,
     },
+    ///
+    /// Picks a player for the user on the clock in the snake draft
+    ///
+    /// Accounts expected by this instruction:
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The user.
+    ///   3. `[]` Clock sysvar, starting the next pick.
+    ///
     PickPlayer {
         args: PickPlayerArgs<'a>,
     },
@@ -64,9 +349,9 @@ pub enum SfsInstruction<'a> {
     /// Adds a swap proposal
     ///
     /// Accounts expected by this instruction:
-    ///   0. `[writable]` The root to initialize.
-    ///   1. `[self user]` The root to initialize.
-    ///   2. `[other user]` The root to initialize.
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The user.
     ///
     ProposeSwap {
         args: ProposeSwapArgs<'a>,
@@ -75,9 +360,9 @@ pub enum SfsInstruction<'a> {
     /// Accepts a swap proposal
     ///
     /// Accounts expected by this instruction:
-    ///   0. `[writable]` The root to initialize.
-    ///   1. `[self user]` The root to initialize.
-    ///   2. `[other user]` The root to initialize.
+    ///   0. `[]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[signer]` The user.
     ///
     AcceptSwap {
         args: AcceptSwapArgs<'a>,
@@ -85,10 +370,43 @@ pub enum SfsInstruction<'a> {
     RejectSwap {
         args: RejectSwapArgs<'a>,
     },
+    /// Records a player's raw stat line for the current week.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The oracle authority.
+    ///
     UpdatePlayerScore {
         args: UpdatePlayerScoreArgs<'a>,
     },
+    /// Advances the current week.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[signer]` The admin authority.
+    ///
     IncrementWeek,
+    /// Pays the pot less the protocol fee out to the top places of a league by its
+    /// payout table, the fee accrues on the root.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The root.
+    ///   1. `[writable]` The league account.
+    ///   2. `[writable]` The league vault.
+    ///   3. `[writable]` The bank receiving the protocol fee.
+    ///   4. `[]` System program
+    ///   5. ..5+N `[writable]` The paid users from the first place down.
+    ///
+    ///   Leagues with a token bid pay the fee to the treasury instead and expect:
+    ///
+    ///   5. `[]` Token program
+    ///   6. `[writable]` The token account of the league vault.
+    ///   7. `[writable]` The token account of the treasury.
+    ///   8. ..8+N `[writable]` The token accounts of the paid users from the first place down.
+    ///
     ClaimReward {
         args: ClaimRewardArgs<'a>,
     },
@@ -107,9 +425,6 @@ impl<'a> SfsInstruction<'a> {
             2 => Self::InitializeRoot {
                 args: InitializeRootArgs::new(input, 1)?,
             },
-            3 => Self::SeedDraftSelection {
-                args: SeedDraftSelectionArgs::new(input, 1)?,
-            },
             4 => Self::StartSeason,
 
             5 => Self::CreateLeague {
@@ -141,6 +456,50 @@ impl<'a> SfsInstruction<'a> {
             14 => Self::ClaimReward {
                 args: ClaimRewardArgs::new(input, 1)?,
             },
+            15 => Self::CompleteSeason,
+            16 => Self::UpdatePlayerScores {
+                args: UpdatePlayerScoresArgs::new(input, 1)?,
+            },
+            17 => Self::CorrectPlayerScore {
+                args: CorrectPlayerScoreArgs::new(input, 1)?,
+            },
+            18 => Self::SettleWeek {
+                args: SettleWeekArgs::new(input, 1)?,
+            },
+            19 => Self::SettlePlayoffRound {
+                args: SettlePlayoffRoundArgs::new(input, 1)?,
+            },
+            20 => Self::SetProtocolFee {
+                args: SetProtocolFeeArgs::new(input, 1)?,
+            },
+            21 => Self::WithdrawFees,
+            22 => Self::LeaveLeague {
+                args: LeaveLeagueArgs::new(input, 1)?,
+            },
+            23 => Self::CancelLeague {
+                args: CancelLeagueArgs::new(input, 1)?,
+            },
+            24 => Self::RefundUnfilledLeague {
+                args: RefundUnfilledLeagueArgs::new(input, 1)?,
+            },
+            25 => Self::AutoPick {
+                args: AutoPickArgs::new(input, 1)?,
+            },
+            26 => Self::SetDraftQueue {
+                args: SetDraftQueueArgs::new(input, 1)?,
+            },
+            27 => Self::NominatePlayer {
+                args: NominatePlayerArgs::new(input, 1)?,
+            },
+            28 => Self::PlaceBid {
+                args: PlaceBidArgs::new(input, 1)?,
+            },
+            29 => Self::AddDropPlayer {
+                args: AddDropPlayerArgs::new(input, 1)?,
+            },
+            30 => Self::DrawPickOrder {
+                args: DrawPickOrderArgs::new(input, 1)?,
+            },
 
             _ => return Err(SfsError::InvalidInstruction.into()),
         })
@@ -163,11 +522,6 @@ impl<'a> SfsInstruction<'a> {
                 buf.extend_from_slice(&[0u8; InitializeRootArgs::LEN]);
                 args.copy_to(array_mut_ref![buf, 1, InitializeRootArgs::LEN]);
             }
-            Self::SeedDraftSelection { args } => {
-                buf.push(3);
-                buf.extend_from_slice(&[0u8; SeedDraftSelectionArgs::LEN]);
-                args.copy_to(array_mut_ref![buf, 1, SeedDraftSelectionArgs::LEN]);
-            }
             Self::StartSeason => {
                 buf.push(4);
             }
@@ -179,12 +533,12 @@ impl<'a> SfsInstruction<'a> {
             Self::JoinLeague { args } => {
                 buf.push(6);
                 buf.extend_from_slice(&[0u8; JoinLeagueArgs::LEN]);
-                args.copy_to(array_mut_ref![buf, 1, SeedDraftSelectionArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, JoinLeagueArgs::LEN]);
             }
             Self::UpdateLineup { args } => {
                 buf.push(7);
-                buf.extend_from_slice(&[0u8; JoinLeagueArgs::LEN]);
-                args.copy_to(array_mut_ref![buf, 1, JoinLeagueArgs::LEN]);
+                buf.extend_from_slice(&[0u8; UpdateLineupArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, UpdateLineupArgs::LEN]);
             }
             Self::PickPlayer { args } => {
                 buf.push(8);
@@ -219,6 +573,82 @@ impl<'a> SfsInstruction<'a> {
                 buf.extend_from_slice(&[0u8; ClaimRewardArgs::LEN]);
                 args.copy_to(array_mut_ref![buf, 1, ClaimRewardArgs::LEN]);
             }
+            Self::CompleteSeason => {
+                buf.push(15);
+            }
+            Self::UpdatePlayerScores { args } => {
+                buf.push(16);
+                buf.extend_from_slice(&[0u8; UpdatePlayerScoresArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, UpdatePlayerScoresArgs::LEN]);
+            }
+            Self::CorrectPlayerScore { args } => {
+                buf.push(17);
+                buf.extend_from_slice(&[0u8; CorrectPlayerScoreArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, CorrectPlayerScoreArgs::LEN]);
+            }
+            Self::SettleWeek { args } => {
+                buf.push(18);
+                buf.extend_from_slice(&[0u8; SettleWeekArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, SettleWeekArgs::LEN]);
+            }
+            Self::SettlePlayoffRound { args } => {
+                buf.push(19);
+                buf.extend_from_slice(&[0u8; SettlePlayoffRoundArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, SettlePlayoffRoundArgs::LEN]);
+            }
+            Self::SetProtocolFee { args } => {
+                buf.push(20);
+                buf.extend_from_slice(&[0u8; SetProtocolFeeArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, SetProtocolFeeArgs::LEN]);
+            }
+            Self::WithdrawFees => {
+                buf.push(21);
+            }
+            Self::LeaveLeague { args } => {
+                buf.push(22);
+                buf.extend_from_slice(&[0u8; LeaveLeagueArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, LeaveLeagueArgs::LEN]);
+            }
+            Self::CancelLeague { args } => {
+                buf.push(23);
+                buf.extend_from_slice(&[0u8; CancelLeagueArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, CancelLeagueArgs::LEN]);
+            }
+            Self::RefundUnfilledLeague { args } => {
+                buf.push(24);
+                buf.extend_from_slice(&[0u8; RefundUnfilledLeagueArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, RefundUnfilledLeagueArgs::LEN]);
+            }
+            Self::AutoPick { args } => {
+                buf.push(25);
+                buf.extend_from_slice(&[0u8; AutoPickArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, AutoPickArgs::LEN]);
+            }
+            Self::SetDraftQueue { args } => {
+                buf.push(26);
+                buf.extend_from_slice(&[0u8; SetDraftQueueArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, SetDraftQueueArgs::LEN]);
+            }
+            Self::NominatePlayer { args } => {
+                buf.push(27);
+                buf.extend_from_slice(&[0u8; NominatePlayerArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, NominatePlayerArgs::LEN]);
+            }
+            Self::PlaceBid { args } => {
+                buf.push(28);
+                buf.extend_from_slice(&[0u8; PlaceBidArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, PlaceBidArgs::LEN]);
+            }
+            Self::AddDropPlayer { args } => {
+                buf.push(29);
+                buf.extend_from_slice(&[0u8; AddDropPlayerArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, AddDropPlayerArgs::LEN]);
+            }
+            Self::DrawPickOrder { args } => {
+                buf.push(30);
+                buf.extend_from_slice(&[0u8; DrawPickOrderArgs::LEN]);
+                args.copy_to(array_mut_ref![buf, 1, DrawPickOrderArgs::LEN]);
+            }
         };
         buf
     }
@@ -228,13 +658,14 @@ impl<'a> SfsInstruction<'a> {
 pub fn add_players(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
     args: AddPlayersArgs,
 ) -> Result<Instruction, ProgramError> {
     let data = SfsInstruction::AddPlayers { args }.pack();
 
     let accounts = vec![
         AccountMeta::new(*root_pubkey, false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
     ];
 
     Ok(Instruction {
@@ -264,15 +695,18 @@ pub fn initialize_root(
     })
 }
 
-/// Creates a `SeedDraftSelection` instruction.
-pub fn seed_draft_selection(
+/// Creates a `StartSeason` instruction.
+pub fn start_season(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
-    args: SeedDraftSelectionArgs,
+    admin_pubkey: &Pubkey,
 ) -> Result<Instruction, ProgramError> {
-    let data = SfsInstruction::SeedDraftSelection { args }.pack();
+    let data = SfsInstruction::StartSeason.pack();
 
-    let accounts = vec![AccountMeta::new(*root_pubkey, false)];
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
 
     Ok(Instruction {
         program_id: *sfs_program_id,
@@ -281,14 +715,59 @@ pub fn seed_draft_selection(
     })
 }
 
-/// Creates a `StartSeason` instruction.
-pub fn start_season(
+/// Creates a `IncrementWeek` instruction.
+pub fn increment_week(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
 ) -> Result<Instruction, ProgramError> {
-    let data = SfsInstruction::StartSeason.pack();
+    let data = SfsInstruction::IncrementWeek.pack();
 
-    let accounts = vec![AccountMeta::new(*root_pubkey, false)];
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CompleteSeason` instruction.
+pub fn complete_season(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::CompleteSeason.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `UpdatePlayerScore` instruction.
+pub fn update_player_score(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    oracle_pubkey: &Pubkey,
+    args: UpdatePlayerScoreArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::UpdatePlayerScore { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*oracle_pubkey, true),
+    ];
 
     Ok(Instruction {
         program_id: *sfs_program_id,
@@ -297,15 +776,472 @@ pub fn start_season(
     })
 }
 
+/// Creates a `UpdatePlayerScores` instruction.
+pub fn update_player_scores(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    oracle_pubkey: &Pubkey,
+    args: UpdatePlayerScoresArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::UpdatePlayerScores { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*oracle_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CorrectPlayerScore` instruction.
+pub fn correct_player_score(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    oracle_pubkey: &Pubkey,
+    args: CorrectPlayerScoreArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::CorrectPlayerScore { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*oracle_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `SettleWeek` instruction.
+pub fn settle_week(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    args: SettleWeekArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::SettleWeek { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `SettlePlayoffRound` instruction.
+pub fn settle_playoff_round(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    args: SettlePlayoffRoundArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::SettlePlayoffRound { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `SetProtocolFee` instruction.
+pub fn set_protocol_fee(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
+    args: SetProtocolFeeArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::SetProtocolFee { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `WithdrawFees` instruction.
+pub fn withdraw_fees(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    admin_pubkey: &Pubkey,
+    treasury_pubkey: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::WithdrawFees.pack();
+    let (bank_pubkey, _) = Pubkey::find_program_address(&[&[0]], sfs_program_id);
+
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new_readonly(*admin_pubkey, true),
+        AccountMeta::new(bank_pubkey, false),
+        AccountMeta::new(*treasury_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `LeaveLeague` instruction, token leagues pass the token accounts of the vault
+/// and of the user.
+pub fn leave_league(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    token_pubkeys: Option<(&Pubkey, &Pubkey)>,
+    args: LeaveLeagueArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::LeaveLeague { args }.pack();
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new(*user_pubkey, true),
+        AccountMeta::new(*vault_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if let Some((token_vault_pubkey, user_token_pubkey)) = token_pubkeys {
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+        accounts.push(AccountMeta::new(*token_vault_pubkey, false));
+        accounts.push(AccountMeta::new(*user_token_pubkey, false));
+    }
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `CancelLeague` instruction, token leagues pass the token account of the vault
+/// and refund the token accounts of the users.
+#[allow(clippy::too_many_arguments)]
+pub fn cancel_league(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    creator_pubkey: &Pubkey,
+    token_vault_pubkey: Option<&Pubkey>,
+    refund_pubkeys: &[Pubkey],
+    args: CancelLeagueArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::CancelLeague { args }.pack();
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*creator_pubkey, true),
+        AccountMeta::new(*vault_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    if let Some(token_vault_pubkey) = token_vault_pubkey {
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+        accounts.push(AccountMeta::new(*token_vault_pubkey, false));
+    }
+    for refund_pubkey in refund_pubkeys {
+        accounts.push(AccountMeta::new(*refund_pubkey, false));
+    }
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `RefundUnfilledLeague` instruction, token leagues pass the token account of the
+/// vault and refund the token accounts of the users.
+pub fn refund_unfilled_league(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    token_vault_pubkey: Option<&Pubkey>,
+    refund_pubkeys: &[Pubkey],
+    args: RefundUnfilledLeagueArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::RefundUnfilledLeague { args }.pack();
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new(*vault_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(token_vault_pubkey) = token_vault_pubkey {
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+        accounts.push(AccountMeta::new(*token_vault_pubkey, false));
+    }
+    for refund_pubkey in refund_pubkeys {
+        accounts.push(AccountMeta::new(*refund_pubkey, false));
+    }
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an `AutoPick` instruction, snake drafts pass the draft queue of the user on
+/// the clock.
+pub fn auto_pick(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    draft_queue_pubkey: Option<&Pubkey>,
+    args: AutoPickArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::AutoPick { args }.pack();
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(draft_queue_pubkey) = draft_queue_pubkey {
+        accounts.push(AccountMeta::new_readonly(*draft_queue_pubkey, false));
+    }
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `SetDraftQueue` instruction.
+pub fn set_draft_queue(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    draft_queue_pubkey: &Pubkey,
+    args: SetDraftQueueArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::SetDraftQueue { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new_readonly(*league_pubkey, false),
+        AccountMeta::new(*user_pubkey, true),
+        AccountMeta::new(*draft_queue_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `NominatePlayer` instruction.
+pub fn nominate_player(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    args: NominatePlayerArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::NominatePlayer { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*user_pubkey, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `PlaceBid` instruction.
+pub fn place_bid(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    args: PlaceBidArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::PlaceBid { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*user_pubkey, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates an `AddDropPlayer` instruction.
+pub fn add_drop_player(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    args: AddDropPlayerArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::AddDropPlayer { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*user_pubkey, true),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `DrawPickOrder` instruction.
+pub fn draw_pick_order(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    args: DrawPickOrderArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::DrawPickOrder { args }.pack();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ClaimReward` instruction.
+pub fn claim_reward(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    user_pubkeys: &[Pubkey],
+    args: ClaimRewardArgs,
+) -> Result<Instruction, ProgramError> {
+    let data = SfsInstruction::ClaimReward { args }.pack();
+    let (bank_pubkey, _) = Pubkey::find_program_address(&[&[0]], sfs_program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new(*vault_pubkey, false),
+        AccountMeta::new(bank_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for user_pubkey in user_pubkeys {
+        accounts.push(AccountMeta::new(*user_pubkey, false));
+    }
+
+    Ok(Instruction {
+        program_id: *sfs_program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Creates a `ClaimReward` instruction for a league with a token bid.
+#[allow(clippy::too_many_arguments)]
+pub fn claim_token_reward(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    token_vault_pubkey: &Pubkey,
+    treasury_token_pubkey: &Pubkey,
+    user_token_pubkeys: &[Pubkey],
+    args: ClaimRewardArgs,
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = claim_reward(
+        sfs_program_id,
+        root_pubkey,
+        league_pubkey,
+        vault_pubkey,
+        &[],
+        args,
+    )?;
+    instruction.accounts.extend(vec![
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(*token_vault_pubkey, false),
+        AccountMeta::new(*treasury_token_pubkey, false),
+    ]);
+    for user_token_pubkey in user_token_pubkeys {
+        instruction
+            .accounts
+            .push(AccountMeta::new(*user_token_pubkey, false));
+    }
+    Ok(instruction)
+}
+
 /// Creates a `CreateLeague` instruction.
 pub fn create_league(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
     args: CreateLeagueArgs,
 ) -> Result<Instruction, ProgramError> {
     let data = SfsInstruction::CreateLeague { args }.pack();
 
-    let accounts = vec![AccountMeta::new(*root_pubkey, false)];
+    let accounts = vec![
+        AccountMeta::new(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new(*user_pubkey, true),
+        AccountMeta::new(*vault_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
 
     Ok(Instruction {
         program_id: *sfs_program_id,
@@ -314,15 +1250,57 @@ pub fn create_league(
     })
 }
 
+/// Creates a `CreateLeague` instruction for a league with a token bid.
+#[allow(clippy::too_many_arguments)]
+pub fn create_token_league(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    user_token_pubkey: &Pubkey,
+    token_vault_pubkey: &Pubkey,
+    args: CreateLeagueArgs,
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = create_league(
+        sfs_program_id,
+        root_pubkey,
+        league_pubkey,
+        vault_pubkey,
+        user_pubkey,
+        args,
+    )?;
+    instruction.accounts.extend(vec![
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(*user_token_pubkey, false),
+        AccountMeta::new(*token_vault_pubkey, false),
+    ]);
+    Ok(instruction)
+}
+
 /// Creates a `JoinLeague` instruction.
 pub fn join_league(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    password_pubkey: Option<&Pubkey>,
     args: JoinLeagueArgs,
 ) -> Result<Instruction, ProgramError> {
     let data = SfsInstruction::JoinLeague { args }.pack();
 
-    let accounts = vec![AccountMeta::new(*root_pubkey, false)];
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*root_pubkey, false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new(*user_pubkey, true),
+        AccountMeta::new(*vault_pubkey, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(password_pubkey) = password_pubkey {
+        accounts.push(AccountMeta::new_readonly(*password_pubkey, true));
+    }
 
     Ok(Instruction {
         program_id: *sfs_program_id,
@@ -331,17 +1309,50 @@ pub fn join_league(
     })
 }
 
+/// Creates a `JoinLeague` instruction for a league with a token bid.
+#[allow(clippy::too_many_arguments)]
+pub fn join_token_league(
+    sfs_program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    vault_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    password_pubkey: Option<&Pubkey>,
+    user_token_pubkey: &Pubkey,
+    token_vault_pubkey: &Pubkey,
+    args: JoinLeagueArgs,
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = join_league(
+        sfs_program_id,
+        root_pubkey,
+        league_pubkey,
+        vault_pubkey,
+        user_pubkey,
+        password_pubkey,
+        args,
+    )?;
+    instruction.accounts.extend(vec![
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(*user_token_pubkey, false),
+        AccountMeta::new(*token_vault_pubkey, false),
+    ]);
+    Ok(instruction)
+}
+
 /// Creates a `UpdateLineup` instruction.
 pub fn update_lineup(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
     args: UpdateLineupArgs,
 ) -> Result<Instruction, ProgramError> {
     let data = SfsInstruction::UpdateLineup { args }.pack();
 
     let accounts = vec![
         AccountMeta::new(*root_pubkey, false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*user_pubkey, true),
     ];
 
     Ok(Instruction {
@@ -355,13 +1366,17 @@ pub fn update_lineup(
 pub fn pick_player(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
     args: PickPlayerArgs,
 ) -> Result<Instruction, ProgramError> {
     let data = SfsInstruction::PickPlayer { args }.pack();
 
     let accounts = vec![
         AccountMeta::new(*root_pubkey, false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*user_pubkey, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
     ];
 
     Ok(Instruction {
@@ -375,13 +1390,16 @@ pub fn pick_player(
 pub fn propose_swap(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
     args: ProposeSwapArgs,
 ) -> Result<Instruction, ProgramError> {
     let data = SfsInstruction::ProposeSwap { args }.pack();
 
     let accounts = vec![
         AccountMeta::new(*root_pubkey, false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*user_pubkey, true),
     ];
 
     Ok(Instruction {
@@ -395,13 +1413,16 @@ pub fn propose_swap(
 pub fn accept_swap(
     sfs_program_id: &Pubkey,
     root_pubkey: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
     args: AcceptSwapArgs,
 ) -> Result<Instruction, ProgramError> {
     let data = SfsInstruction::AcceptSwap { args }.pack();
 
     let accounts = vec![
         AccountMeta::new(*root_pubkey, false),
-        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(*league_pubkey, false),
+        AccountMeta::new_readonly(*user_pubkey, true),
     ];
 
     Ok(Instruction {
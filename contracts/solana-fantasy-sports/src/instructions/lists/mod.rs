@@ -1,6 +1,6 @@
 pub mod player_list;
 pub use player_list::*;
-pub mod pick_order_list;
-pub use pick_order_list::*;
 pub mod active_player_list;
 pub use active_player_list::*;
+pub mod player_score_list;
+pub use player_score_list::*;
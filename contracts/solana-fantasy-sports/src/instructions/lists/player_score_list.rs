@@ -0,0 +1,65 @@
+//! State transition types
+
+use crate::error::SfsError;
+use crate::instructions::*;
+use crate::state::consts::*;
+use arrayref::{array_mut_ref, array_ref, array_refs};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct PlayerScoreList<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> PlayerScoreList<'a> {
+    pub const ITEM_SIZE: usize = PlayerScore::LEN;
+    pub const ITEM_CAPACITY: u8 = MAX_SCORES_PER_INSTRUCTION;
+    pub const LEN: usize = 1 + PlayerScoreList::ITEM_SIZE * PlayerScoreList::ITEM_CAPACITY as usize;
+    fn slice<'b>(
+        &self,
+        data: &'b [u8],
+    ) -> (
+        &'b [u8; 1],
+        &'b [u8; PlayerScoreList::ITEM_SIZE * PlayerScoreList::ITEM_CAPACITY as usize],
+    ) {
+        array_refs![
+            array_ref![data, self.offset, PlayerScoreList::LEN],
+            1,
+            PlayerScoreList::ITEM_SIZE * PlayerScoreList::ITEM_CAPACITY as usize
+        ]
+    }
+
+    pub fn get_count(&self) -> u8 {
+        self.slice(&self.data.borrow()).0[0]
+    }
+
+    pub fn get(&self, i: u8) -> Result<PlayerScore<'a>, ProgramError> {
+        if i >= self.get_count() || i >= PlayerScoreList::ITEM_CAPACITY {
+            return Err(SfsError::IndexOutOfRange.into());
+        }
+        PlayerScore::new(
+            self.data,
+            self.offset + 1 + i as usize * PlayerScoreList::ITEM_SIZE,
+        )
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, PlayerScoreList::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            PlayerScoreList::LEN
+        ]);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<PlayerScoreList<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(PlayerScoreList { data, offset })
+    }
+}
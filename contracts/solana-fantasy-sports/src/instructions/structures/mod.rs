@@ -1,2 +1,4 @@
 pub mod player;
-pub use player::*;
\ No newline at end of file
+pub use player::*;
+pub mod player_score;
+pub use player_score::*;
@@ -16,9 +16,9 @@ pub struct Player<'a> {
     offset: usize,
 }
 impl<'a> Player<'a> {
-    pub const LEN: usize = 2 + 1;
-    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; 2], &'b [u8; 1]) {
-        array_refs![array_ref![data, self.offset, Player::LEN], 2, 1]
+    pub const LEN: usize = 2 + 1 + 2;
+    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; 2], &'b [u8; 1], &'b [u8; 2]) {
+        array_refs![array_ref![data, self.offset, Player::LEN], 2, 1, 2]
     }
 
     pub fn get_external_id(&self) -> u16 {
@@ -30,6 +30,11 @@ impl<'a> Player<'a> {
             .or(Err(ProgramError::InvalidInstructionData))
     }
 
+    /// Draft ranking of the player, lower is better and zero for unranked
+    pub fn get_rank(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&self.data.borrow()).2)
+    }
+
     pub fn copy_to(&self, to: &mut [u8]) {
         let src = self.data.borrow();
         array_mut_ref![to, self.offset, Player::LEN].copy_from_slice(array_ref![
@@ -39,10 +44,11 @@ impl<'a> Player<'a> {
         ]);
     }
 
-    pub fn pack(id: u16, position: Position) -> [u8; Player::LEN] {
+    pub fn pack(id: u16, position: Position, rank: u16) -> [u8; Player::LEN] {
         let mut buf = [0u8; Player::LEN];
         LittleEndian::write_u16(&mut buf, id);
         buf[2] = position as u8;
+        LittleEndian::write_u16(&mut buf[3..], rank);
         buf
     }
 
@@ -0,0 +1,61 @@
+//! State transition types
+use crate::instructions::*;
+use crate::state::consts::*;
+use arrayref::{array_mut_ref, array_ref, array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct PlayerScore<'a> {
+    data: &'a RefCell<&'a [u8]>,
+    offset: usize,
+}
+impl<'a> PlayerScore<'a> {
+    pub const STATS_LEN: usize = 2 * STATS_COUNT as usize;
+    pub const LEN: usize = 2 + PlayerScore::STATS_LEN;
+    fn slice<'b>(&self, data: &'b [u8]) -> (&'b [u8; 2], &'b [u8; PlayerScore::STATS_LEN]) {
+        array_refs![
+            array_ref![data, self.offset, PlayerScore::LEN],
+            2,
+            PlayerScore::STATS_LEN
+        ]
+    }
+
+    pub fn get_player_id(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&self.data.borrow()).0)
+    }
+
+    /// Raw stat line for the current week, indexed by `Stat`
+    pub fn get_stats(&self) -> [i16; STATS_COUNT as usize] {
+        let mut stats = [0i16; STATS_COUNT as usize];
+        LittleEndian::read_i16_into(self.slice(&self.data.borrow()).1, &mut stats);
+        stats
+    }
+
+    pub fn copy_to(&self, to: &mut [u8]) {
+        let src = self.data.borrow();
+        array_mut_ref![to, self.offset, PlayerScore::LEN].copy_from_slice(array_ref![
+            src,
+            self.offset,
+            PlayerScore::LEN
+        ]);
+    }
+
+    pub fn pack(player_id: u16, stats: &[i16; STATS_COUNT as usize]) -> [u8; PlayerScore::LEN] {
+        let mut buf = [0u8; PlayerScore::LEN];
+        LittleEndian::write_u16(&mut buf, player_id);
+        LittleEndian::write_i16_into(stats, &mut buf[2..]);
+        buf
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a [u8]>,
+        offset: usize,
+    ) -> Result<PlayerScore<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        Ok(PlayerScore { data, offset })
+    }
+}
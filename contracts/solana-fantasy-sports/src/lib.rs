@@ -10,6 +10,7 @@ pub mod error;
 pub mod instructions;
 pub mod native_mint;
 pub mod processor;
+pub mod spl_token;
 pub mod state;
 
 // Export current solana-sdk types for downstream users who may also be building with a different
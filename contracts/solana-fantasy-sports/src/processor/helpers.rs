@@ -4,10 +4,12 @@ use crate::{
     error::SfsError,
     instructions,
     instructions::{arguments::*, instruction::*},
-    processor,
+    processor, spl_token,
+    spl_token::TokenAccount,
     state::*,
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use byteorder::{ByteOrder, LittleEndian};
 use num_traits::FromPrimitive;
 use solana_program::program::invoke;
 use solana_program::program::invoke_signed;
@@ -15,16 +17,18 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     decode_error::DecodeError,
     entrypoint::ProgramResult,
+    hash::hashv,
     info,
     instruction::{AccountMeta, Instruction},
     program_error::{PrintProgramError, ProgramError},
     program_option::COption,
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
-    system_instruction::SystemInstruction,
-    sysvar::{rent::Rent, Sysvar},
+    system_instruction::{allocate, assign, create_account, transfer, SystemInstruction},
+    sysvar::{rent::Rent, slot_hashes, Sysvar},
 };
 use std::cell::RefCell;
+use std::collections::BTreeSet;
 use std::convert::TryInto;
 
 /// Validates owner(s) are present
@@ -51,6 +55,739 @@ pub fn validate_bank(program_id: &Pubkey, bank_account_info: &AccountInfo) -> Pr
     Ok(())
 }
 
+/// Derives the league account address from the root and league index
+pub fn find_league_address(
+    program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_index: u16,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[root_pubkey.as_ref(), &league_index.to_le_bytes()], program_id)
+}
+
+/// Derives the escrow vault address of a league from the root and league index
+pub fn find_vault_address(
+    program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_index: u16,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VAULT_SEED,
+            root_pubkey.as_ref(),
+            &league_index.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Creates a rent exempt account owned by the program at a program derived address.
+/// The address is known in advance so anyone can send lamports to it first, which makes
+/// `create_account` fail. A funded address is topped up, allocated and assigned instead.
+pub fn create_pda_account<'a>(
+    payer_account_info: &AccountInfo<'a>,
+    new_account_info: &AccountInfo<'a>,
+    system_program_account_info: &AccountInfo<'a>,
+    rent: &Rent,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let minimum_balance = rent.minimum_balance(space);
+    let lamports = new_account_info.lamports();
+    if lamports == 0 {
+        let instruction = create_account(
+            payer_account_info.key,
+            new_account_info.key,
+            minimum_balance,
+            space as u64,
+            owner,
+        );
+        let accounts = [
+            payer_account_info.clone(),
+            new_account_info.clone(),
+            system_program_account_info.clone(),
+        ];
+        return invoke_signed(&instruction, &accounts, &[signer_seeds]);
+    }
+
+    if lamports < minimum_balance {
+        let instruction = transfer(
+            payer_account_info.key,
+            new_account_info.key,
+            minimum_balance - lamports,
+        );
+        let accounts = [
+            payer_account_info.clone(),
+            new_account_info.clone(),
+            system_program_account_info.clone(),
+        ];
+        invoke(&instruction, &accounts)?;
+    }
+    let accounts = [
+        new_account_info.clone(),
+        system_program_account_info.clone(),
+    ];
+    invoke_signed(
+        &allocate(new_account_info.key, space as u64),
+        &accounts,
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &assign(new_account_info.key, owner),
+        &accounts,
+        &[signer_seeds],
+    )
+}
+
+/// Validates league vault address and returns its bump seed
+pub fn validate_vault(
+    program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_index: u16,
+    vault_account_info: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let (vault_pubkey, bump_seed) = find_vault_address(program_id, root_pubkey, league_index);
+    if vault_pubkey != *vault_account_info.key {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    Ok(bump_seed)
+}
+
+/// Derives the draft queue address of a user from the league address and the user wallet
+pub fn find_draft_queue_address(
+    program_id: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DRAFT_QUEUE_SEED,
+            league_pubkey.as_ref(),
+            user_pubkey.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Queued player ids of the user, empty when the user never set a draft queue
+pub fn get_draft_queue<'a>(
+    program_id: &Pubkey,
+    league_pubkey: &Pubkey,
+    user_pubkey: &Pubkey,
+    draft_queue_account_info: &'a AccountInfo<'a>,
+) -> Result<Vec<u16>, ProgramError> {
+    let (draft_queue_pubkey, _) = find_draft_queue_address(program_id, league_pubkey, user_pubkey);
+    if draft_queue_pubkey != *draft_queue_account_info.key {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    if draft_queue_account_info.owner != program_id {
+        return Ok(vec![]);
+    }
+    Ok(DraftQueueList::new(&draft_queue_account_info.data, 0)?.get_player_ids())
+}
+
+/// Checks the vault holds the bids of every user of the league until the reward is paid out,
+/// the token account of the vault is checked instead for token bids.
+/// Amounts sent to the vault on top of the bids are tolerated so they cannot block payouts.
+pub fn validate_vault_balance(league: &League, vault_account_info: &AccountInfo) -> ProgramResult {
+    let expected = if league.get_is_reward_claimed()? {
+        0
+    } else {
+        league.get_pot()?
+    };
+    let balance = if league.has_token_bid() {
+        TokenAccount::unpack(vault_account_info)?.amount
+    } else {
+        vault_account_info.lamports()
+    };
+    if balance < expected {
+        return Err(SfsError::VaultBalanceMismatch.into());
+    }
+    Ok(())
+}
+
+/// Transfers lamports out of a league vault
+pub fn transfer_from_vault<'a>(
+    root_pubkey: &Pubkey,
+    league_index: u16,
+    bump_seed: u8,
+    vault_account_info: &AccountInfo<'a>,
+    to_account_info: &AccountInfo<'a>,
+    system_program_account_info: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let instruction = transfer(vault_account_info.key, to_account_info.key, amount);
+    let accounts = [
+        vault_account_info.clone(),
+        to_account_info.clone(),
+        system_program_account_info.clone(),
+    ];
+    invoke_signed(
+        &instruction,
+        &accounts,
+        &[&[
+            VAULT_SEED,
+            root_pubkey.as_ref(),
+            &league_index.to_le_bytes(),
+            &[bump_seed],
+        ]],
+    )
+}
+
+/// Validates the token program account
+pub fn validate_token_program(token_program_account_info: &AccountInfo) -> ProgramResult {
+    if *token_program_account_info.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Validates a token account holds the mint and belongs to the owner
+pub fn validate_token_account(
+    token_account_info: &AccountInfo,
+    mint_pubkey: &Pubkey,
+    owner_pubkey: &Pubkey,
+) -> ProgramResult {
+    let token_account = TokenAccount::unpack(token_account_info)?;
+    if token_account.mint != *mint_pubkey || token_account.owner != *owner_pubkey {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    Ok(())
+}
+
+/// Transfers tokens out of a token account of the signing authority
+pub fn transfer_tokens<'a>(
+    from_account_info: &AccountInfo<'a>,
+    to_account_info: &AccountInfo<'a>,
+    authority_account_info: &AccountInfo<'a>,
+    token_program_account_info: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let instruction = spl_token::transfer(
+        from_account_info.key,
+        to_account_info.key,
+        authority_account_info.key,
+        amount,
+    );
+    let accounts = [
+        from_account_info.clone(),
+        to_account_info.clone(),
+        authority_account_info.clone(),
+        token_program_account_info.clone(),
+    ];
+    invoke(&instruction, &accounts)
+}
+
+/// Transfers tokens out of the token account of a league vault
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_tokens_from_vault<'a>(
+    root_pubkey: &Pubkey,
+    league_index: u16,
+    bump_seed: u8,
+    vault_account_info: &AccountInfo<'a>,
+    token_vault_account_info: &AccountInfo<'a>,
+    to_account_info: &AccountInfo<'a>,
+    token_program_account_info: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let instruction = spl_token::transfer(
+        token_vault_account_info.key,
+        to_account_info.key,
+        vault_account_info.key,
+        amount,
+    );
+    let accounts = [
+        token_vault_account_info.clone(),
+        to_account_info.clone(),
+        vault_account_info.clone(),
+        token_program_account_info.clone(),
+    ];
+    invoke_signed(
+        &instruction,
+        &accounts,
+        &[&[
+            VAULT_SEED,
+            root_pubkey.as_ref(),
+            &league_index.to_le_bytes(),
+            &[bump_seed],
+        ]],
+    )
+}
+
+/// Reads the token program and the token account of the vault passed to instructions on
+/// leagues with a token bid
+pub fn next_token_vault_account_infos<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    league: &League,
+    account_info_iter: &mut I,
+) -> Result<Option<(&'a AccountInfo<'b>, &'a AccountInfo<'b>)>, ProgramError> {
+    if !league.has_token_bid() {
+        return Ok(None);
+    }
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let token_vault_account_info = next_account_info(account_info_iter)?;
+    validate_token_program(token_program_account_info)?;
+    if *token_vault_account_info.key != league.get_token_vault() {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    Ok(Some((token_program_account_info, token_vault_account_info)))
+}
+
+/// Pays an amount out of a league vault to the user, token bids are paid to a token
+/// account of the user
+#[allow(clippy::too_many_arguments)]
+pub fn pay_from_vault<'a>(
+    root_pubkey: &Pubkey,
+    league: &League,
+    league_index: u16,
+    bump_seed: u8,
+    vault_account_info: &AccountInfo<'a>,
+    system_program_account_info: &AccountInfo<'a>,
+    token_account_infos: Option<(&AccountInfo<'a>, &AccountInfo<'a>)>,
+    user_pubkey: &Pubkey,
+    to_account_info: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    match token_account_infos {
+        Some((token_program_account_info, token_vault_account_info)) => {
+            validate_token_account(to_account_info, &league.get_mint(), user_pubkey)?;
+            transfer_tokens_from_vault(
+                root_pubkey,
+                league_index,
+                bump_seed,
+                vault_account_info,
+                token_vault_account_info,
+                to_account_info,
+                token_program_account_info,
+                amount,
+            )
+        }
+        None => {
+            if *to_account_info.key != *user_pubkey {
+                return Err(SfsError::InvalidInstruction.into());
+            }
+            transfer_from_vault(
+                root_pubkey,
+                league_index,
+                bump_seed,
+                vault_account_info,
+                to_account_info,
+                system_program_account_info,
+                amount,
+            )
+        }
+    }
+}
+
+/// Refunds the bids of every user of a league out of its vault and leaves the league
+/// uninitialized. The refunded accounts follow the token accounts of the vault by user ids.
+pub fn close_league<'a, I: Iterator<Item = &'a AccountInfo<'a>>>(
+    program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_index: u16,
+    league: &League,
+    vault_account_info: &'a AccountInfo<'a>,
+    system_program_account_info: &'a AccountInfo<'a>,
+    account_info_iter: &mut I,
+) -> ProgramResult {
+    let vault_bump_seed = validate_vault(program_id, root_pubkey, league_index, vault_account_info)?;
+    let token_account_infos = next_token_vault_account_infos(league, account_info_iter)?;
+    validate_vault_balance(
+        league,
+        token_account_infos.map_or(vault_account_info, |(_, token_vault)| token_vault),
+    )?;
+
+    let user_states = league.get_user_states()?;
+    for user_id in 1..user_states.get_count() + 1 {
+        pay_from_vault(
+            root_pubkey,
+            league,
+            league_index,
+            vault_bump_seed,
+            vault_account_info,
+            system_program_account_info,
+            token_account_infos,
+            &user_states.get_by_id(user_id)?.get_pub_key(),
+            next_account_info(account_info_iter)?,
+            league.get_bid(),
+        )?;
+    }
+    league.set_is_initialized(false);
+    Ok(())
+}
+
+/// Hash of the first slot produced after the given one from the slot hashes sysvar, `None`
+/// when the sysvar no longer reaches back to the given slot
+pub fn get_slot_hash_after(
+    slot_hashes_account_info: &AccountInfo,
+    slot: u64,
+) -> Result<Option<[u8; 32]>, ProgramError> {
+    if *slot_hashes_account_info.key != slot_hashes::id() {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    // Serialized as the entries count followed by (slot, hash) entries, newest first
+    let data = slot_hashes_account_info.data.borrow();
+    if data.len() < 8 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let entries_count = LittleEndian::read_u64(&data[0..8]) as usize;
+    if data.len() < 8 + entries_count * (8 + 32) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mut next_hash = None;
+    for i in 0..entries_count {
+        let entry = array_ref![data, 8 + i * (8 + 32), 8 + 32];
+        if LittleEndian::read_u64(&entry[0..8]) <= slot {
+            // Nothing was produced after the slot yet
+            return match next_hash {
+                Some(hash) => Ok(Some(hash)),
+                None => Err(SfsError::InvalidState.into()),
+            };
+        }
+        next_hash = Some(*array_ref![entry, 8, 32]);
+    }
+    // The first slot after the given one may have left the sysvar already
+    Ok(None)
+}
+
+/// Shuffles the draft order of a full league with a seed mixed from a slot hash and the league
+/// address, so leagues drawing from the same slot get different orders. The slot hash is
+/// produced after the league filled up, so neither the user filling it nor the crank can
+/// choose it.
+pub fn set_random_pick_order(
+    league: &League,
+    league_pubkey: &Pubkey,
+    slot_hash: &[u8; 32],
+) -> ProgramResult {
+    let seed = hashv(&[slot_hash, league_pubkey.as_ref()]).to_bytes();
+    let users_count = league.get_user_states()?.get_count();
+    let mut order = (1..users_count + 1).collect::<Vec<u8>>();
+    for i in (1..order.len()).rev() {
+        order.swap(i, seed[i] as usize % (i + 1));
+    }
+    let pick_order = league.get_pick_order()?;
+    for (i, user_id) in order.iter().enumerate() {
+        pick_order.set(i as u8, *user_id);
+    }
+    Ok(())
+}
+
+/// Validates nobody in the league owns the player
+pub fn validate_player_available(league: &League, player_id: u16) -> ProgramResult {
+    let user_states = league.get_user_states()?;
+    for i in 1..user_states.get_count() + 1 {
+        if user_states.get_by_id(i)?.get_user_players()?.contains(player_id) {
+            return Err(SfsError::AlreadyInUse.into());
+        }
+    }
+    Ok(())
+}
+
+/// Ids of the players owned by anyone in the league
+pub fn get_drafted_player_ids(league: &League) -> Result<BTreeSet<u16>, ProgramError> {
+    let user_states = league.get_user_states()?;
+    let mut player_ids = BTreeSet::new();
+    for i in 1..user_states.get_count() + 1 {
+        let user_players = user_states.get_by_id(i)?.get_user_players()?;
+        for j in 0..UserPlayerList::ITEM_COUNT {
+            let player_id = user_players.get(j);
+            if player_id != 0 {
+                player_ids.insert(player_id);
+            }
+        }
+    }
+    Ok(player_ids)
+}
+
+/// Validates the lineup conforms to the position options of the league
+pub fn validate_lineup_positions(
+    players: &PlayerList,
+    league: &League,
+    lineup: &ActivePlayersList,
+) -> ProgramResult {
+    let active_players_count = league.get_active_players_count();
+    let mut lineup_positions = Vec::<Position>::with_capacity(active_players_count as usize);
+    for i in 0..active_players_count {
+        lineup_positions.push(players.get_by_id(lineup.get(i))?.get_position()?);
+    }
+    league
+        .get_position_options()?
+        .validate_lineup(&lineup_positions)
+}
+
+/// Puts the player into the current round of the user unless anyone in the league owns them
+/// already, leaving the slot empty when the team composition would break
+pub fn draft_player(
+    players: &PlayerList,
+    league: &League,
+    user_id: u8,
+    player_id: u16,
+) -> ProgramResult {
+    validate_player_available(league, player_id)?;
+    draft_available_player(players, league, user_id, player_id)
+}
+
+/// Puts a player nobody in the league owns into the current round of the user, leaving the
+/// slot empty when the team composition would break
+pub fn draft_available_player(
+    players: &PlayerList,
+    league: &League,
+    user_id: u8,
+    player_id: u16,
+) -> ProgramResult {
+    let round = league.get_pick_round()?;
+    let user_players = league
+        .get_user_states()?
+        .get_by_id(user_id)?
+        .get_user_players()?;
+    user_players.set(round, player_id);
+    let result = user_players.validate_team_composition(
+        players,
+        &league.get_composition_rules()?,
+        league.get_team_players_count(),
+    );
+    if result.is_err() {
+        user_players.set(round, 0);
+    }
+    result
+}
+
+/// Moves the draft to the next pick and puts it on the clock
+pub fn start_next_pick(league: &League, unix_timestamp: i64) {
+    league.set_current_pick(league.get_current_pick() + 1);
+    league.set_pick_started_at(unix_timestamp);
+}
+
+/// Roster slots of the user not filled yet, auction drafts fill them in order
+pub fn get_open_roster_slots(user_players: &UserPlayerList, roster_size: u8) -> Vec<u8> {
+    (0..roster_size)
+        .filter(|i| user_players.get(*i) == 0)
+        .collect()
+}
+
+/// Validates the user could win the player at the bid in an auction draft: the player fits
+/// the next empty roster slot and the budget keeps at least 1 for every other empty slot
+pub fn validate_auction_bid(
+    players: &PlayerList,
+    league: &League,
+    user_id: u8,
+    player_id: u16,
+    bid: u32,
+) -> ProgramResult {
+    let user_state = league.get_user_states()?.get_by_id(user_id)?;
+    let user_players = user_state.get_user_players()?;
+    let open_slots = get_open_roster_slots(&user_players, league.get_team_players_count());
+    if open_slots.is_empty() {
+        return Err(SfsError::InvalidState.into());
+    }
+    if bid == 0 || bid as u64 + open_slots.len() as u64 - 1 > user_state.get_budget() as u64 {
+        return Err(SfsError::InsufficientFunds.into());
+    }
+
+    user_players.set(open_slots[0], player_id);
+    let result = user_players.validate_team_composition(
+        players,
+        &league.get_composition_rules()?,
+        league.get_team_players_count(),
+    );
+    user_players.set(open_slots[0], 0);
+    result
+}
+
+/// Gives the nominated player to the highest bidder once the auction window is over
+pub fn close_auction(league: &League, unix_timestamp: i64) -> ProgramResult {
+    let player_id = league.get_nominated_player_id();
+    if player_id == 0 {
+        return Err(SfsError::InvalidState.into());
+    }
+    if unix_timestamp < league.get_auction_deadline() {
+        return Err(SfsError::AuctionInProgress.into());
+    }
+
+    let user_state = league
+        .get_user_states()?
+        .get_by_id(league.get_high_bidder_id())?;
+    let user_players = user_state.get_user_players()?;
+    let slot = *get_open_roster_slots(&user_players, league.get_team_players_count())
+        .first()
+        .ok_or(SfsError::InvalidState)?;
+    user_players.set(slot, player_id);
+    user_state.set_budget(
+        user_state
+            .get_budget()
+            .checked_sub(league.get_high_bid())
+            .ok_or(SfsError::InsufficientFunds)?,
+    );
+    league.set_nominated_player_id(0);
+    league.set_high_bid(0);
+    league.set_high_bidder_id(0);
+    // Every won player counts as a pick, the draft is over once all rosters are full
+    league.set_current_pick(league.get_current_pick() + 1);
+    Ok(())
+}
+
+/// Player ids from the best ranked to the worst, unranked players go last
+pub fn get_players_by_rank(players: &PlayerList) -> Result<Vec<u16>, ProgramError> {
+    let mut ranked = Vec::with_capacity(players.get_count() as usize);
+    for id in 1..players.get_count() + 1 {
+        let rank = players.get_by_id(id)?.get_rank();
+        ranked.push((rank == 0, rank, id));
+    }
+    ranked.sort_unstable();
+    Ok(ranked.into_iter().map(|(_, _, id)| id).collect())
+}
+
+/// Validates league account address and returns the initialized league stored in it
+pub fn get_league<'a>(
+    program_id: &Pubkey,
+    root_pubkey: &Pubkey,
+    league_index: u16,
+    league_account_info: &'a AccountInfo<'a>,
+) -> Result<League<'a>, ProgramError> {
+    let (league_pubkey, _) = find_league_address(program_id, root_pubkey, league_index);
+    if league_pubkey != *league_account_info.key {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    if league_account_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let league = League::new(&league_account_info.data, 0)?;
+    if !league.get_is_initialized()? {
+        return Err(SfsError::LeagueNotInitialized.into());
+    }
+    Ok(league)
+}
+
+/// Fantasy points of the user's lineup in the week, in hundredths of a point
+pub fn get_week_points(
+    players: &PlayerList,
+    league: &League,
+    user_state: &UserState,
+    week: u8,
+) -> Result<i64, ProgramError> {
+    let scoring_rules = league.get_scoring_rules()?;
+    let lineup = user_state.get_lineups()?.get_by_week(week)?;
+    let mut points = 0i64;
+    for i in 0..league.get_active_players_count() {
+        let score = players
+            .get_by_id(lineup.get(i))?
+            .get_scores()?
+            .get_by_week(week)?;
+        points = points
+            .checked_add(scoring_rules.get_points(&score)?)
+            .ok_or(SfsError::Overflow)?;
+    }
+    Ok(points)
+}
+
+/// Scores of a week are final once they can be neither recorded nor corrected anymore
+pub fn is_week_final(root: &Root, week: u8) -> Result<bool, ProgramError> {
+    Ok(match root.get_stage()? {
+        Stage::SeasonComplete => true,
+        Stage::SeasonOpen => {
+            root.get_current_week() > week
+                && root.get_current_week() - week > root.get_score_correction_weeks()
+        }
+        _ => false,
+    })
+}
+
+/// Standing of every user by id: total points through `last_week` for total points
+/// leagues, record then points scored for head-to-head leagues
+pub fn get_standings(
+    players: &PlayerList,
+    league: &League,
+    last_week: u8,
+) -> Result<Vec<(i64, i64)>, ProgramError> {
+    let user_states = league.get_user_states()?;
+    let mut standings = Vec::<(i64, i64)>::with_capacity(user_states.get_count() as usize);
+    for user_id in 1..user_states.get_count() + 1 {
+        let user_state = user_states.get_by_id(user_id)?;
+        standings.push(match league.get_league_type()? {
+            LeagueType::TotalPoints => {
+                let mut total = 0i64;
+                for week in league.get_start_week()..last_week + 1 {
+                    total = total
+                        .checked_add(get_week_points(players, league, &user_state, week)?)
+                        .ok_or(SfsError::Overflow)?;
+                }
+                (total, 0)
+            }
+            LeagueType::HeadToHead => (
+                2 * user_state.get_wins() as i64 + user_state.get_ties() as i64,
+                user_state.get_points_for(),
+            ),
+        });
+    }
+    Ok(standings)
+}
+
+/// User ids from the best standing down, ties go to the user who joined first
+pub fn get_ranking(standings: &[(i64, i64)]) -> Vec<u8> {
+    let mut ranking: Vec<u8> = (1..standings.len() as u8 + 1).collect();
+    ranking.sort_by(|a, b| standings[*b as usize - 1].cmp(&standings[*a as usize - 1]));
+    ranking
+}
+
+/// Final places of all users once the season is over: playoff teams first when the
+/// league has a playoff, then everyone else by regular season standings
+pub fn get_final_ranking(
+    players: &PlayerList,
+    league: &League,
+    games_count: u8,
+) -> Result<Vec<u8>, ProgramError> {
+    let bracket = league.get_bracket()?;
+    let regular_season_end = league.get_regular_season_end(games_count)?;
+    if bracket.get_teams_count() > 0 && bracket.get_champion().is_none() {
+        return Err(SfsError::InvalidState.into());
+    }
+    // Leagues that missed their schedule by the end of the season are refunded instead
+    if league.is_schedule_missed(games_count, games_count)? {
+        return Err(SfsError::InvalidState.into());
+    }
+    if league.get_league_type()? == LeagueType::HeadToHead
+        && league.get_last_settled_week() != regular_season_end
+    {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let mut ranking = bracket.get_ranking();
+    let standings = get_standings(players, league, regular_season_end)?;
+    for user_id in get_ranking(&standings) {
+        if !ranking.contains(&user_id) {
+            ranking.push(user_id);
+        }
+    }
+    Ok(ranking)
+}
+
+/// Protocol fee taken from the pot of the league
+pub fn get_fee(league: &League) -> Result<u64, ProgramError> {
+    Ok((league.get_pot()? as u128 * league.get_fee_bps() as u128 / BPS_DENOMINATOR as u128) as u64)
+}
+
+/// Splits the prize between the top places of the ranking by the league payout table.
+/// Shares of places nobody took are spread over the paid places, rounding leftovers
+/// go to the first place.
+pub fn get_payouts(league: &League, ranking: &[u8], prize: u64) -> Result<Vec<u64>, ProgramError> {
+    let places_count = ranking.len().min(LEAGUE_USERS_CAPACITY as usize) as u8;
+    let paid_places: Vec<u16> = (1..places_count + 1)
+        .map(|place| league.get_payout(place))
+        .take_while(|&payout| payout > 0)
+        .collect();
+    let paid_total: u64 = paid_places.iter().map(|&payout| payout as u64).sum();
+    if paid_total == 0 {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let mut payouts: Vec<u64> = paid_places
+        .iter()
+        .map(|&payout| (prize as u128 * payout as u128 / paid_total as u128) as u64)
+        .collect();
+    let leftover = prize - payouts.iter().sum::<u64>();
+    payouts[0] += leftover;
+    Ok(payouts)
+}
+
 // Pull in syscall stubs when building for non-BPF targets
 #[cfg(not(target_arch = "bpf"))]
 
@@ -58,14 +795,51 @@ pub fn validate_bank(program_id: &Pubkey, bank_account_info: &AccountInfo) -> Pr
 pub mod tests {
     use super::*;
     use solana_program::{
-        account::Account as SolanaAccount, account_info::create_is_signer_account_infos,
-        clock::Epoch, instruction::Instruction, sysvar::rent,
+        clock::{Clock, Epoch, Slot, UnixTimestamp},
+        instruction::Instruction,
+        sysvar::{clock, rent},
     };
 
+    /// Host-side stand-in for a runtime account
+    pub struct SolanaAccount {
+        pub lamports: u64,
+        pub data: Vec<u8>,
+        pub owner: Pubkey,
+    }
+    impl SolanaAccount {
+        pub fn new(lamports: u64, space: usize, owner: &Pubkey) -> Self {
+            SolanaAccount {
+                lamports,
+                data: vec![0u8; space],
+                owner: *owner,
+            }
+        }
+    }
+
     pub fn pubkey_rand() -> Pubkey {
         Pubkey::new(&rand::random::<[u8; 32]>())
     }
 
+    pub fn create_is_signer_account_infos<'a>(
+        accounts: &'a mut [(&'a Pubkey, bool, &'a mut SolanaAccount)],
+    ) -> Vec<AccountInfo<'a>> {
+        accounts
+            .iter_mut()
+            .map(|(key, is_signer, account)| {
+                AccountInfo::new(
+                    key,
+                    *is_signer,
+                    false,
+                    &mut account.lamports,
+                    &mut account.data,
+                    &account.owner,
+                    false,
+                    Epoch::default(),
+                )
+            })
+            .collect()
+    }
+
     pub fn do_process_instruction(
         instruction: Instruction,
         accounts: Vec<&mut SolanaAccount>,
@@ -81,18 +855,154 @@ pub mod tests {
         processor::process(&instruction.program_id, &account_infos, &instruction.data)
     }
 
-    // fn do_process_instruction_dups(
-    //     instruction: Instruction,
-    //     account_infos: Vec<AccountInfo>,
-    // ) -> ProgramResult {
-    //     Processor::process(&instruction.program_id, &account_infos, &instruction.data)
-    // }
+    pub fn clock_sysvar(unix_timestamp: UnixTimestamp) -> SolanaAccount {
+        slot_clock_sysvar(0, unix_timestamp)
+    }
+
+    pub fn slot_clock_sysvar(slot: Slot, unix_timestamp: UnixTimestamp) -> SolanaAccount {
+        let mut account = SolanaAccount::new(42, Clock::size_of(), &Pubkey::default());
+        let key = clock::id();
+        let mut account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut account.lamports,
+            &mut account.data,
+            &account.owner,
+            false,
+            Epoch::default(),
+        );
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+        .to_account_info(&mut account_info)
+        .unwrap();
+        account
+    }
+
+    /// Slot hashes sysvar with the given (slot, hash) entries, newest first
+    pub fn slot_hashes_sysvar(entries: &[(u64, [u8; 32])]) -> SolanaAccount {
+        let mut account =
+            SolanaAccount::new(42, 8 + entries.len() * (8 + 32), &Pubkey::default());
+        account.data[0..8].copy_from_slice(&(entries.len() as u64).to_le_bytes());
+        for (i, (slot, hash)) in entries.iter().enumerate() {
+            let offset = 8 + i * (8 + 32);
+            account.data[offset..offset + 8].copy_from_slice(&slot.to_le_bytes());
+            account.data[offset + 8..offset + 8 + 32].copy_from_slice(hash);
+        }
+        account
+    }
 
     pub fn rent_sysvar() -> SolanaAccount {
-        rent::create_account(42, &Rent::default())
+        let mut account = SolanaAccount::new(42, Rent::size_of(), &Pubkey::default());
+        let key = rent::id();
+        let mut account_info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut account.lamports,
+            &mut account.data,
+            &account.owner,
+            false,
+            Epoch::default(),
+        );
+        Rent::default().to_account_info(&mut account_info).unwrap();
+        account
     }
 
     pub fn root_minimum_balance() -> u64 {
         Rent::default().minimum_balance(Root::LEN)
     }
+
+    /// Creates a root account and runs `InitializeRoot` on it
+    pub fn initialized_root_account(
+        program_id: &Pubkey,
+        oracle_authority: &Pubkey,
+        admin_authority: &Pubkey,
+    ) -> (Pubkey, SolanaAccount) {
+        let root_key = pubkey_rand();
+        let mut root_account = SolanaAccount::new(root_minimum_balance(), Root::LEN, program_id);
+
+        let mut args_data = Vec::<u8>::new();
+        args_data.extend_from_slice(oracle_authority.as_ref());
+        args_data.push(0);
+        args_data.extend_from_slice(admin_authority.as_ref());
+        args_data.push(Sport::Football as u8);
+        args_data.push(1);
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = InitializeRootArgs::new(args_data, 0).unwrap();
+
+        do_process_instruction(
+            initialize_root(program_id, &root_key, args).unwrap(),
+            vec![&mut root_account, &mut rent_sysvar()],
+        )
+        .unwrap();
+        (root_key, root_account)
+    }
+
+    /// Runs `AddPlayers` on the root with `count` players of the first position ranked by id
+    pub fn add_test_players(
+        program_id: &Pubkey,
+        root_key: &Pubkey,
+        admin_authority: &Pubkey,
+        root_account: &mut SolanaAccount,
+        count: u8,
+    ) {
+        let mut args_data = Vec::<u8>::new();
+        args_data.push(count);
+        for i in 0..MAX_PLAYERS_PER_INSTRUCTION as u8 {
+            if i < count {
+                args_data.extend_from_slice(&[0, i + 1, 1, i + 1, 0]);
+            } else {
+                args_data.extend_from_slice(&[0, 0, 0, 0, 0]);
+            }
+        }
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = AddPlayersArgs::new(args_data, 0).unwrap();
+
+        do_process_instruction(
+            add_players(program_id, root_key, admin_authority, args).unwrap(),
+            vec![root_account, &mut SolanaAccount::new(0, 0, &Pubkey::default())],
+        )
+        .unwrap();
+    }
+
+    /// Creates an initialized root with a few players and an open season
+    pub fn season_open_root_account(
+        program_id: &Pubkey,
+        oracle_authority: &Pubkey,
+        admin_authority: &Pubkey,
+    ) -> (Pubkey, SolanaAccount) {
+        let (root_key, mut root_account) =
+            initialized_root_account(program_id, oracle_authority, admin_authority);
+        add_test_players(program_id, &root_key, admin_authority, &mut root_account, 5);
+        do_process_instruction(
+            start_season(program_id, &root_key, admin_authority).unwrap(),
+            vec![
+                &mut root_account,
+                &mut SolanaAccount::new(0, 0, &Pubkey::default()),
+            ],
+        )
+        .unwrap();
+        (root_key, root_account)
+    }
+
+    /// Creates a league account as if `CreateLeague` had allocated it
+    pub fn league_account(
+        program_id: &Pubkey,
+        root_key: &Pubkey,
+        league_index: u16,
+    ) -> (Pubkey, SolanaAccount) {
+        let (league_key, _) = find_league_address(program_id, root_key, league_index);
+        let mut league_account = SolanaAccount::new(
+            Rent::default().minimum_balance(League::LEN),
+            League::LEN,
+            program_id,
+        );
+        let league_data = &RefCell::new(&mut *league_account.data);
+        League::new(league_data, 0).unwrap().set_is_initialized(true);
+        (league_key, league_account)
+    }
 }
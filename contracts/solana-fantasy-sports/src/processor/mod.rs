@@ -8,10 +8,28 @@ pub mod process_pick_player;
 pub use process_pick_player::*;
 pub mod process_propose_swap;
 pub use process_propose_swap::*;
-pub mod process_seed_draft_selection;
-pub use process_seed_draft_selection::*;
 pub mod process_start_season;
 pub use process_start_season::*;
+pub mod process_complete_season;
+pub use process_complete_season::*;
+pub mod process_update_player_scores;
+pub use process_update_player_scores::*;
+pub mod process_correct_player_score;
+pub use process_correct_player_score::*;
+pub mod process_settle_week;
+pub use process_settle_week::*;
+pub mod process_settle_playoff_round;
+pub use process_settle_playoff_round::*;
+pub mod process_set_protocol_fee;
+pub use process_set_protocol_fee::*;
+pub mod process_withdraw_fees;
+pub use process_withdraw_fees::*;
+pub mod process_leave_league;
+pub use process_leave_league::*;
+pub mod process_cancel_league;
+pub use process_cancel_league::*;
+pub mod process_refund_unfilled_league;
+pub use process_refund_unfilled_league::*;
 pub mod process_update_lineup;
 pub use process_update_lineup::*;
 pub mod process_create_league;
@@ -30,3 +48,15 @@ pub mod process;
 pub use process::*;
 pub mod helpers;
 pub use helpers::*;
+pub mod process_auto_pick;
+pub use process_auto_pick::*;
+pub mod process_set_draft_queue;
+pub use process_set_draft_queue::*;
+pub mod process_nominate_player;
+pub use process_nominate_player::*;
+pub mod process_place_bid;
+pub use process_place_bid::*;
+pub mod process_add_drop_player;
+pub use process_add_drop_player::*;
+pub mod process_draw_pick_order;
+pub use process_draw_pick_order::*;
@@ -46,10 +46,6 @@ pub fn process<'a>(
             info!("Instruction: InitializeRoot");
             processor::process_initialize_root(program_id, accounts, args)
         }
-        SfsInstruction::SeedDraftSelection { args } => {
-            info!("Instruction: SeedDraftSelection");
-            processor::process_seed_draft_selection(program_id, accounts, args)
-        }
         SfsInstruction::StartSeason => {
             info!("Instruction: StartSeason");
             processor::process_start_season(program_id, accounts)
@@ -94,6 +90,70 @@ pub fn process<'a>(
             info!("Instruction: ClaimReward");
             processor::process_claim_reward(program_id, accounts, args)
         }
+        SfsInstruction::CompleteSeason => {
+            info!("Instruction: CompleteSeason");
+            processor::process_complete_season(program_id, accounts)
+        }
+        SfsInstruction::UpdatePlayerScores { args } => {
+            info!("Instruction: UpdatePlayerScores");
+            processor::process_update_player_scores(program_id, accounts, args)
+        }
+        SfsInstruction::CorrectPlayerScore { args } => {
+            info!("Instruction: CorrectPlayerScore");
+            processor::process_correct_player_score(program_id, accounts, args)
+        }
+        SfsInstruction::SettleWeek { args } => {
+            info!("Instruction: SettleWeek");
+            processor::process_settle_week(program_id, accounts, args)
+        }
+        SfsInstruction::SettlePlayoffRound { args } => {
+            info!("Instruction: SettlePlayoffRound");
+            processor::process_settle_playoff_round(program_id, accounts, args)
+        }
+        SfsInstruction::SetProtocolFee { args } => {
+            info!("Instruction: SetProtocolFee");
+            processor::process_set_protocol_fee(program_id, accounts, args)
+        }
+        SfsInstruction::WithdrawFees => {
+            info!("Instruction: WithdrawFees");
+            processor::process_withdraw_fees(program_id, accounts)
+        }
+        SfsInstruction::LeaveLeague { args } => {
+            info!("Instruction: LeaveLeague");
+            processor::process_leave_league(program_id, accounts, args)
+        }
+        SfsInstruction::CancelLeague { args } => {
+            info!("Instruction: CancelLeague");
+            processor::process_cancel_league(program_id, accounts, args)
+        }
+        SfsInstruction::RefundUnfilledLeague { args } => {
+            info!("Instruction: RefundUnfilledLeague");
+            processor::process_refund_unfilled_league(program_id, accounts, args)
+        }
+        SfsInstruction::AutoPick { args } => {
+            info!("Instruction: AutoPick");
+            processor::process_auto_pick(program_id, accounts, args)
+        }
+        SfsInstruction::SetDraftQueue { args } => {
+            info!("Instruction: SetDraftQueue");
+            processor::process_set_draft_queue(program_id, accounts, args)
+        }
+        SfsInstruction::NominatePlayer { args } => {
+            info!("Instruction: NominatePlayer");
+            processor::process_nominate_player(program_id, accounts, args)
+        }
+        SfsInstruction::PlaceBid { args } => {
+            info!("Instruction: PlaceBid");
+            processor::process_place_bid(program_id, accounts, args)
+        }
+        SfsInstruction::AddDropPlayer { args } => {
+            info!("Instruction: AddDropPlayer");
+            processor::process_add_drop_player(program_id, accounts, args)
+        }
+        SfsInstruction::DrawPickOrder { args } => {
+            info!("Instruction: DrawPickOrder");
+            processor::process_draw_pick_order(program_id, accounts, args)
+        }
 
         _ => return Err(SfsError::InvalidInstruction.into()),
     }
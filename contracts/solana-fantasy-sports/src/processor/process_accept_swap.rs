@@ -35,10 +35,20 @@ pub fn process_accept_swap<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
 
     let user_account_info = next_account_info(account_info_iter)?;
 
-    let league = root.get_leagues()?.get(args.get_league_index())?;
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
 
     if league.get_start_week() == 0 {
         return Err(SfsError::InvalidState.into());
@@ -82,32 +92,183 @@ pub fn process_accept_swap<'a>(
         .replace_id(args.get_want_player_id(), args.get_give_player_id())?;
     accepting_user_state
         .get_user_players()?
-        .validate_team_composition(&root.get_players()?)?;
+        .validate_team_composition(
+            &root.get_players()?,
+            &league.get_composition_rules()?,
+            league.get_team_players_count(),
+        )?;
 
     proposing_user_state
         .get_user_players()?
         .replace_id(args.get_give_player_id(), args.get_want_player_id())?;
     proposing_user_state
         .get_user_players()?
-        .validate_team_composition(&root.get_players()?)?;
+        .validate_team_composition(
+            &root.get_players()?,
+            &league.get_composition_rules()?,
+            league.get_team_players_count(),
+        )?;
 
     proposing_user_state
         .get_swap_proposals()?
         .remove(args.get_give_player_id(), args.get_want_player_id())?;
 
-    for i in root.get_current_week() + 1..GAMES_COUNT + 1 {
+    for i in root.get_current_week() + 1..root.get_games_count()? + 1 {
         let lineup = proposing_user_state.get_lineups()?.get_by_week(i)?;
 
         if lineup.contains(args.get_give_player_id()) {
             lineup.replace_id(args.get_give_player_id(), args.get_want_player_id())?;
+            helpers::validate_lineup_positions(&root.get_players()?, &league, &lineup)?;
         }
 
         let lineup = accepting_user_state.get_lineups()?.get_by_week(i)?;
 
         if lineup.contains(args.get_want_player_id()) {
             lineup.replace_id(args.get_want_player_id(), args.get_give_player_id())?;
+            helpers::validate_lineup_positions(&root.get_players()?, &league, &lineup)?;
         }
     }
 
     Ok(())
 }
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_accept_swap() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_keys = [pubkey_rand(), pubkey_rand()];
+
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            let players = Root::new(root_data).unwrap().get_players().unwrap();
+            players.get_by_id(4).unwrap().set_position(Position::QB);
+
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(2);
+            league.set_active_players_count(1);
+            league.set_bench_players_count(1);
+            league.set_start_week(1);
+            let composition_rules = league.get_composition_rules().unwrap();
+            composition_rules.set_max(Position::RB, 2);
+            composition_rules.set_max(Position::QB, 1);
+            league
+                .get_position_options()
+                .unwrap()
+                .set_number_by_position(Position::RB, 1);
+            for (i, user_key) in user_keys.iter().enumerate() {
+                let user_state = league.get_user_states().unwrap().add(*user_key).unwrap();
+                let first_player_id = i as u16 * 2 + 1;
+                user_state
+                    .get_user_players()
+                    .unwrap()
+                    .set(0, first_player_id);
+                user_state
+                    .get_user_players()
+                    .unwrap()
+                    .set(1, first_player_id + 1);
+                let lineups = user_state.get_lineups().unwrap();
+                for week in 1..FOOTBALL_GAMES_COUNT + 1 {
+                    lineups.get_by_week(week).unwrap().set(0, first_player_id);
+                }
+            }
+        }
+
+        let do_accept_swap = |root_account: &mut SolanaAccount,
+                              league_account: &mut SolanaAccount,
+                              user_account: &mut SolanaAccount,
+                              give_player_id: u16,
+                              want_player_id: u16| {
+            {
+                let league_data = &RefCell::new(&mut *league_account.data);
+                League::new(league_data, 0)
+                    .unwrap()
+                    .get_user_states()
+                    .unwrap()
+                    .get_by_id(2)
+                    .unwrap()
+                    .get_swap_proposals()
+                    .unwrap()
+                    .add(give_player_id, want_player_id)
+                    .unwrap();
+            }
+            let mut args_data = vec![0u8, 0, 1, 2];
+            args_data.extend_from_slice(&give_player_id.to_le_bytes());
+            args_data.extend_from_slice(&want_player_id.to_le_bytes());
+            let args_data = &RefCell::new(args_data.as_slice());
+            do_process_instruction(
+                accept_swap(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_keys[0],
+                    AcceptSwapArgs::new(args_data, 0).unwrap(),
+                )
+                .unwrap(),
+                vec![root_account, league_account, user_account],
+            )
+        };
+
+        do_accept_swap(
+            &mut root_account,
+            &mut league_account,
+            &mut user_account,
+            3,
+            2,
+        )
+        .unwrap();
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            let user_states = league.get_user_states().unwrap();
+            assert_eq!(
+                user_states
+                    .get_by_id(1)
+                    .unwrap()
+                    .get_user_players()
+                    .unwrap()
+                    .get(1),
+                3
+            );
+            let proposing_user_state = user_states.get_by_id(2).unwrap();
+            assert_eq!(proposing_user_state.get_user_players().unwrap().get(0), 2);
+            assert_eq!(
+                proposing_user_state
+                    .get_swap_proposals()
+                    .unwrap()
+                    .get_count(),
+                0
+            );
+            // lineups follow the roster
+            let lineup = proposing_user_state
+                .get_lineups()
+                .unwrap()
+                .get_by_week(1)
+                .unwrap();
+            assert_eq!(lineup.get(0), 2);
+        }
+
+        // the quarterback fits the roster but not the running back slot of the lineups
+        assert_eq!(
+            Err(SfsError::PositionRulesViolation.into()),
+            do_accept_swap(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                4,
+                1
+            )
+        );
+    }
+}
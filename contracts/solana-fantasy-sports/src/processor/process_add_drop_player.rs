@@ -0,0 +1,309 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [AddDropPlayer](enum.SfsInstruction.html) instruction.
+pub fn process_add_drop_player<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: AddDropPlayerArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let user_account_info = next_account_info(account_info_iter)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    let add_player_id = args.get_add_player_id();
+    if add_player_id == 0 || add_player_id > root.get_players()?.get_count() {
+        return Err(SfsError::IndexOutOfRange.into());
+    }
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    let user_state = league.get_user_states()?.get_by_id(args.get_user_id())?;
+
+    helpers::validate_owner(program_id, &user_state.get_pub_key(), user_account_info)?;
+
+    // Free agents can only be picked up once every roster is drafted
+    if league.get_pick_round()? < league.get_team_players_count() {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    // Lineup of the current week is locked
+    if root.get_current_week() > 0
+        && user_state
+            .get_lineups()?
+            .get_by_week(root.get_current_week())?
+            .contains(args.get_drop_player_id())
+    {
+        return Err(SfsError::AlreadyInUse.into());
+    }
+
+    helpers::validate_player_available(&league, add_player_id)?;
+
+    user_state
+        .get_user_players()?
+        .replace_id(args.get_drop_player_id(), add_player_id)?;
+    user_state.get_user_players()?.validate_team_composition(
+        &root.get_players()?,
+        &league.get_composition_rules()?,
+        league.get_team_players_count(),
+    )?;
+
+    for i in root.get_current_week() + 1..root.get_games_count()? + 1 {
+        let lineup = user_state.get_lineups()?.get_by_week(i)?;
+
+        if lineup.contains(args.get_drop_player_id()) {
+            lineup.replace_id(args.get_drop_player_id(), add_player_id)?;
+            helpers::validate_lineup_positions(&root.get_players()?, &league, &lineup)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_add_drop_player() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_key = pubkey_rand();
+
+        do_process_instruction(
+            increment_week(&program_id, &root_key, &admin_key).unwrap(),
+            vec![
+                &mut root_account,
+                &mut SolanaAccount::new(0, 0, &Pubkey::default()),
+            ],
+        )
+        .unwrap();
+
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            let players = Root::new(root_data).unwrap().get_players().unwrap();
+            players.get_by_id(4).unwrap().set_position(Position::QB);
+
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(2);
+            league.set_active_players_count(1);
+            league.set_bench_players_count(1);
+            league
+                .get_composition_rules()
+                .unwrap()
+                .set_max(Position::RB, 2);
+            league
+                .get_position_options()
+                .unwrap()
+                .set_number_by_position(Position::RB, 1);
+            let user_states = league.get_user_states().unwrap();
+            let user_state = user_states.add(user_key).unwrap();
+            user_state.get_user_players().unwrap().set(0, 1);
+            user_state.get_user_players().unwrap().set(1, 2);
+            let lineups = user_state.get_lineups().unwrap();
+            lineups.get_by_week(1).unwrap().set(0, 1);
+            for week in 2..FOOTBALL_GAMES_COUNT + 1 {
+                lineups.get_by_week(week).unwrap().set(0, 2);
+            }
+            let other_user_players = user_states
+                .add(pubkey_rand())
+                .unwrap()
+                .get_user_players()
+                .unwrap();
+            other_user_players.set(0, 3);
+        }
+
+        let do_add_drop_player = |root_account: &mut SolanaAccount,
+                                  league_account: &mut SolanaAccount,
+                                  user_account: &mut SolanaAccount,
+                                  drop_player_id: u16,
+                                  add_player_id: u16| {
+            let mut args_data = vec![0u8, 0, 1];
+            args_data.extend_from_slice(&drop_player_id.to_le_bytes());
+            args_data.extend_from_slice(&add_player_id.to_le_bytes());
+            let args_data = &RefCell::new(args_data.as_slice());
+            do_process_instruction(
+                add_drop_player(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_key,
+                    AddDropPlayerArgs::new(args_data, 0).unwrap(),
+                )
+                .unwrap(),
+                vec![root_account, league_account, user_account],
+            )
+        };
+
+        // draft is not over
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_add_drop_player(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                2,
+                5
+            )
+        );
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0).unwrap().set_current_pick(4);
+        }
+
+        // player is in the locked lineup of the current week
+        assert_eq!(
+            Err(SfsError::AlreadyInUse.into()),
+            do_add_drop_player(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                1,
+                5
+            )
+        );
+
+        // player is owned by the other user
+        assert_eq!(
+            Err(SfsError::AlreadyInUse.into()),
+            do_add_drop_player(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                2,
+                3
+            )
+        );
+
+        // there are only 5 players
+        assert_eq!(
+            Err(SfsError::IndexOutOfRange.into()),
+            do_add_drop_player(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                2,
+                6
+            )
+        );
+
+        // player is not on the roster
+        assert_eq!(
+            Err(SfsError::PlayerNotFound.into()),
+            do_add_drop_player(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                4,
+                5
+            )
+        );
+
+        do_add_drop_player(
+            &mut root_account,
+            &mut league_account,
+            &mut user_account,
+            2,
+            5,
+        )
+        .unwrap();
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            let user_state = league.get_user_states().unwrap().get_by_id(1).unwrap();
+            let user_players = user_state.get_user_players().unwrap();
+            assert_eq!(user_players.get(0), 1);
+            assert_eq!(user_players.get(1), 5);
+            // future lineups follow the roster, the current week stays as it is
+            let lineups = user_state.get_lineups().unwrap();
+            assert_eq!(lineups.get_by_week(1).unwrap().get(0), 1);
+            assert_eq!(lineups.get_by_week(2).unwrap().get(0), 5);
+        }
+
+        // the league allows no quarterbacks
+        assert_eq!(
+            Err(SfsError::TeamCompositionRulesViolation.into()),
+            do_add_drop_player(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                5,
+                4
+            )
+        );
+
+        // the quarterback fits the roster but not the running back slot of the lineups
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league
+                .get_composition_rules()
+                .unwrap()
+                .set_max(Position::QB, 1);
+            // the failed instruction above is not rolled back here
+            league
+                .get_user_states()
+                .unwrap()
+                .get_by_id(1)
+                .unwrap()
+                .get_user_players()
+                .unwrap()
+                .set(1, 5);
+        }
+        assert_eq!(
+            Err(SfsError::PositionRulesViolation.into()),
+            do_add_drop_player(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                5,
+                4
+            )
+        );
+    }
+}
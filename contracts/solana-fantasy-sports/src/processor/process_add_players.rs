@@ -37,16 +37,29 @@ pub fn process_add_players<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let admin_account_info = next_account_info(account_info_iter)?;
 
-    if root.get_stage()? != Stage::Uninitialized {
+    helpers::validate_owner(program_id, &root.get_admin_authority(), admin_account_info)?;
+
+    if root.get_stage()? != Stage::DraftSelection {
         return Err(SfsError::InvalidStage.into());
     }
 
+    let sport = root.get_sport()?;
     let players_args = args.get_players()?;
     let players_root = root.get_players()?;
     for i in 0..players_args.get_count() {
         let arg_player = players_args.get(i)?;
-        players_root.add(arg_player.get_external_id(), arg_player.get_position()?)?;
+        let position = arg_player.get_position()?;
+        if !sport.has_position(position) {
+            return Err(SfsError::InvalidInstruction.into());
+        }
+        players_root.add(
+            arg_player.get_external_id(),
+            position,
+            sport,
+            arg_player.get_rank(),
+        )?;
     }
     Ok(())
 }
@@ -58,21 +71,22 @@ pub fn process_add_players<'a>(
 mod tests {
     use super::helpers::tests::*;
     use super::*;
-    use solana_program::{
-        account::Account as SolanaAccount, account_info::create_is_signer_account_infos,
-        clock::Epoch, instruction::Instruction, sysvar::rent,
-    };
 
     #[test]
     fn test_initialize_root() {
         let program_id = pubkey_rand();
         let owner_key = pubkey_rand();
+        let admin_key = pubkey_rand();
         let root_key = pubkey_rand();
         let mut root_account = SolanaAccount::new(42, Root::LEN, &program_id);
         let mut rent_sysvar = rent_sysvar();
 
         let mut args_data = Vec::<u8>::new();
         args_data.extend_from_slice(owner_key.as_ref());
+        args_data.push(0);
+        args_data.extend_from_slice(admin_key.as_ref());
+        args_data.push(Sport::Football as u8);
+        args_data.push(1);
 
         let args_data = &RefCell::new(args_data.as_slice());
         let args = InitializeRootArgs::new(args_data, 0).unwrap();
@@ -106,39 +120,80 @@ mod tests {
         let root_data = &RefCell::new(&mut *root_account.data);
         let root = Root::new(root_data).unwrap();
         assert_eq!(root.get_oracle_authority(), owner_key);
+        assert_eq!(root.get_admin_authority(), admin_key);
+        assert_eq!(root.get_sport(), Ok(Sport::Football));
     }
 
     #[test]
     fn test_add_players() {
         let program_id = pubkey_rand();
-        let root_key = pubkey_rand();
-        let mut root_account = SolanaAccount::new(42, Root::LEN, &program_id);
-        let mut rent_sysvar = rent_sysvar();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            initialized_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
 
         let mut args_data = Vec::<u8>::new();
         args_data.extend_from_slice(&[5]);
-        args_data.extend_from_slice(&[0, 1, 1]);
-        args_data.extend_from_slice(&[0, 2, 2]);
-        args_data.extend_from_slice(&[0, 3, 3]);
-        args_data.extend_from_slice(&[0, 4, 4]);
-        args_data.extend_from_slice(&[0, 5, 5]);
+        args_data.extend_from_slice(&[0, 1, 1, 2, 0]);
+        args_data.extend_from_slice(&[0, 2, 2, 1, 0]);
+        args_data.extend_from_slice(&[0, 3, 3, 0, 0]);
+        args_data.extend_from_slice(&[0, 4, 4, 3, 0]);
+        args_data.extend_from_slice(&[0, 5, 5, 4, 0]);
         for _ in 0..MAX_PLAYERS_PER_INSTRUCTION - 5 {
-            args_data.extend_from_slice(&[0, 0, 0]);
+            args_data.extend_from_slice(&[0, 0, 0, 0, 0]);
         }
 
         let args_data = &RefCell::new(args_data.as_slice());
         let args = AddPlayersArgs::new(args_data, 0).unwrap();
 
+        // not an admin
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                add_players(&program_id, &root_key, &pubkey_rand(), args.clone()).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        // admin did not sign
+        let mut instruction = add_players(&program_id, &root_key, &admin_key, args.clone()).unwrap();
+        instruction.accounts[1].is_signer = false;
+        assert_eq!(
+            Err(ProgramError::MissingRequiredSignature),
+            do_process_instruction(instruction, vec![&mut root_account, &mut admin_account])
+        );
+
+        // basketball player in a football root
+        let mut basketball_args_data = args_data.borrow().to_vec();
+        basketball_args_data[3] = Position::PG as u8;
+        let basketball_args_data = &RefCell::new(basketball_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                add_players(
+                    &program_id,
+                    &root_key,
+                    &admin_key,
+                    AddPlayersArgs::new(basketball_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
         // add players
         do_process_instruction(
-            add_players(&program_id, &root_key, args.clone()).unwrap(),
-            vec![&mut root_account, &mut rent_sysvar],
+            add_players(&program_id, &root_key, &admin_key, args.clone()).unwrap(),
+            vec![&mut root_account, &mut admin_account],
         )
         .unwrap();
 
         let root_data = &RefCell::new(&mut *root_account.data);
         let root = Root::new(root_data).unwrap();
         assert_eq!(root.get_players().unwrap().get_count(), 5);
-        assert_eq!(root.get_stage(), Ok(Stage::Uninitialized));
+        let player = root.get_players().unwrap().get_by_id(1).unwrap();
+        assert_eq!(player.get_sport(), Ok(Sport::Football));
+        assert_eq!(player.get_rank(), 2);
+        assert_eq!(root.get_stage(), Ok(Stage::DraftSelection));
     }
 }
@@ -0,0 +1,271 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [AutoPick](enum.SfsInstruction.html) instruction.
+pub fn process_auto_pick<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: AutoPickArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    if root.get_current_week() >= root.get_games_count()? {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    if league.get_user_states()?.get_count() != league.get_users_limit() {
+        return Err(SfsError::InvalidState.into());
+    }
+    if league.get_pick_round()? >= league.get_team_players_count() {
+        return Err(SfsError::InvalidState.into());
+    }
+    // Auction drafts have no turns, the crank gives the player to the highest bidder instead
+    if league.get_draft_type()? == DraftType::Auction {
+        return helpers::close_auction(&league, clock.unix_timestamp);
+    }
+    if !league.is_pick_order_drawn()? {
+        return Err(SfsError::InvalidState.into());
+    }
+    if !league.is_pick_expired(clock.unix_timestamp) {
+        return Err(SfsError::PickNotExpired.into());
+    }
+
+    // The first queued, then the best ranked player nobody owns that still fits the team
+    // composition
+    let user_id = league.get_current_pick_user_id()?;
+    let players = root.get_players()?;
+    let draft_queue = helpers::get_draft_queue(
+        program_id,
+        league_account_info.key,
+        &league.get_user_states()?.get_by_id(user_id)?.get_pub_key(),
+        next_account_info(account_info_iter)?,
+    )?;
+    // Collected once, checking every candidate against every roster runs out of compute
+    // late in the draft
+    let drafted_player_ids = helpers::get_drafted_player_ids(&league)?;
+    let mut is_picked = false;
+    for player_id in draft_queue
+        .into_iter()
+        .chain(helpers::get_players_by_rank(&players)?)
+        .filter(|player_id| !drafted_player_ids.contains(player_id))
+    {
+        if helpers::draft_available_player(&players, &league, user_id, player_id).is_ok() {
+            is_picked = true;
+            break;
+        }
+    }
+    if !is_picked {
+        return Err(SfsError::PlayerNotFound.into());
+    }
+    helpers::start_next_pick(&league, clock.unix_timestamp);
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_auto_pick() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let first_user_key = pubkey_rand();
+        let second_user_key = pubkey_rand();
+        let (first_draft_queue_key, _) =
+            helpers::find_draft_queue_address(&program_id, &league_key, &first_user_key);
+        let (second_draft_queue_key, _) =
+            helpers::find_draft_queue_address(&program_id, &league_key, &second_user_key);
+        // the second user never set a draft queue
+        let mut second_draft_queue_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            let players = Root::new(root_data).unwrap().get_players().unwrap();
+            // ranked 1, 3, 4, 5 and unranked 2
+            players.get_by_id(2).unwrap().set_rank(0);
+            players.get_by_id(3).unwrap().set_position(Position::QB);
+
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(2);
+            league.set_active_players_count(2);
+            league.set_pick_time_limit(60);
+            league.set_pick_started_at(1_000);
+            league
+                .get_composition_rules()
+                .unwrap()
+                .set_max(Position::RB, 2);
+            let user_states = league.get_user_states().unwrap();
+            user_states.add(first_user_key).unwrap();
+            user_states.add(second_user_key).unwrap();
+            // the best ranked player is owned already
+            user_states
+                .get_by_id(1)
+                .unwrap()
+                .get_user_players()
+                .unwrap()
+                .set(1, 1);
+            let pick_order = league.get_pick_order().unwrap();
+            pick_order.set(0, 1);
+            pick_order.set(1, 2);
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        let args = AutoPickArgs::new(args_data, 0).unwrap();
+
+        // user is still on the clock
+        assert_eq!(
+            Err(SfsError::PickNotExpired.into()),
+            do_process_instruction(
+                auto_pick(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    Some(&second_draft_queue_key),
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut clock_sysvar(1_059),
+                    &mut second_draft_queue_account,
+                ],
+            )
+        );
+
+        // the draft queue belongs to the user on the clock
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                auto_pick(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    Some(&first_draft_queue_key),
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut clock_sysvar(1_060),
+                    &mut second_draft_queue_account,
+                ],
+            )
+        );
+
+        do_process_instruction(
+            auto_pick(
+                &program_id,
+                &root_key,
+                &league_key,
+                Some(&second_draft_queue_key),
+                args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut clock_sysvar(1_060),
+                &mut second_draft_queue_account,
+            ],
+        )
+        .unwrap();
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            // player 1 is owned and player 3 plays a position the league does not allow
+            let user_players = league
+                .get_user_states()
+                .unwrap()
+                .get_by_id(2)
+                .unwrap()
+                .get_user_players()
+                .unwrap();
+            assert_eq!(user_players.get(0), 4);
+            assert_eq!(league.get_current_pick(), 1);
+            assert_eq!(league.get_pick_started_at(), 1_060);
+            assert_eq!(league.get_current_pick_user_id(), Ok(1));
+        }
+
+        // the queue of the next user goes before the ranking, skipping drafted players
+        let mut first_draft_queue_account = SolanaAccount::new(0, DraftQueueList::LEN, &program_id);
+        first_draft_queue_account.data[0..2].copy_from_slice(&4u16.to_le_bytes());
+        first_draft_queue_account.data[4..6].copy_from_slice(&2u16.to_le_bytes());
+        do_process_instruction(
+            auto_pick(
+                &program_id,
+                &root_key,
+                &league_key,
+                Some(&first_draft_queue_key),
+                args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut clock_sysvar(1_120),
+                &mut first_draft_queue_account,
+            ],
+        )
+        .unwrap();
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let user_players = league
+            .get_user_states()
+            .unwrap()
+            .get_by_id(1)
+            .unwrap()
+            .get_user_players()
+            .unwrap();
+        assert_eq!(user_players.get(0), 2);
+    }
+}
@@ -0,0 +1,350 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes a [CancelLeague](enum.SfsInstruction.html) instruction.
+pub fn process_cancel_league<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CancelLeagueArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let creator_account_info = next_account_info(account_info_iter)?;
+    let vault_account_info = next_account_info(account_info_iter)?;
+    let system_program_account_info = next_account_info(account_info_iter)?;
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    helpers::validate_owner(
+        program_id,
+        &league.get_user_states()?.get_by_id(1)?.get_pub_key(),
+        creator_account_info,
+    )?;
+
+    // The draft starts with the first pick or auction nomination
+    if league.get_current_pick() != 0 || league.get_nominated_player_id() != 0 {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    helpers::close_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        &league,
+        vault_account_info,
+        system_program_account_info,
+        account_info_iter,
+    )
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+    use crate::spl_token::{self, TokenAccount};
+
+    #[test]
+    fn test_cancel_league() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(200, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut creator_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_keys = [pubkey_rand(), pubkey_rand()];
+        let mut user_accounts = [
+            SolanaAccount::new(0, 0, &Pubkey::default()),
+            SolanaAccount::new(0, 0, &Pubkey::default()),
+        ];
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(4);
+            for user_key in user_keys.iter() {
+                league.get_user_states().unwrap().add(*user_key).unwrap();
+            }
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        let args = CancelLeagueArgs::new(args_data, 0).unwrap();
+        let (first, second) = user_accounts.split_at_mut(1);
+
+        // only the creator can cancel
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                cancel_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[1],
+                    None,
+                    &user_keys,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut creator_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut first[0],
+                    &mut second[0],
+                ],
+            )
+        );
+
+        // not once the draft has started
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0)
+                .unwrap()
+                .set_nominated_player_id(1);
+        }
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                cancel_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[0],
+                    None,
+                    &user_keys,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut creator_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut first[0],
+                    &mut second[0],
+                ],
+            )
+        );
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0)
+                .unwrap()
+                .set_nominated_player_id(0);
+        }
+
+        // refunds are not passed by user ids
+        let swapped_keys = [user_keys[1], user_keys[0]];
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                cancel_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[0],
+                    None,
+                    &swapped_keys,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut creator_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut first[0],
+                    &mut second[0],
+                ],
+            )
+        );
+
+        do_process_instruction(
+            cancel_league(
+                &program_id,
+                &root_key,
+                &league_key,
+                &vault_key,
+                &user_keys[0],
+                None,
+                &user_keys,
+                args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut creator_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut first[0],
+                &mut second[0],
+            ],
+        )
+        .unwrap();
+
+        // a cancelled league takes no more instructions
+        assert_eq!(
+            Err(SfsError::LeagueNotInitialized.into()),
+            do_process_instruction(
+                cancel_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[0],
+                    None,
+                    &user_keys,
+                    args
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut creator_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut first[0],
+                    &mut second[0],
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn test_cancel_token_league() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut creator_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut token_program_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mint_key = pubkey_rand();
+        let token_vault_key = pubkey_rand();
+        let mut token_vault_account = SolanaAccount::new(0, 0, &spl_token::id());
+        let user_keys = [pubkey_rand(), pubkey_rand()];
+        let user_token_keys = [pubkey_rand(), pubkey_rand()];
+        let mut user_token_accounts = [
+            SolanaAccount::new(0, 0, &spl_token::id()),
+            SolanaAccount::new(0, 0, &spl_token::id()),
+        ];
+        user_token_accounts[0].data = TokenAccount::pack(&mint_key, &user_keys[0], 0);
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(4);
+            league.set_mint(mint_key);
+            league.set_token_vault(token_vault_key);
+            for user_key in user_keys.iter() {
+                league.get_user_states().unwrap().add(*user_key).unwrap();
+            }
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        let args = CancelLeagueArgs::new(args_data, 0).unwrap();
+        let mut do_cancel_token_league =
+            |token_vault_account: &mut SolanaAccount, user_token_accounts: &mut [SolanaAccount]| {
+                let (first, second) = user_token_accounts.split_at_mut(1);
+                do_process_instruction(
+                    cancel_league(
+                        &program_id,
+                        &root_key,
+                        &league_key,
+                        &vault_key,
+                        &user_keys[0],
+                        Some(&token_vault_key),
+                        &user_token_keys,
+                        args.clone(),
+                    )
+                    .unwrap(),
+                    vec![
+                        &mut root_account,
+                        &mut league_account,
+                        &mut creator_account,
+                        &mut vault_account,
+                        &mut system_account,
+                        &mut token_program_account,
+                        token_vault_account,
+                        &mut first[0],
+                        &mut second[0],
+                    ],
+                )
+            };
+
+        // token account of the vault holds less than the bids of every user
+        token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 199);
+        user_token_accounts[1].data = TokenAccount::pack(&mint_key, &user_keys[1], 0);
+        assert_eq!(
+            Err(SfsError::VaultBalanceMismatch.into()),
+            do_cancel_token_league(&mut token_vault_account, &mut user_token_accounts)
+        );
+        token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 200);
+
+        // the refund of the second user goes to a token account of someone else
+        user_token_accounts[1].data = TokenAccount::pack(&mint_key, &user_keys[0], 0);
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_cancel_token_league(&mut token_vault_account, &mut user_token_accounts)
+        );
+
+        // the refund token account holds another mint
+        user_token_accounts[1].data = TokenAccount::pack(&pubkey_rand(), &user_keys[1], 0);
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_cancel_token_league(&mut token_vault_account, &mut user_token_accounts)
+        );
+
+        user_token_accounts[1].data = TokenAccount::pack(&mint_key, &user_keys[1], 0);
+        do_cancel_token_league(&mut token_vault_account, &mut user_token_accounts).unwrap();
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        assert_eq!(league.get_is_initialized(), Ok(false));
+    }
+}
@@ -36,66 +36,507 @@ pub fn process_claim_reward<'a>(
   let account_info_iter = &mut accounts.iter();
   let root_info = next_account_info(account_info_iter)?;
   let root = Root::new(&root_info.data)?;
+  let league_account_info = next_account_info(account_info_iter)?;
 
-  let current_week = root.get_current_week();
-
-  if current_week != GAMES_COUNT + 1 {
-    return Err(SfsError::InvalidState.into());
+  if root.get_stage()? != Stage::SeasonComplete {
+    return Err(SfsError::InvalidStage.into());
   }
 
-  let league = root.get_leagues()?.get(args.get_league_index())?;
+  let league = helpers::get_league(
+    program_id,
+    root_info.key,
+    args.get_league_index(),
+    league_account_info,
+  )?;
   if league.get_is_reward_claimed()? {
     return Err(SfsError::InvalidState.into());
   }
 
-  let players = root.get_players()?;
-  let mut winners = Vec::<u8>::new();
-  let mut winner_score = 0;
-
+  let ranking = helpers::get_final_ranking(&root.get_players()?, &league, root.get_games_count()?)?;
+  let fee = helpers::get_fee(&league)?;
+  let payouts = helpers::get_payouts(&league, &ranking, league.get_pot()? - fee)?;
   let user_states = league.get_user_states()?;
-  for user_id in 1..user_states.get_count() + 1 {
-    let lineups = user_states.get_by_id(user_id)?.get_lineups()?;
-    let mut user_score = 0;
-    for week in league.get_start_week()..GAMES_COUNT + 1 {
-      let lineup = lineups.get_by_week(week)?;
-      for i in 0..ActivePlayersList::ITEM_COUNT {
-        user_score += players
-          .get_by_id(lineup.get(i))?
-          .get_scores()?
-          .get_by_week(week)?
-          .get_score1();
-      }
-    }
-    if user_score > winner_score {
-      winners.clear();
-      winners.push(user_id);
-      winner_score = user_score;
-    } else if user_score == winner_score {
-      winners.push(user_id);
-    }
-  }
 
+  let vault_account_info = next_account_info(account_info_iter)?;
   let bank_account_info = next_account_info(account_info_iter)?;
   let system_program_account_info = next_account_info(account_info_iter)?;
+  let vault_bump_seed = helpers::validate_vault(
+    program_id,
+    root_info.key,
+    args.get_league_index(),
+    vault_account_info,
+  )?;
   helpers::validate_bank(program_id, bank_account_info)?;
 
-  let (bank_pubkey, bump_seed) = Pubkey::find_program_address(&[&[0]], program_id);
+  // Token bids are paid out of the token account of the vault, the fee goes straight to
+  // the treasury and users are passed by their token accounts
+  if league.has_token_bid() {
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let token_vault_account_info = next_account_info(account_info_iter)?;
+    let treasury_token_account_info = next_account_info(account_info_iter)?;
+    helpers::validate_token_program(token_program_account_info)?;
+    if *token_vault_account_info.key != league.get_token_vault() {
+      return Err(SfsError::InvalidInstruction.into());
+    }
+    helpers::validate_token_account(
+      treasury_token_account_info,
+      &league.get_mint(),
+      &root.get_treasury(),
+    )?;
+    helpers::validate_vault_balance(&league, token_vault_account_info)?;
+
+    if fee > 0 {
+      helpers::transfer_tokens_from_vault(
+        root_info.key,
+        args.get_league_index(),
+        vault_bump_seed,
+        vault_account_info,
+        token_vault_account_info,
+        treasury_token_account_info,
+        token_program_account_info,
+        fee,
+      )?;
+    }
+    for (place, reward) in payouts.iter().enumerate() {
+      let user_token_account_info = next_account_info(account_info_iter)?;
+      helpers::validate_token_account(
+        user_token_account_info,
+        &league.get_mint(),
+        &user_states.get_by_id(ranking[place])?.get_pub_key(),
+      )?;
+      helpers::transfer_tokens_from_vault(
+        root_info.key,
+        args.get_league_index(),
+        vault_bump_seed,
+        vault_account_info,
+        token_vault_account_info,
+        user_token_account_info,
+        token_program_account_info,
+        *reward,
+      )?;
+    }
+
+    league.set_is_reward_claimed(true);
+    return Ok(());
+  }
+
+  helpers::validate_vault_balance(&league, vault_account_info)?;
 
-  let reward = league.get_bid() / winners.len() as u64;
-  for i in 0..winners.len() {
+  // Fees are kept in the bank until withdrawn to the treasury
+  if fee > 0 {
+    helpers::transfer_from_vault(
+      root_info.key,
+      args.get_league_index(),
+      vault_bump_seed,
+      vault_account_info,
+      bank_account_info,
+      system_program_account_info,
+      fee,
+    )?;
+  }
+
+  // Paid users are passed from the first place down
+  for (place, reward) in payouts.iter().enumerate() {
     let user_account_info = next_account_info(account_info_iter)?;
-    if user_states.get_by_id(winners[i])?.get_pub_key() != *user_account_info.key {
+    if user_states.get_by_id(ranking[place])?.get_pub_key() != *user_account_info.key {
       return Err(SfsError::InvalidInstruction.into());
     }
-    let instruction = transfer(bank_account_info.key, user_account_info.key, reward);
-    let accounts = [
-      bank_account_info.clone(),
-      user_account_info.clone(),
-      system_program_account_info.clone(),
-    ];
-    invoke_signed(&instruction, &accounts, &[&[&[0, bump_seed]]])?;
+    helpers::transfer_from_vault(
+      root_info.key,
+      args.get_league_index(),
+      vault_bump_seed,
+      vault_account_info,
+      user_account_info,
+      system_program_account_info,
+      *reward,
+    )?;
   }
 
+  root.set_accrued_fees(
+    root
+      .get_accrued_fees()
+      .checked_add(fee)
+      .ok_or(SfsError::Overflow)?,
+  );
   league.set_is_reward_claimed(true);
   Ok(())
 }
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+  use super::helpers::tests::*;
+  use super::*;
+  use crate::spl_token::{self, TokenAccount};
+
+  #[test]
+  fn test_claim_reward() {
+    let program_id = pubkey_rand();
+    let admin_key = pubkey_rand();
+    let (root_key, mut root_account) =
+      season_open_root_account(&program_id, &pubkey_rand(), &admin_key);
+    let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+    let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+    let mut vault_account = SolanaAccount::new(303, 0, &Pubkey::default());
+    let mut bank_account = SolanaAccount::new(0, 0, &program_id);
+    let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+    let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+    let user_keys = [pubkey_rand(), pubkey_rand(), pubkey_rand()];
+
+    {
+      let root_data = &RefCell::new(&mut *root_account.data);
+      let players = Root::new(root_data).unwrap().get_players().unwrap();
+      let league_data = &RefCell::new(&mut *league_account.data);
+      let league = League::new(league_data, 0).unwrap();
+      league.set_bid(101);
+      league.set_fee_bps(1000);
+      league.set_start_week(1);
+      league.set_active_players_count(1);
+      let mut payouts = [0u8; PAYOUTS_LEN];
+      payouts[0..2].copy_from_slice(&6000u16.to_le_bytes());
+      payouts[2..4].copy_from_slice(&3000u16.to_le_bytes());
+      payouts[4..6].copy_from_slice(&1000u16.to_le_bytes());
+      league.set_payouts(&payouts);
+      league
+        .get_scoring_rules()
+        .unwrap()
+        .set_weight(Stat::RushingYards, 100);
+      let user_states = league.get_user_states().unwrap();
+      for (user_id, yards) in [10, 30, 20].iter().enumerate() {
+        let player_id = user_id as u16 + 1;
+        let lineups = user_states
+          .add(user_keys[user_id])
+          .unwrap()
+          .get_lineups()
+          .unwrap();
+        for week in 1..FOOTBALL_GAMES_COUNT + 1 {
+          lineups.get_by_week(week).unwrap().set(0, player_id);
+        }
+        players
+          .get_by_id(player_id)
+          .unwrap()
+          .get_scores()
+          .unwrap()
+          .get_by_week(1)
+          .unwrap()
+          .set_stat(Stat::RushingYards, *yards);
+      }
+    }
+
+    let args_data = &RefCell::new(&[0u8, 0u8] as &[u8]);
+    let args = ClaimRewardArgs::new(args_data, 0).unwrap();
+    let places = [user_keys[1], user_keys[2], user_keys[0]];
+    let mut user_accounts = [
+      SolanaAccount::new(0, 0, &Pubkey::default()),
+      SolanaAccount::new(0, 0, &Pubkey::default()),
+      SolanaAccount::new(0, 0, &Pubkey::default()),
+    ];
+
+    // season is not complete
+    let (first, rest) = user_accounts.split_at_mut(1);
+    let (second, third) = rest.split_at_mut(1);
+    assert_eq!(
+      Err(SfsError::InvalidStage.into()),
+      do_process_instruction(
+        claim_reward(
+          &program_id,
+          &root_key,
+          &league_key,
+          &vault_key,
+          &places,
+          args.clone()
+        )
+        .unwrap(),
+        vec![
+          &mut root_account,
+          &mut league_account,
+          &mut vault_account,
+          &mut bank_account,
+          &mut system_account,
+          &mut first[0],
+          &mut second[0],
+          &mut third[0],
+        ],
+      )
+    );
+
+    for _ in 0..FOOTBALL_GAMES_COUNT + 1 {
+      do_process_instruction(
+        increment_week(&program_id, &root_key, &admin_key).unwrap(),
+        vec![&mut root_account, &mut admin_account],
+      )
+      .unwrap();
+    }
+    do_process_instruction(
+      complete_season(&program_id, &root_key, &admin_key).unwrap(),
+      vec![&mut root_account, &mut admin_account],
+    )
+    .unwrap();
+
+    // vault holds less than the bids of every user
+    vault_account.lamports = 302;
+    assert_eq!(
+      Err(SfsError::VaultBalanceMismatch.into()),
+      do_process_instruction(
+        claim_reward(
+          &program_id,
+          &root_key,
+          &league_key,
+          &vault_key,
+          &places,
+          args.clone()
+        )
+        .unwrap(),
+        vec![
+          &mut root_account,
+          &mut league_account,
+          &mut vault_account,
+          &mut bank_account,
+          &mut system_account,
+          &mut first[0],
+          &mut second[0],
+          &mut third[0],
+        ],
+      )
+    );
+    vault_account.lamports = 303;
+
+    // users are not passed in the order of their places
+    assert_eq!(
+      Err(SfsError::InvalidInstruction.into()),
+      do_process_instruction(
+        claim_reward(
+          &program_id,
+          &root_key,
+          &league_key,
+          &vault_key,
+          &user_keys,
+          args.clone()
+        )
+        .unwrap(),
+        vec![
+          &mut root_account,
+          &mut league_account,
+          &mut vault_account,
+          &mut bank_account,
+          &mut system_account,
+          &mut first[0],
+          &mut second[0],
+          &mut third[0],
+        ],
+      )
+    );
+
+    do_process_instruction(
+      claim_reward(
+        &program_id,
+        &root_key,
+        &league_key,
+        &vault_key,
+        &places,
+        args.clone(),
+      )
+      .unwrap(),
+      vec![
+        &mut root_account,
+        &mut league_account,
+        &mut vault_account,
+        &mut bank_account,
+        &mut system_account,
+        &mut first[0],
+        &mut second[0],
+        &mut third[0],
+      ],
+    )
+    .unwrap();
+
+    let root_data = &RefCell::new(&mut *root_account.data);
+    let root = Root::new(root_data).unwrap();
+    assert_eq!(root.get_accrued_fees(), 30);
+    let players = root.get_players().unwrap();
+    let league_data = &RefCell::new(&mut *league_account.data);
+    let league = League::new(league_data, 0).unwrap();
+    assert_eq!(league.get_is_reward_claimed(), Ok(true));
+    assert_eq!(helpers::get_fee(&league), Ok(30));
+    let ranking = helpers::get_final_ranking(&players, &league, FOOTBALL_GAMES_COUNT).unwrap();
+    assert_eq!(ranking, vec![2, 3, 1]);
+    // pot of 303 less the fee split 60/30/10, the rounding leftover goes to the winner
+    assert_eq!(
+      helpers::get_payouts(&league, &ranking, 273),
+      Ok(vec![165, 81, 27])
+    );
+  }
+
+  #[test]
+  fn test_claim_token_reward() {
+    let program_id = pubkey_rand();
+    let admin_key = pubkey_rand();
+    let (root_key, mut root_account) =
+      season_open_root_account(&program_id, &pubkey_rand(), &admin_key);
+    let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+    let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+    let mut vault_account = SolanaAccount::new(0, 0, &Pubkey::default());
+    let mut bank_account = SolanaAccount::new(0, 0, &program_id);
+    let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+    let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+    let mut token_program_account = SolanaAccount::new(0, 0, &Pubkey::default());
+    let mint_key = pubkey_rand();
+    let treasury_key = pubkey_rand();
+    let token_vault_key = pubkey_rand();
+    let treasury_token_key = pubkey_rand();
+    let user_keys = [pubkey_rand(), pubkey_rand()];
+    let user_token_keys = [pubkey_rand()];
+
+    {
+      let root_data = &RefCell::new(&mut *root_account.data);
+      let root = Root::new(root_data).unwrap();
+      root.set_treasury(treasury_key);
+      let players = root.get_players().unwrap();
+      let league_data = &RefCell::new(&mut *league_account.data);
+      let league = League::new(league_data, 0).unwrap();
+      league.set_bid(100);
+      league.set_fee_bps(1000);
+      league.set_start_week(1);
+      league.set_active_players_count(1);
+      league.set_mint(mint_key);
+      league.set_token_vault(token_vault_key);
+      let mut payouts = [0u8; PAYOUTS_LEN];
+      payouts[0..2].copy_from_slice(&10000u16.to_le_bytes());
+      league.set_payouts(&payouts);
+      league
+        .get_scoring_rules()
+        .unwrap()
+        .set_weight(Stat::RushingYards, 100);
+      let user_states = league.get_user_states().unwrap();
+      for (user_id, yards) in [30, 10].iter().enumerate() {
+        let player_id = user_id as u16 + 1;
+        let lineups = user_states
+          .add(user_keys[user_id])
+          .unwrap()
+          .get_lineups()
+          .unwrap();
+        for week in 1..FOOTBALL_GAMES_COUNT + 1 {
+          lineups.get_by_week(week).unwrap().set(0, player_id);
+        }
+        players
+          .get_by_id(player_id)
+          .unwrap()
+          .get_scores()
+          .unwrap()
+          .get_by_week(1)
+          .unwrap()
+          .set_stat(Stat::RushingYards, *yards);
+      }
+    }
+
+    for _ in 0..FOOTBALL_GAMES_COUNT + 1 {
+      do_process_instruction(
+        increment_week(&program_id, &root_key, &admin_key).unwrap(),
+        vec![&mut root_account, &mut admin_account],
+      )
+      .unwrap();
+    }
+    do_process_instruction(
+      complete_season(&program_id, &root_key, &admin_key).unwrap(),
+      vec![&mut root_account, &mut admin_account],
+    )
+    .unwrap();
+
+    let args_data = &RefCell::new(&[0u8, 0u8] as &[u8]);
+    let args = ClaimRewardArgs::new(args_data, 0).unwrap();
+    let mut token_vault_account = SolanaAccount::new(0, 0, &spl_token::id());
+    token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 200);
+    let mut treasury_token_account = SolanaAccount::new(0, 0, &spl_token::id());
+    let mut user_token_account = SolanaAccount::new(0, 0, &spl_token::id());
+    user_token_account.data = TokenAccount::pack(&mint_key, &user_keys[0], 0);
+    let mut do_claim_token_reward = |treasury_token_data: Vec<u8>,
+                                     user_token_account: &mut SolanaAccount,
+                                     token_vault_account: &mut SolanaAccount| {
+      treasury_token_account.data = treasury_token_data;
+      do_process_instruction(
+        claim_token_reward(
+          &program_id,
+          &root_key,
+          &league_key,
+          &vault_key,
+          &token_vault_key,
+          &treasury_token_key,
+          &user_token_keys,
+          args.clone(),
+        )
+        .unwrap(),
+        vec![
+          &mut root_account,
+          &mut league_account,
+          &mut vault_account,
+          &mut bank_account,
+          &mut system_account,
+          &mut token_program_account,
+          token_vault_account,
+          &mut treasury_token_account,
+          user_token_account,
+        ],
+      )
+    };
+
+    // treasury token account belongs to someone else
+    assert_eq!(
+      Err(SfsError::InvalidInstruction.into()),
+      do_claim_token_reward(
+        TokenAccount::pack(&mint_key, &admin_key, 0),
+        &mut user_token_account,
+        &mut token_vault_account
+      )
+    );
+
+    // treasury token account holds another mint
+    assert_eq!(
+      Err(SfsError::InvalidInstruction.into()),
+      do_claim_token_reward(
+        TokenAccount::pack(&pubkey_rand(), &treasury_key, 0),
+        &mut user_token_account,
+        &mut token_vault_account
+      )
+    );
+
+    // token account of the vault holds less than the bids of every user
+    token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 199);
+    assert_eq!(
+      Err(SfsError::VaultBalanceMismatch.into()),
+      do_claim_token_reward(
+        TokenAccount::pack(&mint_key, &treasury_key, 0),
+        &mut user_token_account,
+        &mut token_vault_account
+      )
+    );
+    token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 200);
+
+    // the winner is paid to a token account of someone else
+    let mut wrong_user_token_account = SolanaAccount::new(0, 0, &spl_token::id());
+    wrong_user_token_account.data = TokenAccount::pack(&mint_key, &user_keys[1], 0);
+    assert_eq!(
+      Err(SfsError::InvalidInstruction.into()),
+      do_claim_token_reward(
+        TokenAccount::pack(&mint_key, &treasury_key, 0),
+        &mut wrong_user_token_account,
+        &mut token_vault_account
+      )
+    );
+
+    do_claim_token_reward(
+      TokenAccount::pack(&mint_key, &treasury_key, 0),
+      &mut user_token_account,
+      &mut token_vault_account,
+    )
+    .unwrap();
+
+    let root_data = &RefCell::new(&mut *root_account.data);
+    let root = Root::new(root_data).unwrap();
+    // the fee goes straight to the treasury instead of accruing in the bank
+    assert_eq!(root.get_accrued_fees(), 0);
+    let league_data = &RefCell::new(&mut *league_account.data);
+    let league = League::new(league_data, 0).unwrap();
+    assert_eq!(league.get_is_reward_claimed(), Ok(true));
+  }
+}
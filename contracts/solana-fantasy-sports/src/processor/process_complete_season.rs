@@ -0,0 +1,128 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [CompleteSeason](enum.SfsInstruction.html) instruction.
+pub fn process_complete_season<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let admin_account_info = next_account_info(account_info_iter)?;
+
+    helpers::validate_owner(program_id, &root.get_admin_authority(), admin_account_info)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    if root.get_current_week() <= root.get_games_count()? {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    root.set_stage(Stage::SeasonComplete);
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_complete_season() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            initialized_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        // season is not started
+        assert_eq!(
+            Err(SfsError::InvalidStage.into()),
+            do_process_instruction(
+                complete_season(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        add_test_players(&program_id, &root_key, &admin_key, &mut root_account, 1);
+        do_process_instruction(
+            start_season(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut admin_account],
+        )
+        .unwrap();
+
+        for _ in 0..FOOTBALL_GAMES_COUNT {
+            do_process_instruction(
+                increment_week(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+            .unwrap();
+        }
+
+        // last week is not finished yet
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                complete_season(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        do_process_instruction(
+            increment_week(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut admin_account],
+        )
+        .unwrap();
+
+        // not an admin
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                complete_season(&program_id, &root_key, &pubkey_rand()).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        do_process_instruction(
+            complete_season(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut admin_account],
+        )
+        .unwrap();
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        let root = Root::new(root_data).unwrap();
+        assert_eq!(root.get_stage(), Ok(Stage::SeasonComplete));
+    }
+}
@@ -0,0 +1,211 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [CorrectPlayerScore](enum.SfsInstruction.html) instruction.
+pub fn process_correct_player_score<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: CorrectPlayerScoreArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let oracle_account_info = next_account_info(account_info_iter)?;
+
+    helpers::validate_owner(program_id, &root.get_oracle_authority(), oracle_account_info)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    let week = args.get_week();
+    let current_week = root.get_current_week();
+    if week == 0 || week > current_week || week > root.get_games_count()? {
+        return Err(SfsError::InvalidState.into());
+    }
+    if current_week - week > root.get_score_correction_weeks() {
+        return Err(SfsError::ScoreCorrectionWindowClosed.into());
+    }
+
+    let score = root
+        .get_players()?
+        .get_by_id(args.get_player_id())?
+        .get_scores()?
+        .get_by_week(week)?;
+
+    // Only recorded scores can be corrected
+    if !score.get_is_initialized()? {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let stat = args.get_stat()?;
+    score.set_is_stat_corrected(stat);
+    score.set_previous_stat(stat, score.get_stat(stat));
+    score.set_corrections_count(
+        score
+            .get_corrections_count()
+            .checked_add(1)
+            .ok_or(SfsError::Overflow)?,
+    );
+    score.set_stat(stat, args.get_value());
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_correct_player_score() {
+        let program_id = pubkey_rand();
+        let oracle_key = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &oracle_key, &admin_key);
+        let mut oracle_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        do_process_instruction(
+            increment_week(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+
+        let mut args_data = Vec::<u8>::new();
+        args_data.extend_from_slice(&1u16.to_le_bytes());
+        args_data.push(1);
+        args_data.push(Stat::RushingYards as u8);
+        args_data.extend_from_slice(&72i16.to_le_bytes());
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = CorrectPlayerScoreArgs::new(args_data, 0).unwrap();
+
+        // nothing to correct yet
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                correct_player_score(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut oracle_account],
+            )
+        );
+
+        let mut score_args_data = Vec::<u8>::new();
+        score_args_data.extend_from_slice(&1u16.to_le_bytes());
+        score_args_data.extend_from_slice(&[0u8; UpdatePlayerScoreArgs::STATS_LEN]);
+        score_args_data[2 + Stat::RushingYards as usize * 2] = 65;
+        score_args_data[2 + Stat::Receptions as usize * 2] = 4;
+        let score_args_data = &RefCell::new(score_args_data.as_slice());
+        do_process_instruction(
+            update_player_score(
+                &program_id,
+                &root_key,
+                &oracle_key,
+                UpdatePlayerScoreArgs::new(score_args_data, 0).unwrap(),
+            )
+            .unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+
+        // not an oracle
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                correct_player_score(&program_id, &root_key, &admin_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut oracle_account],
+            )
+        );
+
+        // correct the score of the previous week while it is still in the window
+        do_process_instruction(
+            increment_week(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+        do_process_instruction(
+            correct_player_score(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+
+        // a later correction of another stat keeps the previous value of the first one
+        let mut receptions_args_data = Vec::<u8>::new();
+        receptions_args_data.extend_from_slice(&1u16.to_le_bytes());
+        receptions_args_data.push(1);
+        receptions_args_data.push(Stat::Receptions as u8);
+        receptions_args_data.extend_from_slice(&3i16.to_le_bytes());
+        let receptions_args_data = &RefCell::new(receptions_args_data.as_slice());
+        do_process_instruction(
+            correct_player_score(
+                &program_id,
+                &root_key,
+                &oracle_key,
+                CorrectPlayerScoreArgs::new(receptions_args_data, 0).unwrap(),
+            )
+            .unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+
+        do_process_instruction(
+            increment_week(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+        assert_eq!(
+            Err(SfsError::ScoreCorrectionWindowClosed.into()),
+            do_process_instruction(
+                correct_player_score(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut oracle_account],
+            )
+        );
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        let root = Root::new(root_data).unwrap();
+        let score = root
+            .get_players()
+            .unwrap()
+            .get_by_id(1)
+            .unwrap()
+            .get_scores()
+            .unwrap()
+            .get_by_week(1)
+            .unwrap();
+        assert_eq!(score.get_stat(Stat::RushingYards), 72);
+        assert_eq!(score.get_stat(Stat::Receptions), 3);
+        assert_eq!(score.get_corrections_count(), 2);
+        assert!(score.get_is_stat_corrected(Stat::RushingYards));
+        assert!(score.get_is_stat_corrected(Stat::Receptions));
+        assert!(!score.get_is_stat_corrected(Stat::ReceivingYards));
+        assert_eq!(score.get_previous_stat(Stat::RushingYards), 65);
+        assert_eq!(score.get_previous_stat(Stat::Receptions), 4);
+    }
+}
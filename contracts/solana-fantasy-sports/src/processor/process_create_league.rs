@@ -10,9 +10,7 @@ use crate::{
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use num_traits::FromPrimitive;
 use solana_program::program::invoke;
-use solana_program::program::invoke_signed;
 use solana_program::{
-    account::Account,
     account_info::{next_account_info, AccountInfo},
     decode_error::DecodeError,
     entrypoint::ProgramResult,
@@ -22,12 +20,13 @@ use solana_program::{
     program_option::COption,
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
-    system_instruction::{create_account, transfer, SystemInstruction},
-    sysvar::{rent::Rent, Sysvar},
+    system_instruction::{transfer, SystemInstruction},
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
 };
 use std::cell::RefCell;
 use std::convert::TryInto;
 
+/// Processes an [CreateLeague](enum.SfsInstruction.html) instruction.
 pub fn process_create_league<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -41,33 +40,758 @@ pub fn process_create_league<'a>(
         return Err(SfsError::InvalidStage.into());
     }
 
-    if root.get_current_week() >= consts::GAMES_COUNT {
+    if root.get_current_week() >= root.get_games_count()? {
         return Err(SfsError::InvalidState.into());
     }
 
+    // The league fills up when the last user joins, then draws its draft order
+    if args.get_users_limit() < 2 || args.get_users_limit() > LEAGUE_USERS_CAPACITY {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    let active_players_count = args.get_active_players_count();
+    let bench_players_count = args.get_bench_players_count();
+    if active_players_count == 0
+        || active_players_count as u16 + bench_players_count as u16 > TEAM_PLAYERS_CAPACITY as u16
+    {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    // Playoff is a single elimination bracket played over the last weeks of the season,
+    // the regular season before it needs a week left to start in
+    let playoff_teams_count = args.get_playoff_teams_count();
+    if playoff_teams_count != 0
+        && (!(2..=PLAYOFF_TEAMS_CAPACITY).contains(&playoff_teams_count)
+            || !playoff_teams_count.is_power_of_two()
+            || playoff_teams_count > args.get_users_limit()
+            || root.get_current_week() + playoff_teams_count.trailing_zeros() as u8
+                >= root.get_games_count()?)
+    {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    // Paid places come first and share the whole pot
+    let mut payouts_total = 0u32;
+    let mut is_place_paid = true;
+    for place in 1..LEAGUE_USERS_CAPACITY + 1 {
+        let payout = args.get_payout(place);
+        if payout > 0 && (!is_place_paid || place > args.get_users_limit()) {
+            return Err(SfsError::InvalidInstruction.into());
+        }
+        is_place_paid = payout > 0;
+        payouts_total += payout as u32;
+    }
+    if payouts_total != BPS_DENOMINATOR as u32 {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    // A password league without a password key could never be joined
+    let join_access = args.get_join_access()?;
+    if join_access == JoinAccess::Password && args.get_password_key() == Pubkey::default() {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    // Auction users need at least 1 for every roster slot and time to outbid each other
+    let draft_type = args.get_draft_type()?;
+    if draft_type == DraftType::Auction
+        && (args.get_auction_window() == 0
+            || args.get_auction_budget() < active_players_count as u32 + bench_players_count as u32)
+    {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    let league_account_info = next_account_info(account_info_iter)?;
     let user_account_info = next_account_info(account_info_iter)?;
-    let bank_account_info = next_account_info(account_info_iter)?;
+    let vault_account_info = next_account_info(account_info_iter)?;
     let system_program_account_info = next_account_info(account_info_iter)?;
+    let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
 
-    helpers::validate_bank(program_id, bank_account_info)?;
+    let fill_deadline = args.get_fill_deadline();
+    if fill_deadline != 0 && fill_deadline <= clock.unix_timestamp {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    let league_index = root.get_leagues_count();
+    let (league_pubkey, bump_seed) =
+        helpers::find_league_address(program_id, root_info.key, league_index);
+    if league_pubkey != *league_account_info.key {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    helpers::validate_vault(program_id, root_info.key, league_index, vault_account_info)?;
 
-    let instruction = transfer(user_account_info.key, bank_account_info.key, args.get_bid());
-    let accounts = [
-        user_account_info.clone(),
-        bank_account_info.clone(),
-        system_program_account_info.clone(),
-    ];
-    invoke(&instruction, &accounts)?;
+    helpers::create_pda_account(
+        user_account_info,
+        league_account_info,
+        system_program_account_info,
+        rent,
+        League::LEN,
+        program_id,
+        &[
+            root_info.key.as_ref(),
+            &league_index.to_le_bytes(),
+            &[bump_seed],
+        ],
+    )?;
 
-    let league = root.get_leagues()?.create()?;
+    // Token bids are escrowed in a token account owned by the vault
+    let mint = args.get_mint();
+    let token_vault_pubkey = if mint != Pubkey::default() {
+        let token_program_account_info = next_account_info(account_info_iter)?;
+        let user_token_account_info = next_account_info(account_info_iter)?;
+        let token_vault_account_info = next_account_info(account_info_iter)?;
+        helpers::validate_token_program(token_program_account_info)?;
+        helpers::validate_token_account(token_vault_account_info, &mint, vault_account_info.key)?;
+        helpers::transfer_tokens(
+            user_token_account_info,
+            token_vault_account_info,
+            user_account_info,
+            token_program_account_info,
+            args.get_bid(),
+        )?;
+        *token_vault_account_info.key
+    } else {
+        // The vault keeps a rent exempt reserve on top of the bids so that rent
+        // collection never takes its balance below the pot
+        let rent_reserve = rent
+            .minimum_balance(0)
+            .saturating_sub(vault_account_info.lamports());
+        let instruction = transfer(
+            user_account_info.key,
+            vault_account_info.key,
+            args.get_bid()
+                .checked_add(rent_reserve)
+                .ok_or(SfsError::Overflow)?,
+        );
+        let accounts = [
+            user_account_info.clone(),
+            vault_account_info.clone(),
+            system_program_account_info.clone(),
+        ];
+        invoke(&instruction, &accounts)?;
+        Pubkey::default()
+    };
+
+    let league = League::new(&league_account_info.data, 0)?;
+    if league.get_is_initialized()? {
+        return Err(SfsError::AlreadyInUse.into());
+    }
     league.set_name(args.get_name());
     league.set_bid(args.get_bid());
     league.set_users_limit(args.get_users_limit());
+    league.set_league_type(args.get_league_type()?);
+    let sport = root.get_sport()?;
+    league.set_sport(sport);
+    league.set_active_players_count(active_players_count);
+    league.set_bench_players_count(bench_players_count);
     league.set_is_initialized(true);
-    league.get_position_options()?.set(args.get_positions());
+    let position_options = league.get_position_options()?;
+    position_options.set(args.get_positions());
+    if position_options.get_total() != active_players_count as u16
+        || !position_options.is_for_sport(sport)
+    {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    let composition_rules = league.get_composition_rules()?;
+    composition_rules.set(args.get_composition_rules());
+    composition_rules.validate_rules(league.get_team_players_count(), sport)?;
+    league.get_scoring_rules()?.set(args.get_scoring_rules());
+    league.get_bracket()?.set_teams_count(playoff_teams_count);
+    league.set_payouts(args.get_payouts());
+    league.set_fee_bps(root.get_fee_bps());
+    league.set_mint(mint);
+    league.set_token_vault(token_vault_pubkey);
+    league.set_fill_deadline(fill_deadline);
+    league.set_join_access(join_access);
+    league.set_password_key(&args.get_password_key());
+    league.set_allow_list(args.get_allow_list());
+    league.set_pick_time_limit(args.get_pick_time_limit());
+    league.set_draft_type(draft_type);
+    league.set_auction_budget(args.get_auction_budget());
+    league.set_auction_window(args.get_auction_window());
 
     let user_state = league.get_user_states()?.add(*user_account_info.key)?;
     user_state.set_team_name(args.get_team_name());
+    user_state.set_budget(league.get_auction_budget());
+
+    root.set_leagues_count(league_index + 1);
 
     Ok(())
 }
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+    use crate::spl_token::{self, TokenAccount};
+
+    #[test]
+    fn test_create_league() {
+        let program_id = pubkey_rand();
+        let user_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let mut rent_sysvar = rent_sysvar();
+        let mut clock_account = clock_sysvar(1_600_000_000);
+        let mut user_account = SolanaAccount::new(1_000_000, 0, &Pubkey::default());
+        let mut vault_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        let mut args_data = Vec::<u8>::new();
+        args_data.extend_from_slice(&[1u8; LEAGUE_NAME_LEN]);
+        args_data.extend_from_slice(&100u64.to_le_bytes());
+        args_data.push(4);
+        args_data.extend_from_slice(&[2u8; TEAM_NAME_LEN]);
+        let mut positions = [0u8; PositionOptions::LEN];
+        positions[Position::QB as usize - 1] = 1;
+        positions[Position::RB as usize - 1] = 1;
+        positions[PositionOptions::FIXED_LEN] = 1;
+        positions[PositionOptions::FIXED_LEN + 1] = 1 << Position::RB as u8 | 1 << Position::WR as u8;
+        args_data.extend_from_slice(&positions);
+        args_data.push(3);
+        args_data.push(2);
+        let mut composition_rules = [0u8; CompositionRules::LEN];
+        for pos in Position::all().filter(|pos| Sport::Football.has_position(*pos)) {
+            composition_rules[(pos as usize - 1) * CompositionRules::ITEM_SIZE + 1] = 2;
+        }
+        composition_rules[(Position::QB as usize - 1) * CompositionRules::ITEM_SIZE] = 1;
+        args_data.extend_from_slice(&composition_rules);
+        args_data.extend_from_slice(&ScoringRules::football_preset(50));
+        args_data.push(LeagueType::HeadToHead as u8);
+        args_data.push(2);
+        let mut payouts = [0u8; PAYOUTS_LEN];
+        payouts[0..2].copy_from_slice(&7000u16.to_le_bytes());
+        payouts[2..4].copy_from_slice(&3000u16.to_le_bytes());
+        args_data.extend_from_slice(&payouts);
+        args_data.extend_from_slice(Pubkey::default().as_ref());
+        args_data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        args_data.push(JoinAccess::AllowList as u8);
+        args_data.extend_from_slice(&[0u8; PUB_KEY_LEN]);
+        let mut allow_list = [0u8; ALLOW_LIST_LEN];
+        allow_list[0..PUB_KEY_LEN].copy_from_slice(user_key.as_ref());
+        args_data.extend_from_slice(&allow_list);
+        args_data.extend_from_slice(&60u32.to_le_bytes());
+        args_data.push(DraftType::Auction as u8);
+        args_data.extend_from_slice(&200u32.to_le_bytes());
+        args_data.extend_from_slice(&30u32.to_le_bytes());
+        // bytes from the mint to the end
+        let tail_len = PUB_KEY_LEN + 8 + 1 + PUB_KEY_LEN + ALLOW_LIST_LEN + 4 + 1 + 4 + 4;
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = CreateLeagueArgs::new(args_data, 0).unwrap();
+
+        // allocated by the system program on-chain
+        let mut league_account = SolanaAccount::new(0, League::LEN, &program_id);
+        let (league_key, _) = helpers::find_league_address(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+
+        // roster does not fit into a team
+        let mut oversized_args_data = args_data.borrow().to_vec();
+        let len = oversized_args_data.len();
+        oversized_args_data[len - 3 - tail_len - PAYOUTS_LEN - CompositionRules::LEN - ScoringRules::LEN] = TEAM_PLAYERS_CAPACITY - 2;
+        let oversized_args_data = &RefCell::new(oversized_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    CreateLeagueArgs::new(oversized_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // playoff bracket needs a power of two teams
+        let mut bad_playoff_args_data = args_data.borrow().to_vec();
+        let len = bad_playoff_args_data.len();
+        bad_playoff_args_data[len - 1 - tail_len - PAYOUTS_LEN] = 3;
+        let bad_playoff_args_data = &RefCell::new(bad_playoff_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    CreateLeagueArgs::new(bad_playoff_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // the playoff takes the last week, leaving no week for the regular season to start in
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            Root::new(root_data)
+                .unwrap()
+                .set_current_week(FOOTBALL_GAMES_COUNT - 1);
+        }
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    CreateLeagueArgs::new(args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            Root::new(root_data).unwrap().set_current_week(0);
+        }
+
+        // payouts do not add up to the whole pot
+        let mut bad_payouts_args_data = args_data.borrow().to_vec();
+        let len = bad_payouts_args_data.len();
+        bad_payouts_args_data[len - tail_len - PAYOUTS_LEN + 4] = 1;
+        let bad_payouts_args_data = &RefCell::new(bad_payouts_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    CreateLeagueArgs::new(bad_payouts_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // more users than a league can hold
+        let mut crowded_args_data = args_data.borrow().to_vec();
+        crowded_args_data[LEAGUE_NAME_LEN + 8] = LEAGUE_USERS_CAPACITY + 1;
+        let crowded_args_data = &RefCell::new(crowded_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    CreateLeagueArgs::new(crowded_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // password league without a password key
+        let mut no_password_args_data = args_data.borrow().to_vec();
+        no_password_args_data[len - 13 - ALLOW_LIST_LEN - PUB_KEY_LEN - 1] = JoinAccess::Password as u8;
+        let no_password_args_data = &RefCell::new(no_password_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    CreateLeagueArgs::new(no_password_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // auction league without an auction window
+        let mut no_window_args_data = args_data.borrow().to_vec();
+        no_window_args_data[len - 4..].copy_from_slice(&0u32.to_le_bytes());
+        let no_window_args_data = &RefCell::new(no_window_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    CreateLeagueArgs::new(no_window_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // vault is not derived from the root and the league index
+        let (next_vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 1);
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &next_vault_key,
+                    &user_key,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // league address is not derived from the root and the next league index
+        let (next_league_key, _) = helpers::find_league_address(&program_id, &root_key, 1);
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(
+                    &program_id,
+                    &root_key,
+                    &next_league_key,
+                    &vault_key,
+                    &user_key,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // fill deadline is already over
+        let mut expired_clock_account = clock_sysvar(1_700_000_000);
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(&program_id, &root_key, &league_key, &vault_key, &user_key, args.clone())
+                    .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut expired_clock_account,
+                ],
+            )
+        );
+
+        // create new league
+        do_process_instruction(
+            create_league(&program_id, &root_key, &league_key, &vault_key, &user_key, args.clone()).unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut rent_sysvar,
+                &mut clock_account,
+            ],
+        )
+        .unwrap();
+
+        // the same address cannot be reused for the next league
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_league(&program_id, &root_key, &league_key, &vault_key, &user_key, args.clone())
+                    .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        // token bid is escrowed in a token account of the vault
+        let mint_key = pubkey_rand();
+        let mut token_args_data = args_data.borrow().to_vec();
+        let len = token_args_data.len();
+        token_args_data[len - tail_len..len - tail_len + PUB_KEY_LEN].copy_from_slice(mint_key.as_ref());
+        let token_args_data = &RefCell::new(token_args_data.as_slice());
+        let token_args = CreateLeagueArgs::new(token_args_data, 0).unwrap();
+        let mut token_league_account = SolanaAccount::new(0, League::LEN, &program_id);
+        let (token_league_key, _) = helpers::find_league_address(&program_id, &root_key, 1);
+        let (token_vault_owner_key, _) = helpers::find_vault_address(&program_id, &root_key, 1);
+        let mut token_vault_owner_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_token_key = pubkey_rand();
+        let mut user_token_account = SolanaAccount::new(0, 0, &spl_token::id());
+        user_token_account.data = TokenAccount::pack(&mint_key, &user_key, 100);
+        let token_vault_key = pubkey_rand();
+        let mut token_vault_account = SolanaAccount::new(0, 0, &spl_token::id());
+        let mut token_program_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        // token account of the vault belongs to someone else
+        token_vault_account.data = TokenAccount::pack(&mint_key, &user_key, 0);
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                create_token_league(
+                    &program_id,
+                    &root_key,
+                    &token_league_key,
+                    &token_vault_owner_key,
+                    &user_key,
+                    &user_token_key,
+                    &token_vault_key,
+                    token_args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut token_league_account,
+                    &mut user_account,
+                    &mut token_vault_owner_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                    &mut clock_account,
+                    &mut token_program_account,
+                    &mut user_token_account,
+                    &mut token_vault_account,
+                ],
+            )
+        );
+
+        token_vault_account.data = TokenAccount::pack(&mint_key, &token_vault_owner_key, 0);
+        do_process_instruction(
+            create_token_league(
+                &program_id,
+                &root_key,
+                &token_league_key,
+                &token_vault_owner_key,
+                &user_key,
+                &user_token_key,
+                &token_vault_key,
+                token_args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut token_league_account,
+                &mut user_account,
+                &mut token_vault_owner_account,
+                &mut system_account,
+                &mut rent_sysvar,
+                &mut clock_account,
+                &mut token_program_account,
+                &mut user_token_account,
+                &mut token_vault_account,
+            ],
+        )
+        .unwrap();
+        let token_league_data = &RefCell::new(&mut *token_league_account.data);
+        let token_league = League::new(token_league_data, 0).unwrap();
+        assert_eq!(token_league.get_mint(), mint_key);
+        assert_eq!(token_league.get_token_vault(), token_vault_key);
+        assert!(token_league.has_token_bid());
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        let root = Root::new(root_data).unwrap();
+        assert_eq!(root.get_leagues_count(), 2);
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        assert_eq!(league.get_is_initialized(), Ok(true));
+        assert_eq!(league.get_bid(), 100);
+        assert_eq!(league.get_users_limit(), 4);
+        assert_eq!(league.get_active_players_count(), 3);
+        assert_eq!(league.get_bench_players_count(), 2);
+        assert_eq!(league.get_team_players_count(), 5);
+        let composition_rules = league.get_composition_rules().unwrap();
+        assert_eq!(composition_rules.get_min(Position::QB), 1);
+        assert_eq!(composition_rules.get_max(Position::DEF), 2);
+        assert_eq!(composition_rules.get_max(Position::C), 0);
+        assert_eq!(league.get_sport(), Ok(Sport::Football));
+        assert_eq!(league.get_league_type(), Ok(LeagueType::HeadToHead));
+        let scoring_rules = league.get_scoring_rules().unwrap();
+        assert_eq!(scoring_rules.get_weight(Stat::Receptions), 50);
+        assert_eq!(league.get_bracket().unwrap().get_rounds_count(), 1);
+        assert_eq!(league.get_payout(1), 7000);
+        assert_eq!(league.get_payout(3), 0);
+        assert_eq!(league.get_pot(), Ok(100));
+        assert!(!league.has_token_bid());
+        assert_eq!(league.get_fill_deadline(), 1_700_000_000);
+        assert_eq!(league.get_join_access(), Ok(JoinAccess::AllowList));
+        assert_eq!(league.get_pick_time_limit(), 60);
+        assert_eq!(league.get_draft_type(), Ok(DraftType::Auction));
+        assert_eq!(league.get_auction_window(), 30);
+        assert_eq!(
+            league.get_user_states().unwrap().get_by_id(1).unwrap().get_budget(),
+            200
+        );
+        assert!(league.is_allow_listed(&user_key));
+        assert_eq!(league.get_regular_season_end(FOOTBALL_GAMES_COUNT), Ok(16));
+        let user_states = league.get_user_states().unwrap();
+        assert_eq!(user_states.get_count(), 1);
+        assert_eq!(user_states.get_by_id(1).unwrap().get_pub_key(), user_key);
+    }
+
+    #[test]
+    fn test_create_league_at_funded_address() {
+        let program_id = pubkey_rand();
+        let user_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let mut rent_sysvar = rent_sysvar();
+        let mut clock_account = clock_sysvar(1_600_000_000);
+        let mut user_account = SolanaAccount::new(1_000_000, 0, &Pubkey::default());
+        let mut vault_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        let mut args_data = Vec::<u8>::new();
+        args_data.extend_from_slice(&[1u8; LEAGUE_NAME_LEN]);
+        args_data.extend_from_slice(&100u64.to_le_bytes());
+        args_data.push(2);
+        args_data.extend_from_slice(&[2u8; TEAM_NAME_LEN]);
+        let mut positions = [0u8; PositionOptions::LEN];
+        positions[Position::QB as usize - 1] = 1;
+        args_data.extend_from_slice(&positions);
+        args_data.push(1);
+        args_data.push(1);
+        let mut composition_rules = [0u8; CompositionRules::LEN];
+        for pos in Position::all().filter(|pos| Sport::Football.has_position(*pos)) {
+            composition_rules[(pos as usize - 1) * CompositionRules::ITEM_SIZE + 1] = 2;
+        }
+        args_data.extend_from_slice(&composition_rules);
+        args_data.extend_from_slice(&ScoringRules::football_preset(0));
+        args_data.push(LeagueType::TotalPoints as u8);
+        args_data.push(0);
+        let mut payouts = [0u8; PAYOUTS_LEN];
+        payouts[0..2].copy_from_slice(&10000u16.to_le_bytes());
+        args_data.extend_from_slice(&payouts);
+        args_data.extend_from_slice(Pubkey::default().as_ref());
+        args_data.extend_from_slice(&0i64.to_le_bytes());
+        args_data.push(JoinAccess::Public as u8);
+        args_data.extend_from_slice(&[0u8; PUB_KEY_LEN]);
+        args_data.extend_from_slice(&[0u8; ALLOW_LIST_LEN]);
+        args_data.extend_from_slice(&0u32.to_le_bytes());
+        args_data.push(DraftType::Snake as u8);
+        args_data.extend_from_slice(&0u32.to_le_bytes());
+        args_data.extend_from_slice(&0u32.to_le_bytes());
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = CreateLeagueArgs::new(args_data, 0).unwrap();
+
+        // someone sent lamports to the next league address before it was created,
+        // the system program tops it up, allocates and assigns it on-chain
+        let mut league_account = SolanaAccount::new(1, League::LEN, &program_id);
+        let (league_key, _) = helpers::find_league_address(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+
+        do_process_instruction(
+            create_league(&program_id, &root_key, &league_key, &vault_key, &user_key, args).unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut rent_sysvar,
+                &mut clock_account,
+            ],
+        )
+        .unwrap();
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        assert_eq!(Root::new(root_data).unwrap().get_leagues_count(), 1);
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        assert_eq!(league.get_is_initialized(), Ok(true));
+        assert_eq!(
+            league.get_user_states().unwrap().get_by_id(1).unwrap().get_pub_key(),
+            user_key
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes a [DrawPickOrder](enum.SfsInstruction.html) instruction.
+pub fn process_draw_pick_order<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: DrawPickOrderArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let slot_hashes_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    if league.get_user_states()?.get_count() != league.get_users_limit()
+        || league.is_pick_order_drawn()?
+    {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    match helpers::get_slot_hash_after(slot_hashes_account_info, league.get_fill_slot())? {
+        Some(slot_hash) => {
+            helpers::set_random_pick_order(&league, league_account_info.key, &slot_hash)?;
+            league.set_pick_started_at(clock.unix_timestamp);
+        }
+        // Too late to find the slot after the fill, the order is drawn after this one instead
+        None => league.set_fill_slot(clock.slot),
+    }
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_draw_pick_order() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(3);
+            league.set_fill_slot(10);
+            let user_states = league.get_user_states().unwrap();
+            user_states.add(pubkey_rand()).unwrap();
+            user_states.add(pubkey_rand()).unwrap();
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        let args = DrawPickOrderArgs::new(args_data, 0).unwrap();
+        let mut draw =
+            |league_account: &mut SolanaAccount, slot_hashes: &[(u64, [u8; 32])], slot: u64| {
+                do_process_instruction(
+                    draw_pick_order(&program_id, &root_key, &league_key, args.clone()).unwrap(),
+                    vec![
+                        &mut root_account,
+                        league_account,
+                        &mut slot_hashes_sysvar(slot_hashes),
+                        &mut slot_clock_sysvar(slot, 2_000),
+                    ],
+                )
+            };
+
+        // league is not full yet
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            draw(&mut league_account, &[(11, [1u8; 32]), (10, [0u8; 32])], 12)
+        );
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league
+                .get_user_states()
+                .unwrap()
+                .add(pubkey_rand())
+                .unwrap();
+        }
+
+        // no slot after the fill has a hash yet
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            draw(&mut league_account, &[(10, [0u8; 32]), (9, [0u8; 32])], 11)
+        );
+
+        // the slot after the fill left the sysvar, the draw moves after the current slot
+        draw(
+            &mut league_account,
+            &[(600, [2u8; 32]), (599, [3u8; 32])],
+            601,
+        )
+        .unwrap();
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            assert_eq!(league.get_fill_slot(), 601);
+            assert_eq!(league.is_pick_order_drawn(), Ok(false));
+        }
+
+        // drawn from the hash of the first slot after the fill, skipped slots have no hash
+        draw(
+            &mut league_account,
+            &[(605, [4u8; 32]), (603, [5u8; 32]), (601, [6u8; 32])],
+            606,
+        )
+        .unwrap();
+        let mut expected_league_account = SolanaAccount::new(0, League::LEN, &program_id);
+        expected_league_account
+            .data
+            .copy_from_slice(&league_account.data);
+        {
+            let league_data = &RefCell::new(&mut *expected_league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            helpers::set_random_pick_order(&league, &league_key, &[5u8; 32]).unwrap();
+        }
+        assert_eq!(league_account.data, expected_league_account.data);
+
+        // the order is drawn only once
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            draw(
+                &mut league_account,
+                &[(607, [7u8; 32]), (601, [6u8; 32])],
+                608
+            )
+        );
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let pick_order = league.get_pick_order().unwrap();
+        let mut order = vec![pick_order.get(0), pick_order.get(1), pick_order.get(2)];
+        order.sort();
+        assert_eq!(order, vec![1, 2, 3]);
+        assert_eq!(league.get_pick_started_at(), 2_000);
+    }
+}
@@ -35,13 +35,17 @@ pub fn process_increment_week<'a>(
   let account_info_iter = &mut accounts.iter();
   let root_info = next_account_info(account_info_iter)?;
   let root = Root::new(&root_info.data)?;
-  let user_account_info = next_account_info(account_info_iter)?;
+  let admin_account_info = next_account_info(account_info_iter)?;
 
-  helpers::validate_owner(program_id, &root.get_oracle_authority(), user_account_info)?;
+  helpers::validate_owner(program_id, &root.get_admin_authority(), admin_account_info)?;
+
+  if root.get_stage()? != Stage::SeasonOpen {
+    return Err(SfsError::InvalidStage.into());
+  }
 
   let current_week = root.get_current_week();
 
-  if current_week > GAMES_COUNT {
+  if current_week > root.get_games_count()? {
     return Err(SfsError::InvalidState.into());
   }
 
@@ -49,3 +53,40 @@ pub fn process_increment_week<'a>(
 
   Ok(())
 }
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+  use super::helpers::tests::*;
+  use super::*;
+
+  #[test]
+  fn test_increment_week() {
+    let program_id = pubkey_rand();
+    let oracle_key = pubkey_rand();
+    let admin_key = pubkey_rand();
+    let (root_key, mut root_account) =
+      season_open_root_account(&program_id, &oracle_key, &admin_key);
+    let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+    // not an admin
+    assert_eq!(
+      Err(SfsError::OwnerMismatch.into()),
+      do_process_instruction(
+        increment_week(&program_id, &root_key, &oracle_key).unwrap(),
+        vec![&mut root_account, &mut admin_account],
+      )
+    );
+
+    do_process_instruction(
+      increment_week(&program_id, &root_key, &admin_key).unwrap(),
+      vec![&mut root_account, &mut admin_account],
+    )
+    .unwrap();
+
+    let root_data = &RefCell::new(&mut *root_account.data);
+    let root = Root::new(root_data).unwrap();
+    assert_eq!(root.get_current_week(), 1);
+  }
+}
@@ -47,9 +47,17 @@ pub fn process_initialize_root<'a>(
         return Err(SfsError::NotRentExempt.into());
     }
 
+    let sport = args.get_sport()?;
+    if sport == Sport::Uninitialized {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    root.set_sport(sport);
     root.set_oracle_authority(args.get_oracle_authority());
+    root.set_admin_authority(args.get_admin_authority());
     root.set_current_week(args.get_current_week());
-    root.set_stage(Stage::SeasonOpen);
+    root.set_score_correction_weeks(args.get_score_correction_weeks());
+    root.set_stage(Stage::DraftSelection);
 
     Ok(())
 }
@@ -61,21 +69,22 @@ pub fn process_initialize_root<'a>(
 mod tests {
     use super::helpers::tests::*;
     use super::*;
-    use solana_program::{
-        account::Account as SolanaAccount, account_info::create_is_signer_account_infos,
-        clock::Epoch, instruction::Instruction, sysvar::rent,
-    };
 
     #[test]
     fn test_initialize_root() {
         let program_id = pubkey_rand();
         let owner_key = pubkey_rand();
+        let admin_key = pubkey_rand();
         let root_key = pubkey_rand();
         let mut root_account = SolanaAccount::new(42, Root::LEN, &program_id);
         let mut rent_sysvar = rent_sysvar();
 
         let mut args_data = Vec::<u8>::new();
         args_data.extend_from_slice(owner_key.as_ref());
+        args_data.push(0);
+        args_data.extend_from_slice(admin_key.as_ref());
+        args_data.push(Sport::Football as u8);
+        args_data.push(1);
 
         let args_data = &RefCell::new(args_data.as_slice());
         let args = InitializeRootArgs::new(args_data, 0).unwrap();
@@ -109,5 +118,8 @@ mod tests {
         let root_data = &RefCell::new(&mut *root_account.data);
         let root = Root::new(root_data).unwrap();
         assert_eq!(root.get_oracle_authority(), owner_key);
+        assert_eq!(root.get_admin_authority(), admin_key);
+        assert_eq!(root.get_sport(), Ok(Sport::Football));
+        assert_eq!(root.get_score_correction_weeks(), 1);
     }
 }
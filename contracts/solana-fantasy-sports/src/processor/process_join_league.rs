@@ -22,11 +22,13 @@ use solana_program::{
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
     system_instruction::{transfer, SystemInstruction},
-    sysvar::{rent::Rent, Sysvar},
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
 };
 use std::cell::RefCell;
 use std::convert::TryInto;
 
+/// Processes a [JoinLeague](enum.SfsInstruction.html) instruction.
+/// Every precondition is checked before the bid is transferred.
 pub fn process_join_league<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -35,40 +37,568 @@ pub fn process_join_league<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
 
     if root.get_stage()? != Stage::SeasonOpen {
         return Err(SfsError::InvalidStage.into());
     }
 
-    if root.get_current_week() >= consts::GAMES_COUNT {
-        return Err(SfsError::InvalidState.into());
+    if root.get_current_week() >= root.get_games_count()? {
+        return Err(SfsError::JoinClosed.into());
     }
 
     let user_account_info = next_account_info(account_info_iter)?;
-    let bank_account_info = next_account_info(account_info_iter)?;
+    if !user_account_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let vault_account_info = next_account_info(account_info_iter)?;
     let system_program_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
 
-    helpers::validate_bank(program_id, bank_account_info)?;
-    let league = root.get_leagues()?.get(args.get_league_index())?;
-
-    let instruction = transfer(
-        user_account_info.key,
-        bank_account_info.key,
-        league.get_bid(),
-    );
-    let accounts = [
-        user_account_info.clone(),
-        bank_account_info.clone(),
-        system_program_account_info.clone(),
-    ];
-    invoke(&instruction, &accounts)?;
-
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    if league.is_fill_deadline_passed(clock.unix_timestamp) {
+        return Err(SfsError::FillDeadlinePassed.into());
+    }
+    if league.is_schedule_missed(root.get_current_week(), root.get_games_count()?)? {
+        return Err(SfsError::JoinClosed.into());
+    }
     let user_states = league.get_user_states()?;
-    if user_states.get_count() == league.get_users_limit() {
-        return Err(SfsError::OutOfCapacity.into());
+    if user_states.get_count() >= league.get_users_limit() {
+        return Err(SfsError::LeagueFull.into());
+    }
+    if user_states.contains(user_account_info.key)? {
+        return Err(SfsError::AlreadyJoined.into());
     }
+    match league.get_join_access()? {
+        JoinAccess::Public => {}
+        JoinAccess::Password => {
+            let password_account_info = next_account_info(account_info_iter)?;
+            if *password_account_info.key != league.get_password_key()
+                || !password_account_info.is_signer
+            {
+                return Err(SfsError::JoinNotAllowed.into());
+            }
+        }
+        JoinAccess::AllowList => {
+            if !league.is_allow_listed(user_account_info.key) {
+                return Err(SfsError::JoinNotAllowed.into());
+            }
+        }
+    }
+    helpers::validate_vault(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        vault_account_info,
+    )?;
+
+    if league.has_token_bid() {
+        let token_program_account_info = next_account_info(account_info_iter)?;
+        let user_token_account_info = next_account_info(account_info_iter)?;
+        let token_vault_account_info = next_account_info(account_info_iter)?;
+        helpers::validate_token_program(token_program_account_info)?;
+        if *token_vault_account_info.key != league.get_token_vault() {
+            return Err(SfsError::InvalidInstruction.into());
+        }
+        helpers::validate_vault_balance(&league, token_vault_account_info)?;
+        helpers::transfer_tokens(
+            user_token_account_info,
+            token_vault_account_info,
+            user_account_info,
+            token_program_account_info,
+            league.get_bid(),
+        )?;
+    } else {
+        helpers::validate_vault_balance(&league, vault_account_info)?;
+        let instruction = transfer(
+            user_account_info.key,
+            vault_account_info.key,
+            league.get_bid(),
+        );
+        let accounts = [
+            user_account_info.clone(),
+            vault_account_info.clone(),
+            system_program_account_info.clone(),
+        ];
+        invoke(&instruction, &accounts)?;
+    }
+
     let user_state = user_states.add(*user_account_info.key)?;
     user_state.set_team_name(args.get_team_name());
+    user_state.set_budget(league.get_auction_budget());
+
+    // The draft order is drawn by `DrawPickOrder` from a slot the user filling the league
+    // cannot know yet
+    if user_states.get_count() == league.get_users_limit() {
+        league.set_fill_slot(clock.slot);
+    }
 
     Ok(())
 }
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+    use crate::spl_token::{self, TokenAccount};
+
+    fn do_join_league(
+        program_id: &Pubkey,
+        root_key: &Pubkey,
+        root_account: &mut SolanaAccount,
+        league_account: &mut SolanaAccount,
+        vault_account: &mut SolanaAccount,
+        user_key: &Pubkey,
+    ) -> ProgramResult {
+        let (league_key, _) = helpers::find_league_address(program_id, root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(program_id, root_key, 0);
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut clock_account = slot_clock_sysvar(42, 1_000);
+        let args_data = vec![0u8; JoinLeagueArgs::LEN];
+        let args_data = &RefCell::new(args_data.as_slice());
+        do_process_instruction(
+            join_league(
+                program_id,
+                root_key,
+                &league_key,
+                &vault_key,
+                user_key,
+                None,
+                JoinLeagueArgs::new(args_data, 0).unwrap(),
+            )
+            .unwrap(),
+            vec![
+                root_account,
+                league_account,
+                &mut user_account,
+                vault_account,
+                &mut system_account,
+                &mut clock_account,
+            ],
+        )
+    }
+
+    #[test]
+    fn test_join_league() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut vault_account = SolanaAccount::new(100, 0, &Pubkey::default());
+        let creator_key = pubkey_rand();
+        let user_key = pubkey_rand();
+
+        // season is not open yet
+        let (root_key, mut root_account) =
+            initialized_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let (_, mut unopened_league_account) = league_account(&program_id, &root_key, 0);
+        assert_eq!(
+            Err(SfsError::InvalidStage.into()),
+            do_join_league(
+                &program_id,
+                &root_key,
+                &mut root_account,
+                &mut unopened_league_account,
+                &mut vault_account,
+                &user_key,
+            )
+        );
+
+        // league account is not initialized
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let mut uninitialized_league_account = SolanaAccount::new(0, League::LEN, &program_id);
+        assert_eq!(
+            Err(SfsError::LeagueNotInitialized.into()),
+            do_join_league(
+                &program_id,
+                &root_key,
+                &mut root_account,
+                &mut uninitialized_league_account,
+                &mut vault_account,
+                &user_key,
+            )
+        );
+
+        let (_, mut league_account) = league_account(&program_id, &root_key, 0);
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(2);
+            league.get_user_states().unwrap().add(creator_key).unwrap();
+            league.set_fill_deadline(1_000);
+        }
+
+        // the league ran out of time to fill
+        assert_eq!(
+            Err(SfsError::FillDeadlinePassed.into()),
+            do_join_league(
+                &program_id,
+                &root_key,
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &user_key,
+            )
+        );
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_fill_deadline(1_001);
+            league.get_bracket().unwrap().set_teams_count(2);
+        }
+
+        // the playoff takes the last week, the regular season has no week left to start in
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            Root::new(root_data)
+                .unwrap()
+                .set_current_week(FOOTBALL_GAMES_COUNT - 1);
+        }
+        assert_eq!(
+            Err(SfsError::JoinClosed.into()),
+            do_join_league(
+                &program_id,
+                &root_key,
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &user_key,
+            )
+        );
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            Root::new(root_data).unwrap().set_current_week(0);
+        }
+
+        // the creator is already in the league
+        assert_eq!(
+            Err(SfsError::AlreadyJoined.into()),
+            do_join_league(
+                &program_id,
+                &root_key,
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &creator_key,
+            )
+        );
+
+        do_join_league(
+            &program_id,
+            &root_key,
+            &mut root_account,
+            &mut league_account,
+            &mut vault_account,
+            &user_key,
+        )
+        .unwrap();
+        vault_account.lamports = 200;
+
+        assert_eq!(
+            Err(SfsError::LeagueFull.into()),
+            do_join_league(
+                &program_id,
+                &root_key,
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &pubkey_rand(),
+            )
+        );
+
+        for _ in 0..FOOTBALL_GAMES_COUNT {
+            do_process_instruction(
+                increment_week(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            Err(SfsError::JoinClosed.into()),
+            do_join_league(
+                &program_id,
+                &root_key,
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &pubkey_rand(),
+            )
+        );
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let user_states = league.get_user_states().unwrap();
+        assert_eq!(user_states.get_count(), 2);
+        assert_eq!(user_states.get_by_id(2).unwrap().get_pub_key(), user_key);
+        // the order is drawn later from a slot after the one the league filled up in
+        assert_eq!(league.get_fill_slot(), 42);
+        assert_eq!(league.is_pick_order_drawn(), Ok(false));
+    }
+
+    #[test]
+    fn test_join_private_league() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut clock_account = clock_sysvar(1_000);
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_key = pubkey_rand();
+        let password_key = pubkey_rand();
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(4);
+            league.set_join_access(JoinAccess::Password);
+            league.set_password_key(&password_key);
+        }
+
+        let args_data = vec![0u8; JoinLeagueArgs::LEN];
+        let args_data = &RefCell::new(args_data.as_slice());
+        let mut password_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        // signed with a key that is not the one of the password
+        assert_eq!(
+            Err(SfsError::JoinNotAllowed.into()),
+            do_process_instruction(
+                join_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_key,
+                    Some(&pubkey_rand()),
+                    JoinLeagueArgs::new(args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut clock_account,
+                    &mut password_account,
+                ],
+            )
+        );
+
+        // the password key is known but did not sign
+        let mut instruction = join_league(
+            &program_id,
+            &root_key,
+            &league_key,
+            &vault_key,
+            &user_key,
+            Some(&password_key),
+            JoinLeagueArgs::new(args_data, 0).unwrap(),
+        )
+        .unwrap();
+        instruction.accounts[6].is_signer = false;
+        assert_eq!(
+            Err(SfsError::JoinNotAllowed.into()),
+            do_process_instruction(
+                instruction,
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut clock_account,
+                    &mut password_account,
+                ],
+            )
+        );
+
+        do_process_instruction(
+            join_league(
+                &program_id,
+                &root_key,
+                &league_key,
+                &vault_key,
+                &user_key,
+                Some(&password_key),
+                JoinLeagueArgs::new(args_data, 0).unwrap(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut clock_account,
+                &mut password_account,
+            ],
+        )
+        .unwrap();
+
+        let allowed_key = pubkey_rand();
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_join_access(JoinAccess::AllowList);
+            let mut allow_list = [0u8; ALLOW_LIST_LEN];
+            allow_list[PUB_KEY_LEN..2 * PUB_KEY_LEN].copy_from_slice(allowed_key.as_ref());
+            league.set_allow_list(&allow_list);
+        }
+
+        // only wallets on the allow-list can join
+        assert_eq!(
+            Err(SfsError::JoinNotAllowed.into()),
+            do_process_instruction(
+                join_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &pubkey_rand(),
+                    None,
+                    JoinLeagueArgs::new(args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut clock_account,
+                ],
+            )
+        );
+
+        do_process_instruction(
+            join_league(
+                &program_id,
+                &root_key,
+                &league_key,
+                &vault_key,
+                &allowed_key,
+                None,
+                JoinLeagueArgs::new(args_data, 0).unwrap(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut clock_account,
+            ],
+        )
+        .unwrap();
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let user_states = league.get_user_states().unwrap();
+        assert_eq!(user_states.get_count(), 2);
+        assert_eq!(user_states.get_by_id(2).unwrap().get_pub_key(), allowed_key);
+    }
+
+    #[test]
+    fn test_join_token_league() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut clock_account = slot_clock_sysvar(42, 1_000);
+        let mut token_program_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mint_key = pubkey_rand();
+        let token_vault_key = pubkey_rand();
+        let user_key = pubkey_rand();
+        let user_token_key = pubkey_rand();
+        let mut user_token_account = SolanaAccount::new(0, 0, &spl_token::id());
+        user_token_account.data = TokenAccount::pack(&mint_key, &user_key, 100);
+        let mut token_vault_account = SolanaAccount::new(0, 0, &spl_token::id());
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(2);
+            league.set_mint(mint_key);
+            league.set_token_vault(token_vault_key);
+            league
+                .get_user_states()
+                .unwrap()
+                .add(pubkey_rand())
+                .unwrap();
+        }
+
+        let args_data = vec![0u8; JoinLeagueArgs::LEN];
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = JoinLeagueArgs::new(args_data, 0).unwrap();
+        let mut do_join_token_league =
+            |token_vault_key: &Pubkey, token_vault_account: &mut SolanaAccount| {
+                do_process_instruction(
+                    join_token_league(
+                        &program_id,
+                        &root_key,
+                        &league_key,
+                        &vault_key,
+                        &user_key,
+                        None,
+                        &user_token_key,
+                        token_vault_key,
+                        args.clone(),
+                    )
+                    .unwrap(),
+                    vec![
+                        &mut root_account,
+                        &mut league_account,
+                        &mut user_account,
+                        &mut vault_account,
+                        &mut system_account,
+                        &mut clock_account,
+                        &mut token_program_account,
+                        &mut user_token_account,
+                        token_vault_account,
+                    ],
+                )
+            };
+
+        // token account is not the one of the league vault
+        token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 100);
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_join_token_league(&pubkey_rand(), &mut token_vault_account)
+        );
+
+        // token account of the vault holds less than the bid of the creator
+        token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 99);
+        assert_eq!(
+            Err(SfsError::VaultBalanceMismatch.into()),
+            do_join_token_league(&token_vault_key, &mut token_vault_account)
+        );
+
+        token_vault_account.data = TokenAccount::pack(&mint_key, &vault_key, 100);
+        do_join_token_league(&token_vault_key, &mut token_vault_account).unwrap();
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let user_states = league.get_user_states().unwrap();
+        assert_eq!(user_states.get_count(), 2);
+        assert_eq!(user_states.get_by_id(2).unwrap().get_pub_key(), user_key);
+        assert_eq!(league.get_pot(), Ok(200));
+    }
+}
@@ -0,0 +1,327 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes a [LeaveLeague](enum.SfsInstruction.html) instruction.
+pub fn process_leave_league<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: LeaveLeagueArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let user_account_info = next_account_info(account_info_iter)?;
+    let vault_account_info = next_account_info(account_info_iter)?;
+    let system_program_account_info = next_account_info(account_info_iter)?;
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    let user_states = league.get_user_states()?;
+    let user_pubkey = user_states.get_by_id(args.get_user_id())?.get_pub_key();
+    helpers::validate_owner(program_id, &user_pubkey, user_account_info)?;
+
+    // Teams are fixed once the pick order is drawn, leaving would shift the user ids
+    // the order refers to. The creator cancels the league instead
+    if league.is_pick_order_drawn()?
+        || league.get_current_pick() != 0
+        || league.get_nominated_player_id() != 0
+        || args.get_user_id() == 1
+    {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let vault_bump_seed = helpers::validate_vault(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        vault_account_info,
+    )?;
+    let token_account_infos = helpers::next_token_vault_account_infos(&league, account_info_iter)?;
+    let refund_account_info = match token_account_infos {
+        Some((_, token_vault_account_info)) => {
+            helpers::validate_vault_balance(&league, token_vault_account_info)?;
+            next_account_info(account_info_iter)?
+        }
+        None => {
+            helpers::validate_vault_balance(&league, vault_account_info)?;
+            user_account_info
+        }
+    };
+
+    helpers::pay_from_vault(
+        root_info.key,
+        &league,
+        args.get_league_index(),
+        vault_bump_seed,
+        vault_account_info,
+        system_program_account_info,
+        token_account_infos,
+        &user_pubkey,
+        refund_account_info,
+        league.get_bid(),
+    )?;
+    user_states.remove(args.get_user_id())?;
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_leave_league() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(300, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_keys = [pubkey_rand(), pubkey_rand(), pubkey_rand()];
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(4);
+            for user_key in user_keys.iter() {
+                league.get_user_states().unwrap().add(*user_key).unwrap();
+            }
+        }
+
+        // the creator cannot leave
+        let args_data = &RefCell::new(&[0u8, 0, 1] as &[u8]);
+        let args = LeaveLeagueArgs::new(args_data, 0).unwrap();
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                leave_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[0],
+                    None,
+                    args
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                ],
+            )
+        );
+
+        let args_data = &RefCell::new(&[0u8, 0, 2] as &[u8]);
+        let args = LeaveLeagueArgs::new(args_data, 0).unwrap();
+
+        // only the user can leave
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                leave_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[2],
+                    None,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                ],
+            )
+        );
+
+        // vault holds less than the bids of every user
+        vault_account.lamports = 299;
+        assert_eq!(
+            Err(SfsError::VaultBalanceMismatch.into()),
+            do_process_instruction(
+                leave_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[1],
+                    None,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                ],
+            )
+        );
+        vault_account.lamports = 300;
+
+        do_process_instruction(
+            leave_league(
+                &program_id,
+                &root_key,
+                &league_key,
+                &vault_key,
+                &user_keys[1],
+                None,
+                args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut vault_account,
+                &mut system_account,
+            ],
+        )
+        .unwrap();
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            let user_states = league.get_user_states().unwrap();
+            assert_eq!(user_states.get_count(), 2);
+            assert_eq!(
+                user_states.get_by_id(2).unwrap().get_pub_key(),
+                user_keys[2]
+            );
+            assert_eq!(league.get_pot(), Ok(200));
+            league.set_nominated_player_id(1);
+        }
+
+        // an auction draft starts with the first nomination
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                leave_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[2],
+                    None,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                ],
+            )
+        );
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_nominated_player_id(0);
+            let pick_order = league.get_pick_order().unwrap();
+            pick_order.set(0, 2);
+            pick_order.set(1, 1);
+        }
+
+        // the pick order is drawn before the first pick
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                leave_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[2],
+                    None,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                ],
+            )
+        );
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_current_pick(1);
+        }
+
+        // teams are fixed once the draft has started
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                leave_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    &user_keys[2],
+                    None,
+                    args
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut vault_account,
+                    &mut system_account,
+                ],
+            )
+        );
+    }
+}
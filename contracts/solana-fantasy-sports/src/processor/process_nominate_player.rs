@@ -0,0 +1,260 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes a [NominatePlayer](enum.SfsInstruction.html) instruction.
+pub fn process_nominate_player<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: NominatePlayerArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let user_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    if root.get_current_week() >= root.get_games_count()? {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let player_id = args.get_player_id();
+    if player_id == 0 || player_id > root.get_players()?.get_count() {
+        return Err(SfsError::IndexOutOfRange.into());
+    }
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    let user_state = league.get_user_states()?.get_by_id(args.get_user_id())?;
+    helpers::validate_owner(program_id, &user_state.get_pub_key(), user_account_info)?;
+
+    if league.get_user_states()?.get_count() != league.get_users_limit()
+        || league.get_draft_type()? != DraftType::Auction
+    {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    // The previous auction is settled by the next nomination once its window is over
+    if league.get_nominated_player_id() != 0 {
+        helpers::close_auction(&league, clock.unix_timestamp)?;
+    }
+    if league.get_pick_round()? >= league.get_team_players_count() {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    helpers::validate_player_available(&league, player_id)?;
+    helpers::validate_auction_bid(
+        &root.get_players()?,
+        &league,
+        args.get_user_id(),
+        player_id,
+        args.get_bid(),
+    )?;
+    league.set_nominated_player_id(player_id);
+    league.set_high_bid(args.get_bid());
+    league.set_high_bidder_id(args.get_user_id());
+    league.set_auction_deadline(clock.unix_timestamp + league.get_auction_window() as i64);
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    fn nominate_args_data(user_id: u8, player_id: u16, bid: u32) -> Vec<u8> {
+        let mut data = vec![0u8, 0, user_id];
+        data.extend_from_slice(&player_id.to_le_bytes());
+        data.extend_from_slice(&bid.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_nominate_player() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_keys = [pubkey_rand(), pubkey_rand()];
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(2);
+            league.set_active_players_count(2);
+            league.set_auction_budget(10);
+            league.set_auction_window(30);
+            league
+                .get_composition_rules()
+                .unwrap()
+                .set_max(Position::RB, 2);
+            for user_key in user_keys.iter() {
+                let user_state = league.get_user_states().unwrap().add(*user_key).unwrap();
+                user_state.set_budget(10);
+            }
+        }
+
+        let args_data = nominate_args_data(1, 1, 3);
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = NominatePlayerArgs::new(args_data, 0).unwrap();
+
+        // snake draft league
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                nominate_player(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_keys[0],
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut clock_sysvar(1_000),
+                ],
+            )
+        );
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0)
+                .unwrap()
+                .set_draft_type(DraftType::Auction);
+        }
+
+        // the whole budget leaves nothing for the other roster slot
+        let all_in_args_data = nominate_args_data(1, 1, 10);
+        let all_in_args_data = &RefCell::new(all_in_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InsufficientFunds.into()),
+            do_process_instruction(
+                nominate_player(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_keys[0],
+                    NominatePlayerArgs::new(all_in_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut clock_sysvar(1_000),
+                ],
+            )
+        );
+
+        do_process_instruction(
+            nominate_player(
+                &program_id,
+                &root_key,
+                &league_key,
+                &user_keys[0],
+                args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut clock_sysvar(1_000),
+            ],
+        )
+        .unwrap();
+
+        // the first player is still up for auction
+        let next_args_data = nominate_args_data(2, 2, 1);
+        let next_args_data = &RefCell::new(next_args_data.as_slice());
+        let next_args = NominatePlayerArgs::new(next_args_data, 0).unwrap();
+        assert_eq!(
+            Err(SfsError::AuctionInProgress.into()),
+            do_process_instruction(
+                nominate_player(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_keys[1],
+                    next_args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut clock_sysvar(1_029),
+                ],
+            )
+        );
+
+        do_process_instruction(
+            nominate_player(
+                &program_id,
+                &root_key,
+                &league_key,
+                &user_keys[1],
+                next_args,
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut clock_sysvar(1_030),
+            ],
+        )
+        .unwrap();
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let user_state = league.get_user_states().unwrap().get_by_id(1).unwrap();
+        assert_eq!(user_state.get_user_players().unwrap().get(0), 1);
+        assert_eq!(user_state.get_budget(), 7);
+        assert_eq!(league.get_current_pick(), 1);
+        assert_eq!(league.get_nominated_player_id(), 2);
+        assert_eq!(league.get_high_bid(), 1);
+        assert_eq!(league.get_high_bidder_id(), 2);
+        assert_eq!(league.get_auction_deadline(), 1_060);
+    }
+}
@@ -22,12 +22,12 @@ use solana_program::{
     program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
     system_instruction::SystemInstruction,
-    sysvar::{rent::Rent, Sysvar},
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
 };
 use std::cell::RefCell;
 use std::convert::TryInto;
 
-/// Processes an [AddPlayers](enum.SfsInstruction.html) instruction.
+/// Processes a [PickPlayer](enum.SfsInstruction.html) instruction.
 pub fn process_pick_player<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -36,13 +36,15 @@ pub fn process_pick_player<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
     let user_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
 
     if root.get_stage()? != Stage::SeasonOpen {
         return Err(SfsError::InvalidStage.into());
     }
 
-    if root.get_current_week() >= consts::GAMES_COUNT {
+    if root.get_current_week() >= root.get_games_count()? {
         return Err(SfsError::InvalidState.into());
     }
 
@@ -52,52 +54,37 @@ pub fn process_pick_player<'a>(
         return Err(SfsError::IndexOutOfRange.into());
     }
 
-    let league = root.get_leagues()?.get(args.get_league_index())?;
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
     let user_state = league.get_user_states()?.get_by_id(args.get_user_id())?;
 
     helpers::validate_owner(program_id, &user_state.get_pub_key(), user_account_info)?;
 
     let users_count = league.get_user_states()?.get_count();
 
-    if users_count != league.get_users_limit() {
+    if users_count != league.get_users_limit()
+        || league.get_draft_type()? != DraftType::Snake
+        || !league.is_pick_order_drawn()?
+    {
         return Err(SfsError::InvalidState.into());
     }
 
-    let mut league_pick_order = Vec::<u8>::with_capacity(users_count as usize);
-    for i in 0..PickOrderList::ITEM_COUNT {
-        let pick = root.get_pick_order()?.get(i);
-        if pick <= users_count {
-            league_pick_order.push(pick);
-            if league_pick_order.len() == users_count as usize {
-                break;
-            }
-        }
-    }
 
     let round = league.get_pick_round()?;
-    if round >= TEAM_PLAYERS_COUNT {
+    if round >= league.get_team_players_count() {
         return Err(SfsError::InvalidState.into());
     }
 
-    let mut pick_in_round = (league.get_current_pick() % users_count as u16) as u8;
-    if round % 2 == 0 {
-        pick_in_round = users_count - pick_in_round - 1;
-    }
-    let current_pick_user_id = league_pick_order[pick_in_round as usize];
-
-    if args.get_user_id() != current_pick_user_id {
+    if args.get_user_id() != league.get_current_pick_user_id()? {
         return Err(SfsError::InvalidState.into());
     }
 
-    for i in 1..users_count + 1 {
-        let user_players = league.get_user_states()?.get_by_id(i)?.get_user_players()?;
-        if user_players.contains(player_id) {
-            return Err(SfsError::AlreadyInUse.into());
-        }
-    }
-
-    user_state.get_user_players()?.set(round, player_id);
-    league.set_current_pick(league.get_current_pick() + 1);
+    helpers::draft_player(&root.get_players()?, &league, args.get_user_id(), player_id)?;
+    helpers::start_next_pick(&league, clock.unix_timestamp);
 
     Ok(())
 }
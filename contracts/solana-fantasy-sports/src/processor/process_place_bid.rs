@@ -0,0 +1,273 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes a [PlaceBid](enum.SfsInstruction.html) instruction.
+pub fn process_place_bid<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: PlaceBidArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let user_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    let user_state = league.get_user_states()?.get_by_id(args.get_user_id())?;
+    helpers::validate_owner(program_id, &user_state.get_pub_key(), user_account_info)?;
+
+    let player_id = league.get_nominated_player_id();
+    if league.get_user_states()?.get_count() != league.get_users_limit()
+        || league.get_draft_type()? != DraftType::Auction
+        || player_id == 0
+    {
+        return Err(SfsError::InvalidState.into());
+    }
+    if clock.unix_timestamp >= league.get_auction_deadline() {
+        return Err(SfsError::AuctionClosed.into());
+    }
+    if args.get_bid() <= league.get_high_bid() {
+        return Err(SfsError::BidTooLow.into());
+    }
+
+    helpers::validate_auction_bid(
+        &root.get_players()?,
+        &league,
+        args.get_user_id(),
+        player_id,
+        args.get_bid(),
+    )?;
+    league.set_high_bid(args.get_bid());
+    league.set_high_bidder_id(args.get_user_id());
+    // Every higher bid gives the others a full window to answer
+    league.set_auction_deadline(clock.unix_timestamp + league.get_auction_window() as i64);
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_place_bid() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_keys = [pubkey_rand(), pubkey_rand()];
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(2);
+            league.set_active_players_count(2);
+            league.set_draft_type(DraftType::Auction);
+            league.set_auction_window(30);
+            league
+                .get_composition_rules()
+                .unwrap()
+                .set_max(Position::RB, 1);
+            for user_key in user_keys.iter() {
+                let user_state = league.get_user_states().unwrap().add(*user_key).unwrap();
+                user_state.set_budget(10);
+            }
+            league.set_nominated_player_id(1);
+            league.set_high_bid(2);
+            league.set_high_bidder_id(1);
+            league.set_auction_deadline(1_030);
+        }
+
+        let do_place_bid = |root_account: &mut SolanaAccount,
+                            league_account: &mut SolanaAccount,
+                            user_account: &mut SolanaAccount,
+                            user_id: u8,
+                            bid: u32,
+                            unix_timestamp: i64| {
+            let mut args_data = vec![0u8, 0, user_id];
+            args_data.extend_from_slice(&bid.to_le_bytes());
+            let args_data = &RefCell::new(args_data.as_slice());
+            do_process_instruction(
+                place_bid(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_keys[user_id as usize - 1],
+                    PlaceBidArgs::new(args_data, 0).unwrap(),
+                )
+                .unwrap(),
+                vec![
+                    root_account,
+                    league_account,
+                    user_account,
+                    &mut clock_sysvar(unix_timestamp),
+                ],
+            )
+        };
+
+        // nobody bids before the league is full
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0).unwrap().set_users_limit(3);
+        }
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_place_bid(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                2,
+                3,
+                1_010
+            )
+        );
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0).unwrap().set_users_limit(2);
+        }
+
+        // not higher than the current bid
+        assert_eq!(
+            Err(SfsError::BidTooLow.into()),
+            do_place_bid(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                2,
+                2,
+                1_010
+            )
+        );
+
+        // the other roster slot needs at least 1 of the budget
+        assert_eq!(
+            Err(SfsError::InsufficientFunds.into()),
+            do_place_bid(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                2,
+                10,
+                1_010
+            )
+        );
+
+        // the player does not fit the team composition
+        let set_first_player = |league_account: &mut SolanaAccount, player_id: u16| {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0)
+                .unwrap()
+                .get_user_states()
+                .unwrap()
+                .get_by_id(2)
+                .unwrap()
+                .get_user_players()
+                .unwrap()
+                .set(0, player_id);
+        };
+        set_first_player(&mut league_account, 3);
+        assert_eq!(
+            Err(SfsError::TeamCompositionRulesViolation.into()),
+            do_place_bid(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                2,
+                5,
+                1_010
+            )
+        );
+        set_first_player(&mut league_account, 0);
+
+        do_place_bid(
+            &mut root_account,
+            &mut league_account,
+            &mut user_account,
+            2,
+            9,
+            1_010,
+        )
+        .unwrap();
+
+        // the window restarted with the bid and ran out
+        assert_eq!(
+            Err(SfsError::AuctionClosed.into()),
+            do_place_bid(
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                1,
+                10,
+                1_040
+            )
+        );
+
+        // anyone can give the player to the highest bidder
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        do_process_instruction(
+            auto_pick(
+                &program_id,
+                &root_key,
+                &league_key,
+                None,
+                AutoPickArgs::new(args_data, 0).unwrap(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut clock_sysvar(1_040),
+            ],
+        )
+        .unwrap();
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let user_state = league.get_user_states().unwrap().get_by_id(2).unwrap();
+        assert_eq!(user_state.get_user_players().unwrap().get(0), 1);
+        assert_eq!(user_state.get_budget(), 1);
+        assert_eq!(league.get_nominated_player_id(), 0);
+        assert_eq!(league.get_current_pick(), 1);
+    }
+}
@@ -35,8 +35,18 @@ pub fn process_propose_swap<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
 
-    let league = root.get_leagues()?.get(args.get_league_index())?;
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
 
     if league.get_start_week() == 0 {
         return Err(SfsError::InvalidState.into());
@@ -72,7 +82,11 @@ pub fn process_propose_swap<'a>(
         .copy_to(&user_player_list_copy);
 
     user_player_list_copy.replace_id(args.get_give_player_id(), args.get_want_player_id())?;
-    user_player_list_copy.validate_team_composition(&root.get_players()?)?;
+    user_player_list_copy.validate_team_composition(
+        &root.get_players()?,
+        &league.get_composition_rules()?,
+        league.get_team_players_count(),
+    )?;
 
     // validate a user can accept proposal
 
@@ -85,7 +99,11 @@ pub fn process_propose_swap<'a>(
         .copy_to(&user_player_list_copy);
 
     user_player_list_copy.replace_id(args.get_want_player_id(), args.get_give_player_id())?;
-    user_player_list_copy.validate_team_composition(&root.get_players()?)?;
+    user_player_list_copy.validate_team_composition(
+        &root.get_players()?,
+        &league.get_composition_rules()?,
+        league.get_team_players_count(),
+    )?;
 
     // inserting swap proposal in self user
     proposing_user_state
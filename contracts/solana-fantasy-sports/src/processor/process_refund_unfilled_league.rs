@@ -0,0 +1,341 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes a [RefundUnfilledLeague](enum.SfsInstruction.html) instruction.
+pub fn process_refund_unfilled_league<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RefundUnfilledLeagueArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let vault_account_info = next_account_info(account_info_iter)?;
+    let system_program_account_info = next_account_info(account_info_iter)?;
+    let clock = &Clock::from_account_info(next_account_info(account_info_iter)?)?;
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    if league.get_user_states()?.get_count() == league.get_users_limit() {
+        // A full league that started too late to play its regular season can't be ranked
+        if !league.is_schedule_missed(root.get_current_week(), root.get_games_count()?)? {
+            return Err(SfsError::InvalidState.into());
+        }
+    } else {
+        // A league can no longer draft once the season is past the weeks it can join
+        let is_season_over = root.get_stage()? != Stage::SeasonOpen
+            || root.get_current_week() >= root.get_games_count()?;
+        if !is_season_over && !league.is_fill_deadline_passed(clock.unix_timestamp) {
+            return Err(SfsError::InvalidState.into());
+        }
+    }
+
+    helpers::close_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        &league,
+        vault_account_info,
+        system_program_account_info,
+        account_info_iter,
+    )
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_refund_unfilled_league() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(200, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_keys = [pubkey_rand(), pubkey_rand()];
+        let mut user_accounts = [
+            SolanaAccount::new(0, 0, &Pubkey::default()),
+            SolanaAccount::new(0, 0, &Pubkey::default()),
+        ];
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(3);
+            league.set_fill_deadline(1_000);
+            for user_key in user_keys.iter() {
+                league.get_user_states().unwrap().add(*user_key).unwrap();
+            }
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        let args = RefundUnfilledLeagueArgs::new(args_data, 0).unwrap();
+        let (first, second) = user_accounts.split_at_mut(1);
+
+        // deadline has not passed yet
+        let mut clock_account = clock_sysvar(999);
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                refund_unfilled_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    None,
+                    &user_keys,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut clock_account,
+                    &mut first[0],
+                    &mut second[0],
+                ],
+            )
+        );
+
+        let mut clock_account = clock_sysvar(1_000);
+        do_process_instruction(
+            refund_unfilled_league(
+                &program_id,
+                &root_key,
+                &league_key,
+                &vault_key,
+                None,
+                &user_keys,
+                args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut clock_account,
+                &mut first[0],
+                &mut second[0],
+            ],
+        )
+        .unwrap();
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        assert_eq!(league.get_is_initialized(), Ok(false));
+    }
+
+    #[test]
+    fn test_refund_unfilled_league_after_season() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(100, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut clock_account = clock_sysvar(0);
+        let user_key = pubkey_rand();
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(2);
+            league.get_user_states().unwrap().add(user_key).unwrap();
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        let args = RefundUnfilledLeagueArgs::new(args_data, 0).unwrap();
+
+        // league without a deadline can still fill up
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                refund_unfilled_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    None,
+                    &[user_key],
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut clock_account,
+                    &mut user_account,
+                ],
+            )
+        );
+
+        for _ in 0..FOOTBALL_GAMES_COUNT {
+            do_process_instruction(
+                increment_week(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+            .unwrap();
+        }
+
+        do_process_instruction(
+            refund_unfilled_league(
+                &program_id,
+                &root_key,
+                &league_key,
+                &vault_key,
+                None,
+                &[user_key],
+                args,
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut clock_account,
+                &mut user_account,
+            ],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_refund_league_that_missed_its_schedule() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let (vault_key, _) = helpers::find_vault_address(&program_id, &root_key, 0);
+        let mut vault_account = SolanaAccount::new(200, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut clock_account = clock_sysvar(0);
+        let user_keys = [pubkey_rand(), pubkey_rand()];
+        let mut user_accounts = [
+            SolanaAccount::new(0, 0, &Pubkey::default()),
+            SolanaAccount::new(0, 0, &Pubkey::default()),
+        ];
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_bid(100);
+            league.set_users_limit(2);
+            league.get_bracket().unwrap().set_teams_count(2);
+            for user_key in user_keys.iter() {
+                league.get_user_states().unwrap().add(*user_key).unwrap();
+            }
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0] as &[u8]);
+        let args = RefundUnfilledLeagueArgs::new(args_data, 0).unwrap();
+        let (first, second) = user_accounts.split_at_mut(1);
+
+        // the full league can still start its regular season
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                refund_unfilled_league(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &vault_key,
+                    None,
+                    &user_keys,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut vault_account,
+                    &mut system_account,
+                    &mut clock_account,
+                    &mut first[0],
+                    &mut second[0],
+                ],
+            )
+        );
+
+        // lineups were set only for the playoff week, the bracket can never be seeded
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            League::new(league_data, 0)
+                .unwrap()
+                .set_start_week(FOOTBALL_GAMES_COUNT);
+        }
+        do_process_instruction(
+            refund_unfilled_league(
+                &program_id,
+                &root_key,
+                &league_key,
+                &vault_key,
+                None,
+                &user_keys,
+                args,
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut vault_account,
+                &mut system_account,
+                &mut clock_account,
+                &mut first[0],
+                &mut second[0],
+            ],
+        )
+        .unwrap();
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        assert_eq!(league.get_is_initialized(), Ok(false));
+    }
+}
@@ -35,10 +35,20 @@ pub fn process_reject_swap<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
 
     let user_account_info = next_account_info(account_info_iter)?;
 
-    let league = root.get_leagues()?.get(args.get_league_index())?;
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
 
     let accepting_user_state = league
         .get_user_states()?
@@ -0,0 +1,239 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes a [SetDraftQueue](enum.SfsInstruction.html) instruction.
+pub fn process_set_draft_queue<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetDraftQueueArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+    let user_account_info = next_account_info(account_info_iter)?;
+    let draft_queue_account_info = next_account_info(account_info_iter)?;
+    let system_program_account_info = next_account_info(account_info_iter)?;
+    let rent = &Rent::from_account_info(next_account_info(account_info_iter)?)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    let user_state = league.get_user_states()?.get_by_id(args.get_user_id())?;
+    helpers::validate_owner(program_id, &user_state.get_pub_key(), user_account_info)?;
+
+    if league.get_pick_round()? >= league.get_team_players_count() {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    // Drafted ids may stay queued, the auto-pick skips them
+    let players_count = root.get_players()?.get_count();
+    if args
+        .get_draft_queue()
+        .chunks(DraftQueueList::ITEM_SIZE)
+        .any(|item| LittleEndian::read_u16(item) > players_count)
+    {
+        return Err(SfsError::IndexOutOfRange.into());
+    }
+
+    let (draft_queue_pubkey, bump_seed) = helpers::find_draft_queue_address(
+        program_id,
+        league_account_info.key,
+        user_account_info.key,
+    );
+    if draft_queue_pubkey != *draft_queue_account_info.key {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+    // The queue lives in an account of its own paid by the user, created by the first call
+    if draft_queue_account_info.owner != program_id {
+        helpers::create_pda_account(
+            user_account_info,
+            draft_queue_account_info,
+            system_program_account_info,
+            rent,
+            DraftQueueList::LEN,
+            program_id,
+            &[
+                DRAFT_QUEUE_SEED,
+                league_account_info.key.as_ref(),
+                user_account_info.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+    DraftQueueList::new(&draft_queue_account_info.data, 0)?.set_all(args.get_draft_queue());
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_set_draft_queue() {
+        let program_id = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &pubkey_rand());
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let mut user_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let user_key = pubkey_rand();
+        let (draft_queue_key, _) =
+            helpers::find_draft_queue_address(&program_id, &league_key, &user_key);
+        // allocated by the system program on-chain
+        let mut draft_queue_account = SolanaAccount::new(0, DraftQueueList::LEN, &program_id);
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut rent_sysvar = rent_sysvar();
+
+        {
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_users_limit(2);
+            league.set_active_players_count(1);
+            league.get_user_states().unwrap().add(user_key).unwrap();
+        }
+
+        let mut args_data = vec![0u8, 0, 1];
+        args_data.extend_from_slice(&[0u8; DraftQueueList::LEN]);
+        args_data[3..5].copy_from_slice(&3u16.to_le_bytes());
+        args_data[5..7].copy_from_slice(&5u16.to_le_bytes());
+        let mut unknown_args_data = args_data.clone();
+        unknown_args_data[7..9].copy_from_slice(&6u16.to_le_bytes());
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = SetDraftQueueArgs::new(args_data, 0).unwrap();
+
+        // not the user
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                set_draft_queue(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &pubkey_rand(),
+                    &draft_queue_key,
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut draft_queue_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                ],
+            )
+        );
+
+        // there are only 5 players
+        let unknown_args_data = &RefCell::new(unknown_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::IndexOutOfRange.into()),
+            do_process_instruction(
+                set_draft_queue(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_key,
+                    &draft_queue_key,
+                    SetDraftQueueArgs::new(unknown_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut draft_queue_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                ],
+            )
+        );
+
+        do_process_instruction(
+            set_draft_queue(
+                &program_id,
+                &root_key,
+                &league_key,
+                &user_key,
+                &draft_queue_key,
+                args.clone(),
+            )
+            .unwrap(),
+            vec![
+                &mut root_account,
+                &mut league_account,
+                &mut user_account,
+                &mut draft_queue_account,
+                &mut system_account,
+                &mut rent_sysvar,
+            ],
+        )
+        .unwrap();
+
+        let draft_queue_data = &RefCell::new(&mut *draft_queue_account.data);
+        let draft_queue = DraftQueueList::new(draft_queue_data, 0).unwrap();
+        assert_eq!(draft_queue.get_player_ids(), vec![3, 5]);
+
+        // the queue is derived from the league and the user
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                set_draft_queue(
+                    &program_id,
+                    &root_key,
+                    &league_key,
+                    &user_key,
+                    &pubkey_rand(),
+                    args.clone()
+                )
+                .unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut league_account,
+                    &mut user_account,
+                    &mut draft_queue_account,
+                    &mut system_account,
+                    &mut rent_sysvar,
+                ],
+            )
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [SetProtocolFee](enum.SfsInstruction.html) instruction.
+pub fn process_set_protocol_fee<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetProtocolFeeArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let admin_account_info = next_account_info(account_info_iter)?;
+
+    helpers::validate_owner(program_id, &root.get_admin_authority(), admin_account_info)?;
+
+    if args.get_fee_bps() > BPS_DENOMINATOR {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    root.set_fee_bps(args.get_fee_bps());
+    root.set_treasury(args.get_treasury());
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_set_protocol_fee() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let treasury_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            initialized_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        let mut args_data = Vec::<u8>::new();
+        args_data.extend_from_slice(&250u16.to_le_bytes());
+        args_data.extend_from_slice(treasury_key.as_ref());
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = SetProtocolFeeArgs::new(args_data, 0).unwrap();
+
+        // not an admin
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                set_protocol_fee(&program_id, &root_key, &pubkey_rand(), args.clone()).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        // fee is more than the whole pot
+        let mut excessive_args_data = args_data.borrow().to_vec();
+        excessive_args_data[0..2].copy_from_slice(&10_001u16.to_le_bytes());
+        let excessive_args_data = &RefCell::new(excessive_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                set_protocol_fee(
+                    &program_id,
+                    &root_key,
+                    &admin_key,
+                    SetProtocolFeeArgs::new(excessive_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        do_process_instruction(
+            set_protocol_fee(&program_id, &root_key, &admin_key, args.clone()).unwrap(),
+            vec![&mut root_account, &mut admin_account],
+        )
+        .unwrap();
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        let root = Root::new(root_data).unwrap();
+        assert_eq!(root.get_fee_bps(), 250);
+        assert_eq!(root.get_treasury(), treasury_key);
+    }
+}
@@ -0,0 +1,206 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [SettlePlayoffRound](enum.SfsInstruction.html) instruction.
+pub fn process_settle_playoff_round<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SettlePlayoffRoundArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    let bracket = league.get_bracket()?;
+    let rounds_count = bracket.get_rounds_count();
+    let round = bracket.get_settled_rounds() + 1;
+    if round > rounds_count {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let regular_season_end = league.get_regular_season_end(root.get_games_count()?)?;
+    if league.get_start_week() == 0 || league.get_start_week() > regular_season_end {
+        return Err(SfsError::InvalidState.into());
+    }
+    let week = regular_season_end + round;
+    if !helpers::is_week_final(&root, week)? {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let players = root.get_players()?;
+    if !bracket.is_seeded() {
+        if league.get_league_type()? == LeagueType::HeadToHead
+            && league.get_last_settled_week() != regular_season_end
+        {
+            return Err(SfsError::InvalidState.into());
+        }
+        let standings = helpers::get_standings(&players, &league, regular_season_end)?;
+        let ranking = helpers::get_ranking(&standings);
+        if ranking.len() < bracket.get_teams_count() as usize {
+            return Err(SfsError::InvalidState.into());
+        }
+        for seed in 1..bracket.get_teams_count() + 1 {
+            bracket.set_seed(seed, ranking[seed as usize - 1]);
+        }
+    }
+
+    let user_states = league.get_user_states()?;
+    for game in 0..bracket.get_games_count(round) {
+        let (home, away) = bracket.get_matchup(round, game);
+        let home_points =
+            helpers::get_week_points(&players, &league, &user_states.get_by_id(home)?, week)?;
+        let away_points =
+            helpers::get_week_points(&players, &league, &user_states.get_by_id(away)?, week)?;
+        // A tie goes to the higher seed
+        let winner = if home_points > away_points {
+            home
+        } else if away_points > home_points {
+            away
+        } else if bracket.get_seed_of(home) < bracket.get_seed_of(away) {
+            home
+        } else {
+            away
+        };
+        bracket.set_winner(round, game, winner);
+    }
+    bracket.set_settled_rounds(round);
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_settle_playoff_round() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        let playoff_week = FOOTBALL_GAMES_COUNT;
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            let players = Root::new(root_data).unwrap().get_players().unwrap();
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_start_week(1);
+            league.set_active_players_count(1);
+            league.get_bracket().unwrap().set_teams_count(2);
+            league
+                .get_scoring_rules()
+                .unwrap()
+                .set_weight(Stat::RushingYards, 100);
+            let user_states = league.get_user_states().unwrap();
+            // regular season totals make users 2 and 3 the top seeds,
+            // user 3 wins the final
+            for (user_id, (yards, playoff_yards)) in
+                [(10, 50), (40, 5), (30, 9), (20, 60)].iter().enumerate()
+            {
+                let player_id = user_id as u16 + 1;
+                let lineups = user_states
+                    .add(pubkey_rand())
+                    .unwrap()
+                    .get_lineups()
+                    .unwrap();
+                for week in 1..playoff_week + 1 {
+                    lineups.get_by_week(week).unwrap().set(0, player_id);
+                }
+                let scores = players.get_by_id(player_id).unwrap().get_scores().unwrap();
+                scores
+                    .get_by_week(1)
+                    .unwrap()
+                    .set_stat(Stat::RushingYards, *yards);
+                scores
+                    .get_by_week(playoff_week)
+                    .unwrap()
+                    .set_stat(Stat::RushingYards, *playoff_yards);
+            }
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0u8] as &[u8]);
+        let args = SettlePlayoffRoundArgs::new(args_data, 0).unwrap();
+        for _ in 0..playoff_week + 1 {
+            do_process_instruction(
+                increment_week(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+            .unwrap();
+        }
+
+        // playoff week can still be corrected
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                settle_playoff_round(&program_id, &root_key, &league_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut league_account],
+            )
+        );
+
+        do_process_instruction(
+            complete_season(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut admin_account],
+        )
+        .unwrap();
+        do_process_instruction(
+            settle_playoff_round(&program_id, &root_key, &league_key, args.clone()).unwrap(),
+            vec![&mut root_account, &mut league_account],
+        )
+        .unwrap();
+
+        // every round is settled
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                settle_playoff_round(&program_id, &root_key, &league_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut league_account],
+            )
+        );
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        let bracket = league.get_bracket().unwrap();
+        assert_eq!(bracket.get_seed(1), 2);
+        assert_eq!(bracket.get_seed(2), 3);
+        assert_eq!(bracket.get_champion(), Some(3));
+    }
+}
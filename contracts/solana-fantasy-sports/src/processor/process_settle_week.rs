@@ -0,0 +1,206 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [SettleWeek](enum.SfsInstruction.html) instruction.
+pub fn process_settle_week<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SettleWeekArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
+
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
+    if league.get_league_type()? != LeagueType::HeadToHead || league.get_start_week() == 0 {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let week = match league.get_last_settled_week() {
+        0 => league.get_start_week(),
+        last => last + 1,
+    };
+    // Playoff weeks are settled by the bracket
+    if week > league.get_regular_season_end(root.get_games_count()?)? {
+        return Err(SfsError::InvalidState.into());
+    }
+    if !helpers::is_week_final(&root, week)? {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let players = root.get_players()?;
+    let user_states = league.get_user_states()?;
+    let mut points = Vec::<i64>::with_capacity(user_states.get_count() as usize);
+    for user_id in 1..user_states.get_count() + 1 {
+        let user_state = user_states.get_by_id(user_id)?;
+        points.push(helpers::get_week_points(&players, &league, &user_state, week)?);
+    }
+
+    for user_id in 1..user_states.get_count() + 1 {
+        let user_state = user_states.get_by_id(user_id)?;
+        let user_points = points[user_id as usize - 1];
+        user_state.set_points_for(
+            user_state
+                .get_points_for()
+                .checked_add(user_points)
+                .ok_or(SfsError::Overflow)?,
+        );
+
+        let opponent_id = match league.get_opponent(user_id, week)? {
+            Some(id) => id,
+            None => continue,
+        };
+        let opponent_points = points[opponent_id as usize - 1];
+        if user_points > opponent_points {
+            user_state.set_wins(user_state.get_wins() + 1);
+        } else if user_points < opponent_points {
+            user_state.set_losses(user_state.get_losses() + 1);
+        } else {
+            user_state.set_ties(user_state.get_ties() + 1);
+        }
+    }
+
+    league.set_last_settled_week(week);
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_settle_week() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let (league_key, mut league_account) = league_account(&program_id, &root_key, 0);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            let players = Root::new(root_data).unwrap().get_players().unwrap();
+            let league_data = &RefCell::new(&mut *league_account.data);
+            let league = League::new(league_data, 0).unwrap();
+            league.set_league_type(LeagueType::HeadToHead);
+            league.set_start_week(1);
+            league.set_active_players_count(1);
+            league
+                .get_scoring_rules()
+                .unwrap()
+                .set_weight(Stat::RushingYards, 100);
+            let user_states = league.get_user_states().unwrap();
+            for (user_id, yards) in [10, 20, 30].iter().enumerate() {
+                let player_id = user_id as u16 + 1;
+                let user_state = user_states.add(pubkey_rand()).unwrap();
+                user_state
+                    .get_lineups()
+                    .unwrap()
+                    .get_by_week(1)
+                    .unwrap()
+                    .set(0, player_id);
+                players
+                    .get_by_id(player_id)
+                    .unwrap()
+                    .get_scores()
+                    .unwrap()
+                    .get_by_week(1)
+                    .unwrap()
+                    .set_stat(Stat::RushingYards, *yards);
+            }
+        }
+
+        let args_data = &RefCell::new(&[0u8, 0u8] as &[u8]);
+        let args = SettleWeekArgs::new(args_data, 0).unwrap();
+        for _ in 0..2 {
+            do_process_instruction(
+                increment_week(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+            .unwrap();
+        }
+
+        // week 1 can still be corrected
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                settle_week(&program_id, &root_key, &league_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut league_account],
+            )
+        );
+
+        do_process_instruction(
+            increment_week(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut admin_account],
+        )
+        .unwrap();
+        do_process_instruction(
+            settle_week(&program_id, &root_key, &league_key, args.clone()).unwrap(),
+            vec![&mut root_account, &mut league_account],
+        )
+        .unwrap();
+
+        // week 2 is not final yet
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                settle_week(&program_id, &root_key, &league_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut league_account],
+            )
+        );
+
+        let league_data = &RefCell::new(&mut *league_account.data);
+        let league = League::new(league_data, 0).unwrap();
+        assert_eq!(league.get_last_settled_week(), 1);
+        let user_states = league.get_user_states().unwrap();
+        // user 1 has a bye, user 3 beats user 2
+        let user_state = user_states.get_by_id(1).unwrap();
+        assert_eq!(
+            (user_state.get_wins(), user_state.get_losses(), user_state.get_ties()),
+            (0, 0, 0)
+        );
+        assert_eq!(user_state.get_points_for(), 1000);
+        let user_state = user_states.get_by_id(2).unwrap();
+        assert_eq!((user_state.get_wins(), user_state.get_losses()), (0, 1));
+        assert_eq!(user_state.get_points_for(), 2000);
+        let user_state = user_states.get_by_id(3).unwrap();
+        assert_eq!((user_state.get_wins(), user_state.get_losses()), (1, 0));
+        assert_eq!(user_state.get_points_for(), 3000);
+    }
+}
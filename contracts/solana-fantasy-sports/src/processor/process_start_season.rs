@@ -35,12 +35,75 @@ pub fn process_start_season<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let admin_account_info = next_account_info(account_info_iter)?;
 
-    // if root.get_stage()? != Stage::DraftSelection {
-    //     return Err(SfsError::InvalidStage.into());
-    // }
+    helpers::validate_owner(program_id, &root.get_admin_authority(), admin_account_info)?;
 
-    // root.set_stage(Stage::SeasonOpen);
+    if root.get_stage()? != Stage::DraftSelection {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    if root.get_players()?.get_count() == 0 {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    root.set_stage(Stage::SeasonOpen);
 
     Ok(())
 }
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_start_season() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            initialized_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        // not an admin
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                start_season(&program_id, &root_key, &pubkey_rand()).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        // no players yet
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                start_season(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        add_test_players(&program_id, &root_key, &admin_key, &mut root_account, 5);
+
+        do_process_instruction(
+            start_season(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut admin_account],
+        )
+        .unwrap();
+
+        // start twice
+        assert_eq!(
+            Err(SfsError::InvalidStage.into()),
+            do_process_instruction(
+                start_season(&program_id, &root_key, &admin_key).unwrap(),
+                vec![&mut root_account, &mut admin_account],
+            )
+        );
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        let root = Root::new(root_data).unwrap();
+        assert_eq!(root.get_stage(), Ok(Stage::SeasonOpen));
+    }
+}
@@ -35,13 +35,19 @@ pub fn process_update_lineup<'a>(
     let account_info_iter = &mut accounts.iter();
     let root_info = next_account_info(account_info_iter)?;
     let root = Root::new(&root_info.data)?;
+    let league_account_info = next_account_info(account_info_iter)?;
     let user_account_info = next_account_info(account_info_iter)?;
 
     if root.get_stage()? != Stage::SeasonOpen {
         return Err(SfsError::InvalidStage.into());
     }
 
-    let league = root.get_leagues()?.get(args.get_league_index())?;
+    let league = helpers::get_league(
+        program_id,
+        root_info.key,
+        args.get_league_index(),
+        league_account_info,
+    )?;
     let user_states = league.get_user_states()?;
     let user_state = user_states.get_by_id(args.get_user_id())?;
 
@@ -50,12 +56,13 @@ pub fn process_update_lineup<'a>(
     let round = league.get_pick_round()?;
 
     // Checking if draft selection complete for this league
-    if round < TEAM_PLAYERS_COUNT {
+    if round < league.get_team_players_count() {
         return Err(SfsError::InvalidState.into());
     }
 
+    let active_players_count = league.get_active_players_count();
     let user_players = user_state.get_user_players()?;
-    for i in 0..ActivePlayersList::ITEM_COUNT {
+    for i in 0..active_players_count {
         let player_id = args.get_active_players()?.get(i);
         if !user_players.contains(player_id) {
             return Err(SfsError::OwnerMismatch.into());
@@ -66,9 +73,9 @@ pub fn process_update_lineup<'a>(
         return Err(SfsError::InvalidState.into());
     }
 
-    for week in args.get_week()..GAMES_COUNT + 1 {
+    for week in args.get_week()..root.get_games_count()? + 1 {
         let lineup = user_state.get_lineups()?.get_by_week(week)?;
-        for i in 0..ActivePlayersList::ITEM_COUNT {
+        for i in 0..active_players_count {
             let player_id = args.get_active_players()?.get(i);
             lineup.set(i, player_id);
         }
@@ -76,7 +83,7 @@ pub fn process_update_lineup<'a>(
 
     // Check for duplicates
     let lineup = user_state.get_lineups()?.get_by_week(args.get_week())?;
-    for i in 0..ActivePlayersList::ITEM_COUNT {
+    for i in 0..active_players_count {
         let index = lineup.index_of(lineup.get(i));
         if index.is_ok() && index? != i {
             return Err(SfsError::AlreadyInUse.into());
@@ -84,17 +91,7 @@ pub fn process_update_lineup<'a>(
     }
 
     //Make sure new lineup conforms to the leagues position ruleset
-    let positions = league.get_position_options()?;
-    let total_active = positions.get_total();
-    for i in 0..total_active{
-        let id = lineup.get(i as u8);
-        let player_position = root.get_players()?.get_by_id(id)?.get_position()?;
-        let position_count = positions.get_number_by_position(player_position as usize);
-        positions.set_number_by_position(player_position, position_count -1);
-        if positions.get_number_by_position(player_position as usize) < 0 {
-            return Err(SfsError::AlreadyInUse.into()); //create new error for this
-        }
-    }
+    helpers::validate_lineup_positions(&root.get_players()?, &league, &lineup)?;
 
     if !user_state.get_is_lineup_set()? {
         user_state.set_is_lineup_set(true);
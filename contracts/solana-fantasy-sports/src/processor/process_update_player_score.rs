@@ -39,8 +39,12 @@ pub fn process_update_player_score<'a>(
 
   helpers::validate_owner(program_id, &root.get_oracle_authority(), user_account_info)?;
 
+  if root.get_stage()? != Stage::SeasonOpen {
+    return Err(SfsError::InvalidStage.into());
+  }
+
   let current_week = root.get_current_week();
-  if current_week == 0 || current_week > GAMES_COUNT {
+  if current_week == 0 || current_week > root.get_games_count()? {
     return Err(SfsError::InvalidState.into());
   }
 
@@ -50,7 +54,92 @@ pub fn process_update_player_score<'a>(
     .get_scores()?
     .get_by_week(root.get_current_week())?;
 
-  score.set_score1(args.get_player_score());
+  if score.get_is_initialized()? {
+    return Err(SfsError::ScoreAlreadyUpdated.into());
+  }
+
+  score.set_stats(&args.get_stats());
+  score.set_is_initialized(true);
 
   Ok(())
 }
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+  use super::helpers::tests::*;
+  use super::*;
+
+  #[test]
+  fn test_update_player_score() {
+    let program_id = pubkey_rand();
+    let oracle_key = pubkey_rand();
+    let admin_key = pubkey_rand();
+    let (root_key, mut root_account) =
+      season_open_root_account(&program_id, &oracle_key, &admin_key);
+    let mut oracle_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+    let mut args_data = Vec::<u8>::new();
+    args_data.extend_from_slice(&1u16.to_le_bytes());
+    for i in 0..STATS_COUNT as i16 {
+      args_data.extend_from_slice(&(i - 2).to_le_bytes());
+    }
+    let args_data = &RefCell::new(args_data.as_slice());
+    let args = UpdatePlayerScoreArgs::new(args_data, 0).unwrap();
+
+    // no week is being played yet
+    assert_eq!(
+      Err(SfsError::InvalidState.into()),
+      do_process_instruction(
+        update_player_score(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+        vec![&mut root_account, &mut oracle_account],
+      )
+    );
+
+    do_process_instruction(
+      increment_week(&program_id, &root_key, &admin_key).unwrap(),
+      vec![&mut root_account, &mut oracle_account],
+    )
+    .unwrap();
+
+    // not an oracle
+    assert_eq!(
+      Err(SfsError::OwnerMismatch.into()),
+      do_process_instruction(
+        update_player_score(&program_id, &root_key, &admin_key, args.clone()).unwrap(),
+        vec![&mut root_account, &mut oracle_account],
+      )
+    );
+
+    do_process_instruction(
+      update_player_score(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+      vec![&mut root_account, &mut oracle_account],
+    )
+    .unwrap();
+
+    // second write for the same week
+    assert_eq!(
+      Err(SfsError::ScoreAlreadyUpdated.into()),
+      do_process_instruction(
+        update_player_score(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+        vec![&mut root_account, &mut oracle_account],
+      )
+    );
+
+    let root_data = &RefCell::new(&mut *root_account.data);
+    let root = Root::new(root_data).unwrap();
+    let score = root
+      .get_players()
+      .unwrap()
+      .get_by_id(1)
+      .unwrap()
+      .get_scores()
+      .unwrap()
+      .get_by_week(1)
+      .unwrap();
+    assert_eq!(score.get_is_initialized(), Ok(true));
+    assert_eq!(score.get_stat(Stat::PassingYards), -2);
+    assert_eq!(score.get_stat(Stat::Turnovers), STATS_COUNT as i16 - 3);
+  }
+}
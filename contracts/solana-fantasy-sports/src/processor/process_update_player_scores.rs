@@ -0,0 +1,168 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [UpdatePlayerScores](enum.SfsInstruction.html) instruction.
+pub fn process_update_player_scores<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdatePlayerScoresArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let oracle_account_info = next_account_info(account_info_iter)?;
+
+    helpers::validate_owner(program_id, &root.get_oracle_authority(), oracle_account_info)?;
+
+    if root.get_stage()? != Stage::SeasonOpen {
+        return Err(SfsError::InvalidStage.into());
+    }
+
+    let current_week = root.get_current_week();
+    if current_week == 0 || current_week > root.get_games_count()? {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let players = root.get_players()?;
+    let scores_args = args.get_scores()?;
+    for i in 0..scores_args.get_count() {
+        let score_args = scores_args.get(i)?;
+        let score = players
+            .get_by_id(score_args.get_player_id())?
+            .get_scores()?
+            .get_by_week(current_week)?;
+        if score.get_is_initialized()? {
+            return Err(SfsError::ScoreAlreadyUpdated.into());
+        }
+        score.set_stats(&score_args.get_stats());
+        score.set_is_initialized(true);
+    }
+
+    info!(&format!("Updated player scores: {}", scores_args.get_count()));
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+    use crate::instructions::structures::PlayerScore;
+
+    fn scores_args_data(count: u8, player_ids: &[u16]) -> Vec<u8> {
+        let mut args_data = Vec::<u8>::new();
+        args_data.push(count);
+        for i in 0..MAX_SCORES_PER_INSTRUCTION as usize {
+            let player_id = player_ids.get(i).cloned().unwrap_or(0);
+            let stats = [player_id as i16 * 10; STATS_COUNT as usize];
+            args_data.extend_from_slice(&PlayerScore::pack(player_id, &stats));
+        }
+        args_data
+    }
+
+    #[test]
+    fn test_update_player_scores() {
+        let program_id = pubkey_rand();
+        let oracle_key = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            season_open_root_account(&program_id, &oracle_key, &admin_key);
+        let mut oracle_account = SolanaAccount::new(0, 0, &Pubkey::default());
+
+        do_process_instruction(
+            increment_week(&program_id, &root_key, &admin_key).unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+
+        let args_data = scores_args_data(3, &[1, 3, 5]);
+        let args_data = &RefCell::new(args_data.as_slice());
+        let args = UpdatePlayerScoresArgs::new(args_data, 0).unwrap();
+
+        // not an oracle
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                update_player_scores(&program_id, &root_key, &admin_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut oracle_account],
+            )
+        );
+
+        // unknown player
+        let unknown_args_data = scores_args_data(1, &[42]);
+        let unknown_args_data = &RefCell::new(unknown_args_data.as_slice());
+        assert_eq!(
+            Err(SfsError::IndexOutOfRange.into()),
+            do_process_instruction(
+                update_player_scores(
+                    &program_id,
+                    &root_key,
+                    &oracle_key,
+                    UpdatePlayerScoresArgs::new(unknown_args_data, 0).unwrap()
+                )
+                .unwrap(),
+                vec![&mut root_account, &mut oracle_account],
+            )
+        );
+
+        do_process_instruction(
+            update_player_scores(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+            vec![&mut root_account, &mut oracle_account],
+        )
+        .unwrap();
+
+        // scores of the week are already recorded
+        assert_eq!(
+            Err(SfsError::ScoreAlreadyUpdated.into()),
+            do_process_instruction(
+                update_player_scores(&program_id, &root_key, &oracle_key, args.clone()).unwrap(),
+                vec![&mut root_account, &mut oracle_account],
+            )
+        );
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        let root = Root::new(root_data).unwrap();
+        let players = root.get_players().unwrap();
+        for &(player_id, is_updated) in &[(1, true), (2, false), (3, true), (4, false), (5, true)]
+        {
+            let score = players
+                .get_by_id(player_id)
+                .unwrap()
+                .get_scores()
+                .unwrap()
+                .get_by_week(1)
+                .unwrap();
+            assert_eq!(score.get_is_initialized(), Ok(is_updated));
+            let expected = if is_updated { player_id as i16 * 10 } else { 0 };
+            assert_eq!(score.get_stat(Stat::RushingYards), expected);
+        }
+    }
+}
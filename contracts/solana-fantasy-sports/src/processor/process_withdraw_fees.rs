@@ -0,0 +1,157 @@
+//! Program state processor
+
+use crate::{
+    error::SfsError,
+    instructions,
+    instructions::{arguments::*, instruction::*},
+    processor::helpers,
+    state::*,
+};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use num_traits::FromPrimitive;
+use solana_program::program::invoke;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    decode_error::DecodeError,
+    entrypoint::ProgramResult,
+    info,
+    instruction::{AccountMeta, Instruction},
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::{IsInitialized, Pack},
+    pubkey::Pubkey,
+    system_instruction::{transfer, SystemInstruction},
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::cell::RefCell;
+use std::convert::TryInto;
+
+/// Processes an [WithdrawFees](enum.SfsInstruction.html) instruction.
+pub fn process_withdraw_fees<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let root_info = next_account_info(account_info_iter)?;
+    let root = Root::new(&root_info.data)?;
+    let admin_account_info = next_account_info(account_info_iter)?;
+    let bank_account_info = next_account_info(account_info_iter)?;
+    let treasury_account_info = next_account_info(account_info_iter)?;
+    let system_program_account_info = next_account_info(account_info_iter)?;
+
+    helpers::validate_owner(program_id, &root.get_admin_authority(), admin_account_info)?;
+    helpers::validate_bank(program_id, bank_account_info)?;
+    if root.get_treasury() != *treasury_account_info.key {
+        return Err(SfsError::InvalidInstruction.into());
+    }
+
+    let fees = root.get_accrued_fees();
+    if fees == 0 {
+        return Err(SfsError::InvalidState.into());
+    }
+
+    let (_, bump_seed) = Pubkey::find_program_address(&[&[0]], program_id);
+    let instruction = transfer(bank_account_info.key, treasury_account_info.key, fees);
+    let accounts = [
+        bank_account_info.clone(),
+        treasury_account_info.clone(),
+        system_program_account_info.clone(),
+    ];
+    invoke_signed(&instruction, &accounts, &[&[&[0, bump_seed]]])?;
+
+    root.set_accrued_fees(0);
+
+    Ok(())
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::helpers::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_withdraw_fees() {
+        let program_id = pubkey_rand();
+        let admin_key = pubkey_rand();
+        let treasury_key = pubkey_rand();
+        let (root_key, mut root_account) =
+            initialized_root_account(&program_id, &pubkey_rand(), &admin_key);
+        let mut admin_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut bank_account = SolanaAccount::new(1_000, 0, &program_id);
+        let mut treasury_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        let mut system_account = SolanaAccount::new(0, 0, &Pubkey::default());
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            Root::new(root_data).unwrap().set_treasury(treasury_key);
+        }
+
+        // nothing accrued yet
+        assert_eq!(
+            Err(SfsError::InvalidState.into()),
+            do_process_instruction(
+                withdraw_fees(&program_id, &root_key, &admin_key, &treasury_key).unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut admin_account,
+                    &mut bank_account,
+                    &mut treasury_account,
+                    &mut system_account,
+                ],
+            )
+        );
+
+        {
+            let root_data = &RefCell::new(&mut *root_account.data);
+            Root::new(root_data).unwrap().set_accrued_fees(30);
+        }
+
+        // not the root treasury
+        assert_eq!(
+            Err(SfsError::InvalidInstruction.into()),
+            do_process_instruction(
+                withdraw_fees(&program_id, &root_key, &admin_key, &pubkey_rand()).unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut admin_account,
+                    &mut bank_account,
+                    &mut treasury_account,
+                    &mut system_account,
+                ],
+            )
+        );
+
+        // not an admin
+        assert_eq!(
+            Err(SfsError::OwnerMismatch.into()),
+            do_process_instruction(
+                withdraw_fees(&program_id, &root_key, &pubkey_rand(), &treasury_key).unwrap(),
+                vec![
+                    &mut root_account,
+                    &mut admin_account,
+                    &mut bank_account,
+                    &mut treasury_account,
+                    &mut system_account,
+                ],
+            )
+        );
+
+        do_process_instruction(
+            withdraw_fees(&program_id, &root_key, &admin_key, &treasury_key).unwrap(),
+            vec![
+                &mut root_account,
+                &mut admin_account,
+                &mut bank_account,
+                &mut treasury_account,
+                &mut system_account,
+            ],
+        )
+        .unwrap();
+
+        let root_data = &RefCell::new(&mut *root_account.data);
+        let root = Root::new(root_data).unwrap();
+        assert_eq!(root.get_accrued_fees(), 0);
+    }
+}
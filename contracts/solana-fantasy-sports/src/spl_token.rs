@@ -0,0 +1,79 @@
+//! Parts of the SPL token program interface used to escrow token bids
+
+use arrayref::{array_ref, array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+solana_program::declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Tag of the token program `Transfer` instruction
+const TRANSFER_TAG: u8 = 3;
+
+/// Creates a token program `Transfer` instruction.
+pub fn transfer(
+    source_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    authority_pubkey: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![TRANSFER_TAG];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![
+            AccountMeta::new(*source_pubkey, false),
+            AccountMeta::new(*destination_pubkey, false),
+            AccountMeta::new_readonly(*authority_pubkey, true),
+        ],
+        data,
+    }
+}
+
+/// Fields of a token account
+pub struct TokenAccount {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+impl TokenAccount {
+    pub const LEN: usize = 165;
+
+    /// Reads an initialized account owned by the token program
+    pub fn unpack(account_info: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+        if *account_info.owner != id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let data = account_info.data.borrow();
+        if data.len() != TokenAccount::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        #[allow(clippy::ptr_offset_with_cast)]
+        let (mint, owner, amount, _delegate, state, _rest) =
+            array_refs![array_ref![data, 0, TokenAccount::LEN], 32, 32, 8, 36, 1, 56];
+        if state[0] == 0 {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(TokenAccount {
+            mint: Pubkey::new_from_array(*mint),
+            owner: Pubkey::new_from_array(*owner),
+            amount: LittleEndian::read_u64(amount),
+        })
+    }
+
+    /// Packs a token account as the token program stores it, for host tests
+    #[cfg(test)]
+    pub fn pack(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0u8; TokenAccount::LEN];
+        data[0..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        data[108] = 1;
+        data
+    }
+}
@@ -1,29 +1,29 @@
 //! State transition types
 
 pub const MAX_PLAYERS_PER_INSTRUCTION: u16 = 255;
+pub const MAX_SCORES_PER_INSTRUCTION: u8 = 20;
 pub const PLAYERS_CAPACITY: u16 = 100;
-pub const GAMES_COUNT: u8 = 17;
+pub const FOOTBALL_GAMES_COUNT: u8 = 17;
+pub const BASKETBALL_GAMES_COUNT: u8 = 24;
+pub const GAMES_CAPACITY: u8 = BASKETBALL_GAMES_COUNT; //Longest season of all sports
 
-//Active and bench should be set within the league by creator
-pub const ACTIVE_PLAYERS_COUNT: u8 = 3;
-pub const BENCH_PLAYERS_COUNT: u8 = 3;
-pub const TEAM_PLAYERS_COUNT: u8 = ACTIVE_PLAYERS_COUNT + BENCH_PLAYERS_COUNT;
-
-pub const LEAGUES_CAPACITY: u16 = 10;
 pub const LEAGUE_USERS_CAPACITY: u8 = (PLAYERS_CAPACITY / TEAM_PLAYERS_CAPACITY as u16) as u8;
 pub const SWAP_PROPOSALS_CAPACITY: u8 = 20;
+pub const PLAYOFF_TEAMS_CAPACITY: u8 = 8;
+pub const PAYOUTS_LEN: usize = 2 * LEAGUE_USERS_CAPACITY as usize; //Share of the pot in basis points for every place
+pub const BPS_DENOMINATOR: u16 = 10_000;
 
-pub const TEAM_PLAYERS_CAPACITY: u8 = 10; //10 is arbitrary. This should be used instead of TEAM_PLAYERS_COUNT since active/bench players # will vary between leagues
+pub const TEAM_PLAYERS_CAPACITY: u8 = 10; //10 is arbitrary. Active and bench players # are set by each league within this capacity
 pub const NUM_POSITIONS: u8 = 12; //Number of possible positions including unitialized
+pub const FLEX_SLOTS_CAPACITY: u8 = 2;
+pub const DRAFT_QUEUE_CAPACITY: u8 = 2 * TEAM_PLAYERS_CAPACITY; //Player ids a user can queue up for the auto-pick
+pub const STATS_COUNT: u8 = 20; //Number of Stat variants
 
 pub const LEAGUE_NAME_LEN: usize = 256;
 pub const TEAM_NAME_LEN: usize = 256;
 
 pub const PUB_KEY_LEN: usize = 32;
+pub const ALLOW_LIST_LEN: usize = PUB_KEY_LEN * LEAGUE_USERS_CAPACITY as usize;
 
-pub const MAX_QB: usize = 4;
-pub const MAX_RB: usize = 8;
-pub const MAX_WR: usize = 8;
-pub const MAX_TE: usize = 3;
-pub const MAX_K: usize = 3;
-pub const MAX_D: usize = 3;
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const DRAFT_QUEUE_SEED: &[u8] = b"draft_queue";
@@ -0,0 +1,58 @@
+//! State transition types
+
+use crate::state::*;
+use arrayref::{array_mut_ref, array_ref};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::{
+    program_error::ProgramError,
+    program_pack::{Pack, Sealed},
+};
+use std::cell::RefCell;
+
+#[repr(C)]
+pub struct DraftQueueList<'a> {
+    data: &'a RefCell<&'a mut [u8]>,
+    offset: usize,
+}
+impl<'a> DraftQueueList<'a> {
+    pub const ITEM_SIZE: usize = 2;
+    pub const ITEM_COUNT: u8 = consts::DRAFT_QUEUE_CAPACITY;
+    pub const LEN: usize = DraftQueueList::ITEM_SIZE * DraftQueueList::ITEM_COUNT as usize;
+    fn slice<'b>(&self, data: &'b mut [u8], i: u8) -> &'b mut [u8; 2] {
+        array_mut_ref![
+            data,
+            self.offset + i as usize * DraftQueueList::ITEM_SIZE,
+            DraftQueueList::ITEM_SIZE
+        ]
+    }
+
+    pub fn get(&self, i: u8) -> u16 {
+        LittleEndian::read_u16(self.slice(&mut self.data.borrow_mut(), i))
+    }
+    pub fn set(&self, i: u8, value: u16) {
+        LittleEndian::write_u16(self.slice(&mut self.data.borrow_mut(), i), value);
+    }
+
+    /// Queued player ids from the most wanted, empty slots are skipped
+    pub fn get_player_ids(&self) -> Vec<u16> {
+        (0..DraftQueueList::ITEM_COUNT)
+            .map(|i| self.get(i))
+            .filter(|player_id| *player_id != 0)
+            .collect()
+    }
+
+    pub fn set_all(&self, values: &[u8; DraftQueueList::LEN]) {
+        let mut data = self.data.borrow_mut();
+        array_mut_ref![data, self.offset, DraftQueueList::LEN].copy_from_slice(values);
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a mut [u8]>,
+        offset: usize,
+    ) -> Result<DraftQueueList<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(DraftQueueList { data, offset })
+    }
+}
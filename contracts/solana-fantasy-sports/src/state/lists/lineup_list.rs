@@ -16,7 +16,7 @@ pub struct LineupList<'a> {
 }
 impl<'a> LineupList<'a> {
     pub const ITEM_SIZE: usize = lists::ActivePlayersList::LEN;
-    pub const ITEM_COUNT: u8 = consts::GAMES_COUNT;
+    pub const ITEM_COUNT: u8 = consts::GAMES_CAPACITY;
     pub const LEN: usize = LineupList::ITEM_SIZE * LineupList::ITEM_COUNT as usize;
 
     pub fn get_by_week(&self, i: u8) -> Result<ActivePlayersList<'a>, ProgramError> {
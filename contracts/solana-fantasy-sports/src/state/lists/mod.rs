@@ -1,7 +1,5 @@
 pub mod active_players_list;
 pub use active_players_list::*;
-pub mod league_list;
-pub use league_list::*;
 pub mod lineup_list;
 pub use lineup_list::*;
 pub mod player_list;
@@ -16,3 +14,5 @@ pub mod pick_order_list;
 pub use pick_order_list::*;
 pub mod user_player_list;
 pub use user_player_list::*;
+pub mod draft_queue_list;
+pub use draft_queue_list::*;
@@ -52,7 +52,13 @@ impl<'a> PlayerList<'a> {
         )
     }
 
-    pub fn add(&self, external_id: u16, position: Position) -> Result<(), ProgramError> {
+    pub fn add(
+        &self,
+        external_id: u16,
+        position: Position,
+        sport: Sport,
+        rank: u16,
+    ) -> Result<(), ProgramError> {
         if self.get_count() >= PlayerList::ITEM_CAPACITY {
             return Err(SfsError::OutOfCapacity.into());
         }
@@ -60,6 +66,8 @@ impl<'a> PlayerList<'a> {
         let player = self.get_by_id(self.get_count())?;
         player.set_external_id(external_id);
         player.set_position(position);
+        player.set_sport(sport);
+        player.set_rank(rank);
         player.set_is_initialized(true);
         Ok(())
     }
@@ -17,7 +17,7 @@ pub struct ScoreList<'a> {
 }
 impl<'a> ScoreList<'a> {
     pub const ITEM_SIZE: usize = Score::LEN;
-    pub const ITEM_COUNT: u8 = consts::GAMES_COUNT;
+    pub const ITEM_COUNT: u8 = consts::GAMES_CAPACITY;
     pub const LEN: usize = ScoreList::ITEM_SIZE * ScoreList::ITEM_COUNT as usize;
 
     pub fn get_by_week(&self, i: u8) -> Result<Score<'a>, ProgramError> {
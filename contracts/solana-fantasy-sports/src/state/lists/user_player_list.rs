@@ -5,7 +5,6 @@ use crate::state::*;
 use arrayref::{array_mut_ref, array_ref, mut_array_refs};
 use byteorder::{ByteOrder, LittleEndian};
 use solana_program::{
-    info,
     program_error::ProgramError,
     program_pack::{Pack, Sealed},
 };
@@ -18,7 +17,7 @@ pub struct UserPlayerList<'a> {
 }
 impl<'a> UserPlayerList<'a> {
     pub const ITEM_SIZE: usize = 2;
-    pub const ITEM_COUNT: u8 = consts::TEAM_PLAYERS_COUNT;
+    pub const ITEM_COUNT: u8 = consts::TEAM_PLAYERS_CAPACITY;
     pub const LEN: usize = UserPlayerList::ITEM_SIZE * UserPlayerList::ITEM_COUNT as usize;
     fn slice<'b>(&self, data: &'b mut [u8], i: u8) -> &'b mut [u8; 2] {
         array_mut_ref![
@@ -36,6 +35,10 @@ impl<'a> UserPlayerList<'a> {
     }
 
     pub fn index_of(&self, player_id: u16) -> Result<u8, ProgramError> {
+        // Slots beyond the league's roster size are left empty
+        if player_id == 0 {
+            return Err(SfsError::PlayerNotFound.into());
+        }
         for i in 0..UserPlayerList::ITEM_COUNT {
             if self.get(i as u8) == player_id {
                 return Ok(i as u8);
@@ -58,61 +61,22 @@ impl<'a> UserPlayerList<'a> {
         Ok(())
     }
 
-    pub fn validate_team_composition(&self, player_list: &PlayerList) -> Result<(), ProgramError> {
-        let mut qb_count = 0; // max 4
-        let mut rb_count = 0; // max 8
-        let mut wr_count = 0; // max 8
-        let mut te_count = 0; // max 3
-        let mut k_count = 0; // max 3
-        let mut d_count = 0; // max 3
+    pub fn validate_team_composition(
+        &self,
+        player_list: &PlayerList,
+        rules: &CompositionRules,
+        roster_size: u8,
+    ) -> Result<(), ProgramError> {
+        let mut positions = Vec::<Position>::with_capacity(roster_size as usize);
         for i in 0..UserPlayerList::ITEM_COUNT {
-            match player_list.get_by_id(self.get(i as u8))?.get_position()? {
-                Position::RB => {
-                    rb_count += 1;
-                }
-                // LB => {},
-                // DL => {},
-                Position::TE => {
-                    te_count += 1;
-                }
-                // DB => {},
-                Position::QB => {
-                    qb_count += 1;
-                }
-                Position::WR => {
-                    wr_count += 1;
-                }
-                // OL => {},
-                _ => {}
+            let player_id = self.get(i);
+            // Empty slot beyond the league's roster size or not picked yet
+            if player_id == 0 {
+                continue;
             }
+            positions.push(player_list.get_by_id(player_id)?.get_position()?);
         }
-
-        if qb_count > 4 {
-            info!("There was error: QB are becoming more than 4");
-            return Err(SfsError::TeamCompositionRulesViolation.into());
-        }
-        if rb_count > 8 {
-            info!("There was error: RB are becoming more than 8");
-            return Err(SfsError::TeamCompositionRulesViolation.into());
-        }
-        if wr_count > 8 {
-            info!("There was error: WR are becoming more than 8");
-            return Err(SfsError::TeamCompositionRulesViolation.into());
-        }
-        if te_count > 3 {
-            info!("There was error: TE are becoming more than 3");
-            return Err(SfsError::TeamCompositionRulesViolation.into());
-        }
-        if k_count > 3 {
-            info!("There was error: K are becoming more than 3");
-            return Err(SfsError::TeamCompositionRulesViolation.into());
-        }
-        if d_count > 3 {
-            info!("There was error: D are becoming more than 3");
-            return Err(SfsError::TeamCompositionRulesViolation.into());
-        }
-
-        return Ok(());
+        rules.validate(&positions, roster_size)
     }
 
     pub fn copy_to<'b>(&self, to: &UserPlayerList<'b>) {
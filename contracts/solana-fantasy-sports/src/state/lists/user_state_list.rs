@@ -55,10 +55,8 @@ impl<'a> UserStateList<'a> {
         if self.get_count() >= UserStateList::ITEM_CAPACITY {
             return Err(SfsError::OutOfCapacity.into());
         }
-        for i in 1..self.get_count() + 1 {
-            if self.get_by_id(i)?.get_pub_key() == pubkey {
-                return Err(SfsError::AlreadyInUse.into());
-            }
+        if self.contains(&pubkey)? {
+            return Err(SfsError::AlreadyInUse.into());
         }
         self.set_count(self.get_count() + 1);
         let user_state = self.get_by_id(self.get_count())?;
@@ -67,6 +65,35 @@ impl<'a> UserStateList<'a> {
         Ok(user_state)
     }
 
+    pub fn contains(&self, pubkey: &Pubkey) -> Result<bool, ProgramError> {
+        for i in 1..self.get_count() + 1 {
+            if self.get_by_id(i)?.get_pub_key() == *pubkey {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Removes the user, ids of the users after it shift down by one
+    pub fn remove(&self, id: u8) -> Result<(), ProgramError> {
+        let count = self.get_count();
+        if id == 0 || id > count {
+            return Err(SfsError::IndexOutOfRange.into());
+        }
+        {
+            let mut data = self.data.borrow_mut();
+            let items = self.slice(&mut data).1;
+            let start = (id as usize - 1) * UserStateList::ITEM_SIZE;
+            let end = count as usize * UserStateList::ITEM_SIZE;
+            items.copy_within(start + UserStateList::ITEM_SIZE..end, start);
+            for byte in items[end - UserStateList::ITEM_SIZE..end].iter_mut() {
+                *byte = 0;
+            }
+        }
+        self.set_count(count - 1);
+        Ok(())
+    }
+
     pub fn copy_to(&self, to: &Self) {
         let mut dst = to.data.borrow_mut();
         let mut src = self.data.borrow_mut();
@@ -93,6 +120,35 @@ impl<'a> UserStateList<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_remove() {
+        let mut data = vec![0u8; UserStateList::LEN];
+        let data = &RefCell::new(&mut data[..]);
+        let user_states = UserStateList::new(data, 0).unwrap();
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        for key in keys.iter() {
+            user_states.add(*key).unwrap();
+        }
+
+        assert_eq!(user_states.remove(4), Err(SfsError::IndexOutOfRange.into()));
+        user_states.remove(1).unwrap();
+        assert_eq!(user_states.get_count(), 2);
+        assert_eq!(user_states.get_by_id(1).unwrap().get_pub_key(), keys[1]);
+        assert_eq!(user_states.get_by_id(2).unwrap().get_pub_key(), keys[2]);
+
+        // the freed slot is reused by the next user
+        user_states.add(keys[0]).unwrap();
+        assert_eq!(user_states.get_by_id(3).unwrap().get_pub_key(), keys[0]);
+        assert_eq!(
+            user_states.get_by_id(3).unwrap().get_is_lineup_set(),
+            Ok(false)
+        );
+    }
+
     // #[test]
     // fn test_pack_unpack() {
     //     let check = UserStateList {
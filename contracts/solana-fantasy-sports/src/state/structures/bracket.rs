@@ -0,0 +1,209 @@
+//! State transition types
+
+use crate::state::*;
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+
+/// Single elimination playoff of a league, played over the last weeks of the season
+#[repr(C)]
+pub struct Bracket<'a> {
+    data: &'a RefCell<&'a mut [u8]>,
+    offset: usize,
+}
+impl<'a> Bracket<'a> {
+    pub const SEEDS_LEN: usize = PLAYOFF_TEAMS_CAPACITY as usize;
+    pub const WINNERS_LEN: usize = PLAYOFF_TEAMS_CAPACITY as usize - 1;
+    pub const LEN: usize = 1 + 1 + Bracket::SEEDS_LEN + Bracket::WINNERS_LEN;
+    fn slice<'b>(
+        &self,
+        data: &'b mut [u8],
+    ) -> (
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; Bracket::SEEDS_LEN],
+        &'b mut [u8; Bracket::WINNERS_LEN],
+    ) {
+        mut_array_refs![
+            array_mut_ref![data, self.offset, Bracket::LEN],
+            1,
+            1,
+            Bracket::SEEDS_LEN,
+            Bracket::WINNERS_LEN
+        ]
+    }
+
+    /// Number of teams advancing to the playoff, 0 when the league has no playoff
+    pub fn get_teams_count(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).0[0]
+    }
+    pub fn set_teams_count(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).0[0] = value;
+    }
+
+    pub fn get_rounds_count(&self) -> u8 {
+        match self.get_teams_count() {
+            0 => 0,
+            teams_count => teams_count.trailing_zeros() as u8,
+        }
+    }
+
+    pub fn get_settled_rounds(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).1[0]
+    }
+    pub fn set_settled_rounds(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).1[0] = value;
+    }
+
+    /// User id holding the seed, seeds start from 1
+    pub fn get_seed(&self, seed: u8) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).2[seed as usize - 1]
+    }
+    pub fn set_seed(&self, seed: u8, user_id: u8) {
+        self.slice(&mut self.data.borrow_mut()).2[seed as usize - 1] = user_id;
+    }
+
+    pub fn is_seeded(&self) -> bool {
+        self.get_teams_count() > 0 && self.get_seed(1) != 0
+    }
+
+    /// Seed of the user, lower is better
+    pub fn get_seed_of(&self, user_id: u8) -> Option<u8> {
+        (1..self.get_teams_count() + 1).find(|&seed| self.get_seed(seed) == user_id)
+    }
+
+    /// Games are stored round after round, the last one is the final
+    fn get_game_index(&self, round: u8, game: u8) -> usize {
+        let teams_count = self.get_teams_count() as usize;
+        let mut index = game as usize;
+        for r in 1..round {
+            index += teams_count >> r;
+        }
+        index
+    }
+
+    pub fn get_winner(&self, round: u8, game: u8) -> u8 {
+        let index = self.get_game_index(round, game);
+        self.slice(&mut self.data.borrow_mut()).3[index]
+    }
+    pub fn set_winner(&self, round: u8, game: u8, user_id: u8) {
+        let index = self.get_game_index(round, game);
+        self.slice(&mut self.data.borrow_mut()).3[index] = user_id;
+    }
+
+    pub fn get_games_count(&self, round: u8) -> u8 {
+        self.get_teams_count() >> round
+    }
+
+    /// User ids facing each other in a game of a round. The first round pairs
+    /// seeds so that the top seeds can only meet in the final.
+    pub fn get_matchup(&self, round: u8, game: u8) -> (u8, u8) {
+        if round == 1 {
+            let order = Bracket::get_seed_order(self.get_teams_count());
+            (
+                self.get_seed(order[2 * game as usize]),
+                self.get_seed(order[2 * game as usize + 1]),
+            )
+        } else {
+            (
+                self.get_winner(round - 1, 2 * game),
+                self.get_winner(round - 1, 2 * game + 1),
+            )
+        }
+    }
+
+    /// User id of the bracket winner once every round is settled
+    pub fn get_champion(&self) -> Option<u8> {
+        let rounds_count = self.get_rounds_count();
+        if rounds_count == 0 || self.get_settled_rounds() < rounds_count {
+            return None;
+        }
+        Some(self.get_winner(rounds_count, 0))
+    }
+
+    /// Playoff teams from the champion down: losers of later rounds place higher,
+    /// losers of the same round are placed by seed
+    pub fn get_ranking(&self) -> Vec<u8> {
+        let mut ranking = Vec::<u8>::with_capacity(self.get_teams_count() as usize);
+        let champion = match self.get_champion() {
+            Some(champion) => champion,
+            None => return ranking,
+        };
+        ranking.push(champion);
+        for round in (1..self.get_rounds_count() + 1).rev() {
+            let mut losers: Vec<u8> = (0..self.get_games_count(round))
+                .map(|game| {
+                    let (home, away) = self.get_matchup(round, game);
+                    if self.get_winner(round, game) == home {
+                        away
+                    } else {
+                        home
+                    }
+                })
+                .collect();
+            losers.sort_by_key(|&user_id| self.get_seed_of(user_id));
+            ranking.extend(losers);
+        }
+        ranking
+    }
+
+    /// Seeds in bracket order, e.g. 1, 4, 2, 3 for four teams
+    pub fn get_seed_order(teams_count: u8) -> Vec<u8> {
+        let mut order = vec![1u8];
+        while (order.len() as u8) < teams_count {
+            let size = 2 * order.len() as u8;
+            order = order
+                .iter()
+                .flat_map(|&seed| vec![seed, size + 1 - seed])
+                .collect();
+        }
+        order
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a mut [u8]>,
+        offset: usize,
+    ) -> Result<Bracket<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Bracket { data, offset })
+    }
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matchups() {
+        assert_eq!(Bracket::get_seed_order(2), vec![1, 2]);
+        assert_eq!(Bracket::get_seed_order(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+
+        let mut data = [0u8; Bracket::LEN];
+        let data = RefCell::new(&mut data as &mut [u8]);
+        let bracket = Bracket::new(&data, 0).unwrap();
+        bracket.set_teams_count(4);
+        assert_eq!(bracket.get_rounds_count(), 2);
+        assert!(!bracket.is_seeded());
+        for seed in 1..5 {
+            bracket.set_seed(seed, 10 + seed);
+        }
+        assert_eq!(bracket.get_seed_of(13), Some(3));
+        assert_eq!(bracket.get_matchup(1, 0), (11, 14));
+        assert_eq!(bracket.get_matchup(1, 1), (12, 13));
+
+        bracket.set_winner(1, 0, 14);
+        bracket.set_winner(1, 1, 12);
+        bracket.set_settled_rounds(1);
+        assert_eq!(bracket.get_matchup(2, 0), (14, 12));
+        assert_eq!(bracket.get_champion(), None);
+
+        bracket.set_winner(2, 0, 12);
+        bracket.set_settled_rounds(2);
+        assert_eq!(bracket.get_champion(), Some(12));
+        assert_eq!(bracket.get_ranking(), vec![12, 14, 11, 13]);
+    }
+}
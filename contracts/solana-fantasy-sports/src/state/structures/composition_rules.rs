@@ -0,0 +1,154 @@
+//! State transition types
+use crate::error::SfsError;
+use crate::state::*;
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::{info, program_error::ProgramError};
+use std::cell::RefCell;
+
+/// Minimum and maximum number of players per position on a league roster
+#[repr(C)]
+pub struct CompositionRules<'a> {
+    data: &'a RefCell<&'a mut [u8]>,
+    offset: usize,
+}
+impl<'a> CompositionRules<'a> {
+    pub const ITEM_SIZE: usize = 1 + 1;
+    pub const ITEM_COUNT: u8 = NUM_POSITIONS - 1; // Uninitialized has no rule
+    pub const LEN: usize = CompositionRules::ITEM_SIZE * CompositionRules::ITEM_COUNT as usize;
+    fn slice<'b>(&self, data: &'b mut [u8], pos: Position) -> (&'b mut [u8; 1], &'b mut [u8; 1]) {
+        mut_array_refs![
+            array_mut_ref![
+                data,
+                self.offset + (pos as usize - 1) * CompositionRules::ITEM_SIZE,
+                CompositionRules::ITEM_SIZE
+            ],
+            1,
+            1
+        ]
+    }
+
+    pub fn get_min(&self, pos: Position) -> u8 {
+        self.slice(&mut self.data.borrow_mut(), pos).0[0]
+    }
+    pub fn set_min(&self, pos: Position, value: u8) {
+        self.slice(&mut self.data.borrow_mut(), pos).0[0] = value;
+    }
+
+    pub fn get_max(&self, pos: Position) -> u8 {
+        self.slice(&mut self.data.borrow_mut(), pos).1[0]
+    }
+    pub fn set_max(&self, pos: Position, value: u8) {
+        self.slice(&mut self.data.borrow_mut(), pos).1[0] = value;
+    }
+
+    pub fn set(&self, values: &[u8; CompositionRules::LEN]) {
+        let data = &mut self.data.borrow_mut();
+        array_mut_ref![data, self.offset, CompositionRules::LEN].copy_from_slice(values);
+    }
+
+    /// Checks that a roster of `roster_size` players can be built with the rules
+    /// from positions of the sport only
+    pub fn validate_rules(&self, roster_size: u8, sport: Sport) -> Result<(), ProgramError> {
+        let mut min_total = 0u16;
+        let mut max_total = 0u16;
+        for pos in Position::all() {
+            if self.get_min(pos) > self.get_max(pos)
+                || (!sport.has_position(pos) && self.get_max(pos) > 0)
+            {
+                return Err(SfsError::InvalidInstruction.into());
+            }
+            min_total += self.get_min(pos) as u16;
+            max_total += self.get_max(pos) as u16;
+        }
+        if min_total > roster_size as u16 || max_total < roster_size as u16 {
+            return Err(SfsError::InvalidInstruction.into());
+        }
+        Ok(())
+    }
+
+    /// Checks a possibly incomplete roster: no position is over its maximum and
+    /// the picks left are enough to reach every minimum.
+    pub fn validate(&self, positions: &[Position], roster_size: u8) -> Result<(), ProgramError> {
+        let mut counts = [0u8; NUM_POSITIONS as usize];
+        for &pos in positions {
+            if pos == Position::Uninitialized {
+                return Err(SfsError::TeamCompositionRulesViolation.into());
+            }
+            counts[pos as usize] += 1;
+        }
+
+        let mut missing = 0u16;
+        for pos in Position::all() {
+            let count = counts[pos as usize];
+            if count > self.get_max(pos) {
+                info!("Team composition: too many players for a position");
+                return Err(SfsError::TeamCompositionRulesViolation.into());
+            }
+            missing += self.get_min(pos).saturating_sub(count) as u16;
+        }
+        if positions.len() as u16 + missing > roster_size as u16 {
+            info!("Team composition: not enough picks left to reach position minimums");
+            return Err(SfsError::TeamCompositionRulesViolation.into());
+        }
+        Ok(())
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a mut [u8]>,
+        offset: usize,
+    ) -> Result<CompositionRules<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(CompositionRules { data, offset })
+    }
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let mut data = [0u8; CompositionRules::LEN];
+        let data = RefCell::new(&mut data as &mut [u8]);
+        let rules = CompositionRules::new(&data, 0).unwrap();
+        rules.set_min(Position::QB, 1);
+        rules.set_max(Position::QB, 2);
+        rules.set_max(Position::RB, 3);
+        rules.set_max(Position::WR, 3);
+
+        assert_eq!(rules.validate_rules(4, Sport::Football), Ok(()));
+        assert_eq!(
+            rules.validate_rules(9, Sport::Football),
+            Err(SfsError::InvalidInstruction.into())
+        );
+        assert_eq!(
+            rules.validate_rules(4, Sport::Basketball),
+            Err(SfsError::InvalidInstruction.into())
+        );
+
+        // draft in progress, a QB can still be picked
+        assert_eq!(rules.validate(&[Position::RB, Position::WR], 4), Ok(()));
+        // last pick must be a QB
+        assert_eq!(
+            rules.validate(&[Position::RB, Position::WR, Position::RB, Position::WR], 4),
+            Err(SfsError::TeamCompositionRulesViolation.into())
+        );
+        assert_eq!(
+            rules.validate(&[Position::RB, Position::WR, Position::RB, Position::QB], 4),
+            Ok(())
+        );
+        // position without a maximum cannot be rostered
+        assert_eq!(
+            rules.validate(&[Position::QB, Position::K], 4),
+            Err(SfsError::TeamCompositionRulesViolation.into())
+        );
+        assert_eq!(
+            rules.validate(&[Position::QB, Position::QB, Position::QB], 4),
+            Err(SfsError::TeamCompositionRulesViolation.into())
+        );
+    }
+}
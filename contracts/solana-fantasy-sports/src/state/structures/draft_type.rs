@@ -0,0 +1,19 @@
+//! State transition types
+
+use num_enum::TryFromPrimitive;
+
+/// How the users of a league draft their teams.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum DraftType {
+    /// Users pick one player at a time, the order reverses every round
+    Snake,
+    /// Users nominate players and bid on them out of their budgets
+    Auction,
+}
+#[allow(clippy::derivable_impls)]
+impl Default for DraftType {
+    fn default() -> Self {
+        DraftType::Snake
+    }
+}
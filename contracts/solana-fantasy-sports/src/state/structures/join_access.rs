@@ -0,0 +1,23 @@
+//! State transition types
+
+use num_enum::TryFromPrimitive;
+
+/// Who can join a league.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum JoinAccess {
+    /// Any wallet can join
+    Public,
+    /// Wallets that know the password can join. The password seeds a keypair whose public
+    /// key is stored on the league and every join is signed with it, so the password never
+    /// goes on chain and a join signature cannot be reused by another wallet.
+    Password,
+    /// Only wallets on the allow-list of the league can join
+    AllowList,
+}
+#[allow(clippy::derivable_impls)]
+impl Default for JoinAccess {
+    fn default() -> Self {
+        JoinAccess::Public
+    }
+}
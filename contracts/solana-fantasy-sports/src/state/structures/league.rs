@@ -1,10 +1,13 @@
 //! State transition types
+use crate::error::SfsError;
 use crate::state::*;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use byteorder::{ByteOrder, LittleEndian};
+use num_enum::TryFromPrimitive;
 use solana_program::{
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
 };
 use std::cell::RefCell;
 
@@ -14,7 +17,7 @@ pub struct League<'a> {
     offset: usize,
 }
 impl<'a> League<'a> {
-    pub const LEN: usize = UserStateList::LEN + LEAGUE_NAME_LEN + 8 + 1 + 2 + 1 + 1 + 1+ PositionOptions::LEN;
+    pub const LEN: usize = UserStateList::LEN + LEAGUE_NAME_LEN + 8 + 1 + 2 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + 1 + PAYOUTS_LEN + 2 + PUB_KEY_LEN + PUB_KEY_LEN + 8 + 1 + PUB_KEY_LEN + ALLOW_LIST_LEN + 4 + 8 + 1 + 4 + 4 + 2 + 4 + 1 + 8 + 8 + PickOrderList::LEN + Bracket::LEN + ScoringRules::LEN + CompositionRules::LEN + PositionOptions::LEN;
     fn slice<'b>(
         &self,
         data: &'b mut [u8],
@@ -27,6 +30,33 @@ impl<'a> League<'a> {
         &'b mut [u8; 1],
         &'b mut [u8; 1],
         &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; PAYOUTS_LEN],
+        &'b mut [u8; 2],
+        &'b mut [u8; PUB_KEY_LEN],
+        &'b mut [u8; PUB_KEY_LEN],
+        &'b mut [u8; 8],
+        &'b mut [u8; 1],
+        &'b mut [u8; PUB_KEY_LEN],
+        &'b mut [u8; ALLOW_LIST_LEN],
+        &'b mut [u8; 4],
+        &'b mut [u8; 8],
+        &'b mut [u8; 1],
+        &'b mut [u8; 4],
+        &'b mut [u8; 4],
+        &'b mut [u8; 2],
+        &'b mut [u8; 4],
+        &'b mut [u8; 1],
+        &'b mut [u8; 8],
+        &'b mut [u8; 8],
+        &'b mut [u8; PickOrderList::LEN],
+        &'b mut [u8; Bracket::LEN],
+        &'b mut [u8; ScoringRules::LEN],
+        &'b mut [u8; CompositionRules::LEN],
         &'b mut [u8;PositionOptions::LEN],
     ) {
         mut_array_refs![
@@ -39,6 +69,33 @@ impl<'a> League<'a> {
             1,
             1,
             1,
+            1,
+            1,
+            1,
+            1,
+            1,
+            PAYOUTS_LEN,
+            2,
+            PUB_KEY_LEN,
+            PUB_KEY_LEN,
+            8,
+            1,
+            PUB_KEY_LEN,
+            ALLOW_LIST_LEN,
+            4,
+            8,
+            1,
+            4,
+            4,
+            2,
+            4,
+            1,
+            8,
+            8,
+            PickOrderList::LEN,
+            Bracket::LEN,
+            ScoringRules::LEN,
+            CompositionRules::LEN,
             PositionOptions::LEN
         ]
     }
@@ -98,10 +155,296 @@ impl<'a> League<'a> {
         self.slice(&mut self.data.borrow_mut()).7[0] = value as u8;
     }
 
+    pub fn get_active_players_count(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).8[0]
+    }
+    pub fn set_active_players_count(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).8[0] = value;
+    }
+
+    pub fn get_bench_players_count(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).9[0]
+    }
+    pub fn set_bench_players_count(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).9[0] = value;
+    }
+
+    pub fn get_sport(&self) -> Result<Sport, ProgramError> {
+        Sport::try_from_primitive(self.slice(&mut self.data.borrow_mut()).10[0])
+            .or(Err(ProgramError::InvalidAccountData))
+    }
+    pub fn set_sport(&self, value: Sport) {
+        self.slice(&mut self.data.borrow_mut()).10[0] = value as u8;
+    }
+
+    pub fn get_league_type(&self) -> Result<LeagueType, ProgramError> {
+        LeagueType::try_from_primitive(self.slice(&mut self.data.borrow_mut()).11[0])
+            .or(Err(ProgramError::InvalidAccountData))
+    }
+    pub fn set_league_type(&self, value: LeagueType) {
+        self.slice(&mut self.data.borrow_mut()).11[0] = value as u8;
+    }
+
+    /// Last week whose head-to-head matchups are recorded, 0 if none
+    pub fn get_last_settled_week(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).12[0]
+    }
+    pub fn set_last_settled_week(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).12[0] = value;
+    }
+
+    pub fn get_team_players_count(&self) -> u8 {
+        self.get_active_players_count() + self.get_bench_players_count()
+    }
+
+    /// Share of the pot paid to a place in basis points, places start from 1
+    pub fn get_payout(&self, place: u8) -> u16 {
+        let index = 2 * (place as usize - 1);
+        LittleEndian::read_u16(&self.slice(&mut self.data.borrow_mut()).13[index..index + 2])
+    }
+    pub fn set_payouts(&self, value: &[u8; PAYOUTS_LEN]) {
+        self.slice(&mut self.data.borrow_mut())
+            .13
+            .copy_from_slice(value);
+    }
+
+    /// Protocol fee of the root when the league was created, in basis points
+    pub fn get_fee_bps(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&mut self.data.borrow_mut()).14)
+    }
+    pub fn set_fee_bps(&self, value: u16) {
+        LittleEndian::write_u16(self.slice(&mut self.data.borrow_mut()).14, value);
+    }
+
+    /// SPL token mint the bid is denominated in, default for native lamports
+    pub fn get_mint(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&mut self.data.borrow_mut()).15)
+    }
+    pub fn set_mint(&self, value: Pubkey) {
+        self.slice(&mut self.data.borrow_mut())
+            .15
+            .copy_from_slice(value.as_ref());
+    }
+
+    pub fn has_token_bid(&self) -> bool {
+        self.get_mint() != Pubkey::default()
+    }
+
+    /// Token account owned by the league vault escrowing token bids
+    pub fn get_token_vault(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&mut self.data.borrow_mut()).16)
+    }
+    pub fn set_token_vault(&self, value: Pubkey) {
+        self.slice(&mut self.data.borrow_mut())
+            .16
+            .copy_from_slice(value.as_ref());
+    }
+
+    /// Unix timestamp after which an unfilled league can be refunded, zero for none
+    pub fn get_fill_deadline(&self) -> i64 {
+        LittleEndian::read_i64(self.slice(&mut self.data.borrow_mut()).17)
+    }
+    pub fn set_fill_deadline(&self, value: i64) {
+        LittleEndian::write_i64(self.slice(&mut self.data.borrow_mut()).17, value);
+    }
+
+    /// Whether the league ran out of time to fill at the given unix timestamp
+    pub fn is_fill_deadline_passed(&self, now: i64) -> bool {
+        let fill_deadline = self.get_fill_deadline();
+        fill_deadline != 0 && now >= fill_deadline
+    }
+
+    pub fn get_join_access(&self) -> Result<JoinAccess, ProgramError> {
+        JoinAccess::try_from_primitive(self.slice(&mut self.data.borrow_mut()).18[0])
+            .or(Err(ProgramError::InvalidAccountData))
+    }
+    pub fn set_join_access(&self, value: JoinAccess) {
+        self.slice(&mut self.data.borrow_mut()).18[0] = value as u8;
+    }
+
+    /// Public key of the keypair derived from the join password
+    pub fn get_password_key(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&mut self.data.borrow_mut()).19)
+    }
+    pub fn set_password_key(&self, value: &Pubkey) {
+        self.slice(&mut self.data.borrow_mut())
+            .19
+            .copy_from_slice(value.as_ref());
+    }
+
+    /// Wallets allowed to join, unused entries are default pubkeys
+    pub fn is_allow_listed(&self, pubkey: &Pubkey) -> bool {
+        self.slice(&mut self.data.borrow_mut())
+            .20
+            .chunks(PUB_KEY_LEN)
+            .any(|entry| entry == pubkey.as_ref())
+    }
+    pub fn set_allow_list(&self, value: &[u8; ALLOW_LIST_LEN]) {
+        self.slice(&mut self.data.borrow_mut())
+            .20
+            .copy_from_slice(value);
+    }
+
+    /// Seconds a user has to make a pick before it can be auto-picked, zero for no timer
+    pub fn get_pick_time_limit(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&mut self.data.borrow_mut()).21)
+    }
+    pub fn set_pick_time_limit(&self, value: u32) {
+        LittleEndian::write_u32(self.slice(&mut self.data.borrow_mut()).21, value);
+    }
+
+    /// Unix timestamp when the current pick went on the clock
+    pub fn get_pick_started_at(&self) -> i64 {
+        LittleEndian::read_i64(self.slice(&mut self.data.borrow_mut()).22)
+    }
+    pub fn set_pick_started_at(&self, value: i64) {
+        LittleEndian::write_i64(self.slice(&mut self.data.borrow_mut()).22, value);
+    }
+
+    /// Whether the current pick ran out of time at the given unix timestamp
+    pub fn is_pick_expired(&self, now: i64) -> bool {
+        let time_limit = self.get_pick_time_limit();
+        time_limit != 0 && now >= self.get_pick_started_at().saturating_add(time_limit as i64)
+    }
+
+    pub fn get_draft_type(&self) -> Result<DraftType, ProgramError> {
+        DraftType::try_from_primitive(self.slice(&mut self.data.borrow_mut()).23[0])
+            .or(Err(ProgramError::InvalidAccountData))
+    }
+    pub fn set_draft_type(&self, value: DraftType) {
+        self.slice(&mut self.data.borrow_mut()).23[0] = value as u8;
+    }
+
+    /// Budget every user of an auction draft starts with
+    pub fn get_auction_budget(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&mut self.data.borrow_mut()).24)
+    }
+    pub fn set_auction_budget(&self, value: u32) {
+        LittleEndian::write_u32(self.slice(&mut self.data.borrow_mut()).24, value);
+    }
+
+    /// Seconds without a higher bid after which the nominated player is won
+    pub fn get_auction_window(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&mut self.data.borrow_mut()).25)
+    }
+    pub fn set_auction_window(&self, value: u32) {
+        LittleEndian::write_u32(self.slice(&mut self.data.borrow_mut()).25, value);
+    }
+
+    /// Player up for auction, zero when none is nominated
+    pub fn get_nominated_player_id(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&mut self.data.borrow_mut()).26)
+    }
+    pub fn set_nominated_player_id(&self, value: u16) {
+        LittleEndian::write_u16(self.slice(&mut self.data.borrow_mut()).26, value);
+    }
+
+    pub fn get_high_bid(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&mut self.data.borrow_mut()).27)
+    }
+    pub fn set_high_bid(&self, value: u32) {
+        LittleEndian::write_u32(self.slice(&mut self.data.borrow_mut()).27, value);
+    }
+
+    pub fn get_high_bidder_id(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).28[0]
+    }
+    pub fn set_high_bidder_id(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).28[0] = value;
+    }
+
+    /// Unix timestamp when the auction of the nominated player closes
+    pub fn get_auction_deadline(&self) -> i64 {
+        LittleEndian::read_i64(self.slice(&mut self.data.borrow_mut()).29)
+    }
+    pub fn set_auction_deadline(&self, value: i64) {
+        LittleEndian::write_i64(self.slice(&mut self.data.borrow_mut()).29, value);
+    }
+
+    /// Slot the league filled up in, its pick order is drawn from the hash of a later slot
+    pub fn get_fill_slot(&self) -> u64 {
+        LittleEndian::read_u64(self.slice(&mut self.data.borrow_mut()).30)
+    }
+    pub fn set_fill_slot(&self, value: u64) {
+        LittleEndian::write_u64(self.slice(&mut self.data.borrow_mut()).30, value);
+    }
+
+    pub fn is_pick_order_drawn(&self) -> Result<bool, ProgramError> {
+        Ok(self.get_pick_order()?.get(0) != 0)
+    }
+
+    /// Bids of every user
+    pub fn get_pot(&self) -> Result<u64, ProgramError> {
+        self.get_bid()
+            .checked_mul(self.get_user_states()?.get_count() as u64)
+            .ok_or_else(|| SfsError::Overflow.into())
+    }
+
+    /// Last week of the regular season, the playoff takes the weeks after it
+    pub fn get_regular_season_end(&self, games_count: u8) -> Result<u8, ProgramError> {
+        Ok(games_count.saturating_sub(self.get_bracket()?.get_rounds_count()))
+    }
+
+    /// Whether the regular season ran out of weeks before every lineup was set, such a
+    /// league can't be ranked and is refunded instead
+    pub fn is_schedule_missed(
+        &self,
+        current_week: u8,
+        games_count: u8,
+    ) -> Result<bool, ProgramError> {
+        let regular_season_end = self.get_regular_season_end(games_count)?;
+        Ok(match self.get_start_week() {
+            0 => current_week >= regular_season_end,
+            start_week => start_week > regular_season_end,
+        })
+    }
+
     pub fn get_pick_round(&self) -> Result<u8, ProgramError> {
         Ok((self.get_current_pick() / self.get_user_states()?.get_count() as u16) as u8)
     }
 
+    /// User id on the clock at the current pick of the snake draft
+    pub fn get_current_pick_user_id(&self) -> Result<u8, ProgramError> {
+        let users_count = self.get_user_states()?.get_count();
+        let mut pick_in_round = (self.get_current_pick() % users_count as u16) as u8;
+        if self.get_pick_round()? % 2 == 0 {
+            pick_in_round = users_count - pick_in_round - 1;
+        }
+        Ok(self.get_pick_order()?.get(pick_in_round))
+    }
+
+    /// Round robin (circle method) opponent of the user in the given week counting from
+    /// the start week, `None` when the user has a bye in a league with odd users count
+    pub fn get_opponent(&self, user_id: u8, week: u8) -> Result<Option<u8>, ProgramError> {
+        let users_count = self.get_user_states()?.get_count();
+        let start_week = self.get_start_week();
+        if user_id == 0 || user_id > users_count || start_week == 0 || week < start_week {
+            return Err(SfsError::IndexOutOfRange.into());
+        }
+        // An odd users count gets a phantom user, whoever faces it has a bye
+        let slots = users_count + users_count % 2;
+        let rounds = slots - 1;
+        let round = (week - start_week) % rounds;
+        let rotate = |slot: u8| if slot == 0 { 0 } else { (slot - 1 + round) % rounds + 1 };
+        for i in 0..slots / 2 {
+            let home = rotate(i) + 1;
+            let away = rotate(slots - 1 - i) + 1;
+            let opponent = if home == user_id {
+                away
+            } else if away == user_id {
+                home
+            } else {
+                continue;
+            };
+            return Ok(if opponent > users_count {
+                None
+            } else {
+                Some(opponent)
+            });
+        }
+        Err(SfsError::IndexOutOfRange.into())
+    }
+
     pub fn copy_to(&self, to: &Self) {
         let mut dst = to.data.borrow_mut();
         let mut src = self.data.borrow_mut();
@@ -119,6 +462,48 @@ impl<'a> League<'a> {
         Ok(League { data, offset })
     }
 
+    /// User ids in the draft order of the first round, drawn by `DrawPickOrder` once the
+    /// league is full
+    pub fn get_pick_order(&self) -> Result<PickOrderList<'a>, ProgramError> {
+        PickOrderList::new(
+            self.data,
+            self.offset + League::LEN
+                - PositionOptions::LEN
+                - CompositionRules::LEN
+                - ScoringRules::LEN
+                - Bracket::LEN
+                - PickOrderList::LEN,
+        )
+    }
+
+    pub fn get_bracket(&self) -> Result<Bracket<'a>, ProgramError> {
+        Bracket::new(
+            self.data,
+            self.offset + League::LEN
+                - PositionOptions::LEN
+                - CompositionRules::LEN
+                - ScoringRules::LEN
+                - Bracket::LEN,
+        )
+    }
+
+    pub fn get_scoring_rules(&self) -> Result<ScoringRules<'a>, ProgramError> {
+        ScoringRules::new(
+            self.data,
+            self.offset + League::LEN
+                - PositionOptions::LEN
+                - CompositionRules::LEN
+                - ScoringRules::LEN,
+        )
+    }
+
+    pub fn get_composition_rules(&self) -> Result<CompositionRules<'a>, ProgramError> {
+        CompositionRules::new(
+            self.data,
+            self.offset + League::LEN - PositionOptions::LEN - CompositionRules::LEN,
+        )
+    }
+
     pub fn get_position_options(&self)->Result<PositionOptions<'a>,ProgramError>{
         PositionOptions::new(self.data,self.offset + League::LEN - PositionOptions::LEN)
     }
@@ -129,6 +514,18 @@ impl<'a> League<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+    #[test]
+    #[allow(clippy::assertions_on_constants)]
+    fn test_len_fits_account_creation() {
+        // League accounts are allocated by a cross-program invocation of the system program
+        assert!(
+            League::LEN <= MAX_PERMITTED_DATA_INCREASE,
+            "league takes {} bytes",
+            League::LEN
+        );
+    }
 
     // #[test]
     // fn test_pack_unpack() {
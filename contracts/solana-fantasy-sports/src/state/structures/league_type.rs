@@ -0,0 +1,19 @@
+//! State transition types
+
+use num_enum::TryFromPrimitive;
+
+/// How the winner of a league is decided.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum LeagueType {
+    /// Highest cumulative score over the season wins
+    TotalPoints,
+    /// Users face each other in a round robin schedule, best win/loss/tie record wins
+    HeadToHead,
+}
+#[allow(clippy::derivable_impls)]
+impl Default for LeagueType {
+    fn default() -> Self {
+        LeagueType::TotalPoints
+    }
+}
@@ -14,5 +14,21 @@ pub mod swap_proposal;
 pub use swap_proposal::*;
 pub mod stage;
 pub use stage::*;
+pub mod sport;
+pub use sport::*;
+pub mod stat;
+pub use stat::*;
+pub mod scoring_rules;
+pub use scoring_rules::*;
+pub mod league_type;
+pub use league_type::*;
+pub mod join_access;
+pub use join_access::*;
+pub mod bracket;
+pub use bracket::*;
 pub mod position_options;
-pub use position_options::*;
\ No newline at end of file
+pub use position_options::*;
+pub mod composition_rules;
+pub use composition_rules::*;
+pub mod draft_type;
+pub use draft_type::*;
\ No newline at end of file
@@ -15,7 +15,7 @@ pub struct Player<'a> {
     offset: usize,
 }
 impl<'a> Player<'a> {
-    pub const LEN: usize = ScoreList::LEN + 2 + 1 + 1;
+    pub const LEN: usize = ScoreList::LEN + 2 + 1 + 1 + 1 + 2;
     fn slice<'b>(
         &self,
         data: &'b mut [u8],
@@ -24,13 +24,17 @@ impl<'a> Player<'a> {
         &'b mut [u8; 2],
         &'b mut [u8; 1],
         &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 2],
     ) {
         mut_array_refs![
             array_mut_ref![data, self.offset, Player::LEN],
             ScoreList::LEN,
             2,
             1,
-            1
+            1,
+            1,
+            2
         ]
     }
 
@@ -53,11 +57,27 @@ impl<'a> Player<'a> {
         self.slice(&mut self.data.borrow_mut()).2[0] = value as u8;
     }
 
+    pub fn get_sport(&self) -> Result<Sport, ProgramError> {
+        Sport::try_from_primitive(self.slice(&mut self.data.borrow_mut()).3[0])
+            .or(Err(ProgramError::InvalidAccountData))
+    }
+    pub fn set_sport(&self, value: Sport) {
+        self.slice(&mut self.data.borrow_mut()).3[0] = value as u8;
+    }
+
     pub fn get_is_initialized(&self) -> Result<bool, ProgramError> {
-        unpack_bool(self.slice(&mut self.data.borrow_mut()).3)
+        unpack_bool(self.slice(&mut self.data.borrow_mut()).4)
     }
     pub fn set_is_initialized(&self, value: bool) {
-        self.slice(&mut self.data.borrow_mut()).3[0] = value as u8;
+        self.slice(&mut self.data.borrow_mut()).4[0] = value as u8;
+    }
+
+    /// Draft ranking uploaded by the admin, lower is better and zero for unranked
+    pub fn get_rank(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&mut self.data.borrow_mut()).5)
+    }
+    pub fn set_rank(&self, value: u16) {
+        LittleEndian::write_u16(self.slice(&mut self.data.borrow_mut()).5, value);
     }
 
     pub fn copy_to(&self, to: &Self) {
@@ -1,6 +1,8 @@
 //! State transition types
 
+use crate::state::consts::NUM_POSITIONS;
 use num_enum::TryFromPrimitive;
+use std::convert::TryFrom;
 
 /// Position state.
 #[repr(u8)]
@@ -37,3 +39,9 @@ impl Default for Position {
         Position::Uninitialized
     }
 }
+impl Position {
+    /// Every position a player can have, skipping `Uninitialized`
+    pub fn all() -> impl Iterator<Item = Position> {
+        (1..NUM_POSITIONS).map(|i| Position::try_from(i).unwrap())
+    }
+}
@@ -1,10 +1,13 @@
 //Structure implemented by each league to determine the number of players / position to use
+use crate::error::SfsError;
 use crate::state::*;
 use std::cell::RefCell;
+use std::convert::TryFrom;
 use solana_program::{
     program_error::ProgramError
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use byteorder::{ByteOrder, LittleEndian};
 
 pub struct PositionOptions<'a>{
     data: &'a RefCell<&'a mut [u8]>,
@@ -14,71 +17,202 @@ pub struct PositionOptions<'a>{
 /*
     Structure to be included in each league
     Get and set the number of players / position available to select for each team in the league
+    followed by flex slots, each one is a number of players and a bitmask of eligible positions
+    (bit N is set when Position N can fill the slot, e.g. RB/WR/TE)
 */
 impl <'a> PositionOptions<'a>{
-    pub const LEN:usize = NUM_POSITIONS as usize -1; //Ignore type of position: Uninitialized == 6
+    pub const FIXED_LEN: usize = NUM_POSITIONS as usize -1; //Ignore type of position: Uninitialized
+    pub const FLEX_ITEM_SIZE: usize = 1 + 2;
+    pub const FLEX_LEN: usize = PositionOptions::FLEX_ITEM_SIZE * FLEX_SLOTS_CAPACITY as usize;
+    pub const LEN: usize = PositionOptions::FIXED_LEN + PositionOptions::FLEX_LEN;
 
-    //Each slice represents a position
     fn slice<'b>(
         &self,
         data: &'b mut [u8],
     ) -> (
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
-        &'b mut [u8; 1],
+        &'b mut [u8; PositionOptions::FIXED_LEN],
+        &'b mut [u8; PositionOptions::FLEX_LEN],
     ) {
         mut_array_refs![
             array_mut_ref![data, self.offset, PositionOptions::LEN],
+            PositionOptions::FIXED_LEN,
+            PositionOptions::FLEX_LEN
+        ]
+    }
+
+    fn flex_slice<'b>(&self, data: &'b mut [u8], i: u8) -> (&'b mut [u8; 1], &'b mut [u8; 2]) {
+        mut_array_refs![
+            array_mut_ref![
+                self.slice(data).1,
+                i as usize * PositionOptions::FLEX_ITEM_SIZE,
+                PositionOptions::FLEX_ITEM_SIZE
+            ],
             1,
-            1,
-            1,
-            1,
-            1,
-            1,
-            1,
-            1,
-            1,
-            1,
-            1
+            2
         ]
     }
-    // May have to decrement position so that it matches with the position structure and ignores unitialized
 
-    //Value is position
-    pub fn get_number_by_position(&self,value: usize) -> u8{ 
+    pub fn get_number_by_position(&self, pos: Position) -> u8 {
+        if pos == Position::Uninitialized {
+            return 0;
+        }
+        self.slice(&mut self.data.borrow_mut()).0[pos as usize - 1]
+    }
+    pub fn set_number_by_position(&self, pos: Position, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).0[pos as usize - 1] = value;
+    }
+
+    pub fn get_flex_number(&self, i: u8) -> u8 {
+        self.flex_slice(&mut self.data.borrow_mut(), i).0[0]
+    }
+    pub fn get_flex_positions(&self, i: u8) -> u16 {
+        LittleEndian::read_u16(self.flex_slice(&mut self.data.borrow_mut(), i).1)
+    }
+    pub fn set_flex(&self, i: u8, number: u8, positions: u16) {
         let data = &mut self.data.borrow_mut();
-        array_mut_ref![data,self.offset+value,1][0]
+        let (number_dst, positions_dst) = self.flex_slice(data, i);
+        number_dst[0] = number;
+        LittleEndian::write_u16(positions_dst, positions);
+    }
+    pub fn is_flex_eligible(&self, i: u8, pos: Position) -> bool {
+        pos != Position::Uninitialized && self.get_flex_positions(i) & (1 << pos as u16) != 0
     }
-    pub fn get_total(&self) ->u16 {
-        let mut sum:u16 = 0;
-        for i in 0..PositionOptions::LEN{
-            sum += self.get_number_by_position(i) as u16;
+
+    pub fn get_total(&self) -> u16 {
+        let mut sum: u16 = 0;
+        for i in 1..NUM_POSITIONS {
+            sum += self.get_number_by_position(Position::try_from(i).unwrap()) as u16;
+        }
+        for i in 0..FLEX_SLOTS_CAPACITY {
+            sum += self.get_flex_number(i) as u16;
         }
-        return sum
+        sum
     }
-    
-    pub fn set_number_by_position(&self,pos: Position, value: u8){
-        let data = &mut self.data.borrow_mut();
-        array_mut_ref![data,self.offset+pos as usize,1][0] = value;
+
+    /// Checks that only positions of the sport have slots
+    pub fn is_for_sport(&self, sport: Sport) -> bool {
+        for pos in Position::all() {
+            if sport.has_position(pos) {
+                continue;
+            }
+            if self.get_number_by_position(pos) > 0 {
+                return false;
+            }
+            for i in 0..FLEX_SLOTS_CAPACITY {
+                if self.get_flex_number(i) > 0 && self.is_flex_eligible(i, pos) {
+                    return false;
+                }
+            }
+        }
+        true
     }
 
-    pub fn set(&self, values: &[u8;11]){
+    pub fn set(&self, values: &[u8; PositionOptions::LEN]){
         let data = &mut self.data.borrow_mut();
         array_mut_ref![data, self.offset, PositionOptions::LEN].copy_from_slice(values);
     }
 
+    /// Checks that the lineup fills every slot of the template exactly, without modifying it.
+    /// Players that don't fit into a slot of their own position are placed into flex slots.
+    pub fn validate_lineup(&self, positions: &[Position]) -> Result<(), ProgramError> {
+        if positions.len() != self.get_total() as usize {
+            return Err(SfsError::PositionRulesViolation.into());
+        }
+
+        let mut fixed_left = [0u8; NUM_POSITIONS as usize];
+        for i in 1..NUM_POSITIONS {
+            fixed_left[i as usize] = self.get_number_by_position(Position::try_from(i).unwrap());
+        }
+        let mut flex_players = Vec::<Position>::new();
+        for &pos in positions {
+            if pos == Position::Uninitialized {
+                return Err(SfsError::PositionRulesViolation.into());
+            }
+            if fixed_left[pos as usize] > 0 {
+                fixed_left[pos as usize] -= 1;
+            } else {
+                flex_players.push(pos);
+            }
+        }
+
+        let mut flex_left = [0u8; FLEX_SLOTS_CAPACITY as usize];
+        for (i, left) in flex_left.iter_mut().enumerate() {
+            *left = self.get_flex_number(i as u8);
+        }
+        if !self.fill_flex_slots(&flex_players, &mut flex_left) {
+            return Err(SfsError::PositionRulesViolation.into());
+        }
+        Ok(())
+    }
+
+    // Flex slots may overlap, so try every eligible slot for each remaining player
+    fn fill_flex_slots(&self, players: &[Position], flex_left: &mut [u8]) -> bool {
+        let (pos, rest) = match players.split_first() {
+            Some(split) => split,
+            None => return true,
+        };
+        for i in 0..FLEX_SLOTS_CAPACITY {
+            if flex_left[i as usize] > 0 && self.is_flex_eligible(i, *pos) {
+                flex_left[i as usize] -= 1;
+                let filled = self.fill_flex_slots(rest, flex_left);
+                flex_left[i as usize] += 1;
+                if filled {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
     pub fn new(data: &'a RefCell<&'a mut [u8]>, offset: usize) -> Result<PositionOptions,ProgramError>{
         if data.borrow().len() < Self::LEN + offset {
             return Err(ProgramError::InvalidAccountData);
         }
         Ok(PositionOptions{data,offset})
     }
-}
\ No newline at end of file
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_lineup() {
+        let mut data = [0u8; PositionOptions::LEN];
+        let data = RefCell::new(&mut data as &mut [u8]);
+        let options = PositionOptions::new(&data, 0).unwrap();
+        options.set_number_by_position(Position::QB, 1);
+        options.set_number_by_position(Position::RB, 1);
+        options.set_number_by_position(Position::WR, 1);
+        options.set_flex(
+            0,
+            1,
+            1 << Position::RB as u16 | 1 << Position::WR as u16 | 1 << Position::TE as u16,
+        );
+        assert_eq!(options.get_total(), 4);
+
+        assert_eq!(
+            options.validate_lineup(&[Position::QB, Position::RB, Position::WR, Position::TE]),
+            Ok(())
+        );
+        assert_eq!(
+            options.validate_lineup(&[Position::RB, Position::QB, Position::RB, Position::WR]),
+            Ok(())
+        );
+        // two QBs, flex does not accept QB
+        assert_eq!(
+            options.validate_lineup(&[Position::QB, Position::QB, Position::RB, Position::WR]),
+            Err(SfsError::PositionRulesViolation.into())
+        );
+        // flex is taken by a TE, no slot for the second RB
+        assert_eq!(
+            options.validate_lineup(&[Position::RB, Position::RB, Position::TE, Position::QB]),
+            Err(SfsError::PositionRulesViolation.into())
+        );
+        // validation does not change the template
+        assert_eq!(options.get_number_by_position(Position::RB), 1);
+        assert_eq!(options.get_flex_number(0), 1);
+    }
+}
@@ -1,6 +1,8 @@
 //! State transition types
 use crate::state::*;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use byteorder::{ByteOrder, LittleEndian};
+use num_enum::TryFromPrimitive;
 use solana_program::{
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
@@ -15,26 +17,36 @@ pub struct Root<'a> {
 }
 impl<'a> Root<'a> {
     pub const LEN: usize =
-        PlayerList::LEN + LeagueList::LEN + PickOrderList::LEN + 1 + 1 + PUB_KEY_LEN;
+        PlayerList::LEN + 1 + 1 + PUB_KEY_LEN + PUB_KEY_LEN + 2 + 1 + 1 + 2 + PUB_KEY_LEN + 8;
     fn slice<'b>(
         &self,
         data: &'b mut [u8],
     ) -> (
         &'b mut [u8; PlayerList::LEN],
-        &'b mut [u8; LeagueList::LEN],
-        &'b mut [u8; PickOrderList::LEN],
         &'b mut [u8; 1],
         &'b mut [u8; 1],
         &'b mut [u8; PUB_KEY_LEN],
+        &'b mut [u8; PUB_KEY_LEN],
+        &'b mut [u8; 2],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 2],
+        &'b mut [u8; PUB_KEY_LEN],
+        &'b mut [u8; 8],
     ) {
         mut_array_refs![
             array_mut_ref![data, self.offset, Root::LEN],
             PlayerList::LEN,
-            LeagueList::LEN,
-            PickOrderList::LEN,
             1,
             1,
-            PUB_KEY_LEN
+            PUB_KEY_LEN,
+            PUB_KEY_LEN,
+            2,
+            1,
+            1,
+            2,
+            PUB_KEY_LEN,
+            8
         ]
     }
 
@@ -42,43 +54,101 @@ impl<'a> Root<'a> {
         PlayerList::new(self.data, self.offset)
     }
 
-    pub fn get_leagues(&self) -> Result<LeagueList<'a>, ProgramError> {
-        LeagueList::new(self.data, self.offset + PlayerList::LEN)
-    }
-
-    pub fn get_pick_order(&self) -> Result<PickOrderList<'a>, ProgramError> {
-        PickOrderList::new(self.data, self.offset + PlayerList::LEN + LeagueList::LEN)
-    }
-
     pub fn get_stage(&self) -> Result<Stage, ProgramError> {
-        let stage = match self.slice(&mut self.data.borrow_mut()).3 {
+        let stage = match self.slice(&mut self.data.borrow_mut()).1 {
             [0] => Stage::Uninitialized,
-            [1] => Stage::SeasonOpen,
-            [2] => Stage::SeasonComplete,
+            [1] => Stage::DraftSelection,
+            [2] => Stage::SeasonOpen,
+            [3] => Stage::SeasonComplete,
             _ => return Err(ProgramError::InvalidAccountData),
         };
         return Ok(stage);
     }
     pub fn set_stage(&self, value: Stage) {
-        self.slice(&mut self.data.borrow_mut()).3[0] = value as u8;
+        self.slice(&mut self.data.borrow_mut()).1[0] = value as u8;
     }
 
     pub fn get_current_week(&self) -> u8 {
-        self.slice(&mut self.data.borrow_mut()).4[0]
+        self.slice(&mut self.data.borrow_mut()).2[0]
     }
     pub fn set_current_week(&self, value: u8) {
-        self.slice(&mut self.data.borrow_mut()).4[0] = value;
+        self.slice(&mut self.data.borrow_mut()).2[0] = value;
     }
 
     pub fn get_oracle_authority(&self) -> Pubkey {
-        Pubkey::new_from_array(*self.slice(&mut self.data.borrow_mut()).5)
+        Pubkey::new_from_array(*self.slice(&mut self.data.borrow_mut()).3)
     }
     pub fn set_oracle_authority(&self, value: Pubkey) {
         self.slice(&mut self.data.borrow_mut())
-            .5
+            .3
             .copy_from_slice(value.as_ref());
     }
 
+    /// Authority allowed to manage the player pool and the season
+    pub fn get_admin_authority(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&mut self.data.borrow_mut()).4)
+    }
+    pub fn set_admin_authority(&self, value: Pubkey) {
+        self.slice(&mut self.data.borrow_mut())
+            .4
+            .copy_from_slice(value.as_ref());
+    }
+
+    /// Number of league accounts created so far, also the index of the next league
+    pub fn get_leagues_count(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&mut self.data.borrow_mut()).5)
+    }
+    pub fn set_leagues_count(&self, value: u16) {
+        LittleEndian::write_u16(self.slice(&mut self.data.borrow_mut()).5, value);
+    }
+
+    pub fn get_sport(&self) -> Result<Sport, ProgramError> {
+        Sport::try_from_primitive(self.slice(&mut self.data.borrow_mut()).6[0])
+            .or(Err(ProgramError::InvalidAccountData))
+    }
+    pub fn set_sport(&self, value: Sport) {
+        self.slice(&mut self.data.borrow_mut()).6[0] = value as u8;
+    }
+
+    /// Number of past weeks, besides the current one, whose scores the oracle can still correct
+    pub fn get_score_correction_weeks(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).7[0]
+    }
+    pub fn set_score_correction_weeks(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).7[0] = value;
+    }
+
+    /// Protocol fee taken from the pot of every league in basis points
+    pub fn get_fee_bps(&self) -> u16 {
+        LittleEndian::read_u16(self.slice(&mut self.data.borrow_mut()).8)
+    }
+    pub fn set_fee_bps(&self, value: u16) {
+        LittleEndian::write_u16(self.slice(&mut self.data.borrow_mut()).8, value);
+    }
+
+    /// Account receiving the withdrawn protocol fees
+    pub fn get_treasury(&self) -> Pubkey {
+        Pubkey::new_from_array(*self.slice(&mut self.data.borrow_mut()).9)
+    }
+    pub fn set_treasury(&self, value: Pubkey) {
+        self.slice(&mut self.data.borrow_mut())
+            .9
+            .copy_from_slice(value.as_ref());
+    }
+
+    /// Protocol fees collected by claims and not withdrawn yet
+    pub fn get_accrued_fees(&self) -> u64 {
+        LittleEndian::read_u64(self.slice(&mut self.data.borrow_mut()).10)
+    }
+    pub fn set_accrued_fees(&self, value: u64) {
+        LittleEndian::write_u64(self.slice(&mut self.data.borrow_mut()).10, value);
+    }
+
+    /// Number of weeks in the season of the root's sport
+    pub fn get_games_count(&self) -> Result<u8, ProgramError> {
+        Ok(self.get_sport()?.get_games_count())
+    }
+
     pub fn copy_to(&self, to: &Self) {
         let mut dst = to.data.borrow_mut();
         let mut src = self.data.borrow_mut();
@@ -7,6 +7,7 @@ use solana_program::{
     program_pack::{IsInitialized, Pack, Sealed},
 };
 use byteorder::{ByteOrder, LittleEndian};
+use num_enum::TryFromPrimitive;
 use std::cell::RefCell;
 
 #[repr(C)]
@@ -15,16 +16,41 @@ pub struct Score<'a> {
     offset: usize,
 }
 impl<'a> Score<'a> {
-    pub const LEN: usize = 2 + 1;
-    fn slice<'b>(&self, data: &'b mut [u8]) -> (&'b mut [u8; 2], &'b mut [u8; 1]) {
-        mut_array_refs![array_mut_ref![data, self.offset, Score::LEN], 2, 1]
+    pub const STATS_LEN: usize = 2 * STATS_COUNT as usize;
+    pub const LEN: usize = Score::STATS_LEN + 1 + 1 + 4 + Score::STATS_LEN;
+    #[allow(clippy::type_complexity)]
+    fn slice<'b>(
+        &self,
+        data: &'b mut [u8],
+    ) -> (
+        &'b mut [u8; Score::STATS_LEN],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 4],
+        &'b mut [u8; Score::STATS_LEN],
+    ) {
+        mut_array_refs![
+            array_mut_ref![data, self.offset, Score::LEN],
+            Score::STATS_LEN,
+            1,
+            1,
+            4,
+            Score::STATS_LEN
+        ]
     }
 
-    pub fn get_score1(&self) -> u16 {
-        LittleEndian::read_u16(self.slice(&mut self.data.borrow_mut()).0)
+    pub fn get_stat(&self, stat: Stat) -> i16 {
+        LittleEndian::read_i16(&self.slice(&mut self.data.borrow_mut()).0[stat as usize * 2..])
     }
-    pub fn set_score1(&self, value: u16) {
-        LittleEndian::write_u16(self.slice(&mut self.data.borrow_mut()).0, value);
+    pub fn set_stat(&self, stat: Stat, value: i16) {
+        LittleEndian::write_i16(
+            &mut self.slice(&mut self.data.borrow_mut()).0[stat as usize * 2..],
+            value,
+        );
+    }
+
+    pub fn set_stats(&self, values: &[i16; STATS_COUNT as usize]) {
+        LittleEndian::write_i16_into(values, self.slice(&mut self.data.borrow_mut()).0);
     }
 
     pub fn get_is_initialized(&self) -> Result<bool, ProgramError> {
@@ -34,6 +60,37 @@ impl<'a> Score<'a> {
         self.slice(&mut self.data.borrow_mut()).1[0] = value as u8;
     }
 
+    /// Number of corrections made after the score was first recorded
+    pub fn get_corrections_count(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).2[0]
+    }
+    pub fn set_corrections_count(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).2[0] = value;
+    }
+
+    /// Whether the stat was changed by a correction
+    pub fn get_is_stat_corrected(&self, stat: Stat) -> bool {
+        LittleEndian::read_u32(self.slice(&mut self.data.borrow_mut()).3) & 1 << stat as u32 != 0
+    }
+    pub fn set_is_stat_corrected(&self, stat: Stat) {
+        let mask = LittleEndian::read_u32(self.slice(&mut self.data.borrow_mut()).3);
+        LittleEndian::write_u32(
+            self.slice(&mut self.data.borrow_mut()).3,
+            mask | 1 << stat as u32,
+        );
+    }
+
+    /// Value of the stat before its last correction
+    pub fn get_previous_stat(&self, stat: Stat) -> i16 {
+        LittleEndian::read_i16(&self.slice(&mut self.data.borrow_mut()).4[stat as usize * 2..])
+    }
+    pub fn set_previous_stat(&self, stat: Stat, value: i16) {
+        LittleEndian::write_i16(
+            &mut self.slice(&mut self.data.borrow_mut()).4[stat as usize * 2..],
+            value,
+        );
+    }
+
     pub fn copy_to(&self, to: &Self) {
         let mut dst = to.data.borrow_mut();
         let mut src = self.data.borrow_mut();
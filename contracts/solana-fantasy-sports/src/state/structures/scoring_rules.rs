@@ -0,0 +1,135 @@
+//! State transition types
+use crate::error::SfsError;
+use crate::state::*;
+use arrayref::{array_mut_ref, array_ref};
+use byteorder::{ByteOrder, LittleEndian};
+use solana_program::program_error::ProgramError;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+
+/// League scoring table, fantasy points per unit of every `Stat` in hundredths of a point
+#[repr(C)]
+pub struct ScoringRules<'a> {
+    data: &'a RefCell<&'a mut [u8]>,
+    offset: usize,
+}
+impl<'a> ScoringRules<'a> {
+    pub const ITEM_SIZE: usize = 2;
+    pub const ITEM_COUNT: u8 = STATS_COUNT;
+    pub const LEN: usize = ScoringRules::ITEM_SIZE * ScoringRules::ITEM_COUNT as usize;
+    fn slice<'b>(&self, data: &'b mut [u8], stat: Stat) -> &'b mut [u8; 2] {
+        array_mut_ref![
+            data,
+            self.offset + stat as usize * ScoringRules::ITEM_SIZE,
+            ScoringRules::ITEM_SIZE
+        ]
+    }
+
+    pub fn get_weight(&self, stat: Stat) -> i16 {
+        LittleEndian::read_i16(self.slice(&mut self.data.borrow_mut(), stat))
+    }
+    pub fn set_weight(&self, stat: Stat, value: i16) {
+        LittleEndian::write_i16(self.slice(&mut self.data.borrow_mut(), stat), value);
+    }
+
+    pub fn set(&self, values: &[u8; ScoringRules::LEN]) {
+        let data = &mut self.data.borrow_mut();
+        array_mut_ref![data, self.offset, ScoringRules::LEN].copy_from_slice(values);
+    }
+
+    /// Fantasy points of a stat line, in hundredths of a point
+    pub fn get_points(&self, score: &Score) -> Result<i64, ProgramError> {
+        let mut points = 0i64;
+        for i in 0..STATS_COUNT {
+            let stat = Stat::try_from(i).unwrap();
+            points = (score.get_stat(stat) as i64)
+                .checked_mul(self.get_weight(stat) as i64)
+                .and_then(|stat_points| points.checked_add(stat_points))
+                .ok_or(SfsError::Overflow)?;
+        }
+        Ok(points)
+    }
+
+    /// Football scoring table, `reception_weight` is 0 for standard,
+    /// 50 for half-PPR and 100 for PPR leagues
+    pub fn football_preset(reception_weight: i16) -> [u8; ScoringRules::LEN] {
+        let mut weights = [0i16; STATS_COUNT as usize];
+        weights[Stat::PassingYards as usize] = 4;
+        weights[Stat::PassingTouchdowns as usize] = 400;
+        weights[Stat::Interceptions as usize] = -200;
+        weights[Stat::RushingYards as usize] = 10;
+        weights[Stat::RushingTouchdowns as usize] = 600;
+        weights[Stat::Receptions as usize] = reception_weight;
+        weights[Stat::ReceivingYards as usize] = 10;
+        weights[Stat::ReceivingTouchdowns as usize] = 600;
+        weights[Stat::FumblesLost as usize] = -200;
+        weights[Stat::FieldGoals as usize] = 300;
+        weights[Stat::ExtraPoints as usize] = 100;
+        weights[Stat::Sacks as usize] = 100;
+        weights[Stat::DefensiveInterceptions as usize] = 200;
+        weights[Stat::DefensiveTouchdowns as usize] = 600;
+
+        let mut buf = [0u8; ScoringRules::LEN];
+        LittleEndian::write_i16_into(&weights, &mut buf);
+        buf
+    }
+
+    pub fn new(
+        data: &'a RefCell<&'a mut [u8]>,
+        offset: usize,
+    ) -> Result<ScoringRules<'a>, ProgramError> {
+        if data.borrow().len() < Self::LEN + offset {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(ScoringRules { data, offset })
+    }
+}
+
+// Pull in syscall stubs when building for non-BPF targets
+#[cfg(not(target_arch = "bpf"))]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_points() {
+        let mut score_data = [0u8; Score::LEN];
+        let score_data = RefCell::new(&mut score_data as &mut [u8]);
+        let score = Score::new(&score_data, 0).unwrap();
+        score.set_stat(Stat::Receptions, 6);
+        score.set_stat(Stat::ReceivingYards, 85);
+        score.set_stat(Stat::ReceivingTouchdowns, 1);
+        score.set_stat(Stat::RushingYards, -3);
+        score.set_stat(Stat::FumblesLost, 1);
+
+        let mut rules_data = ScoringRules::football_preset(0);
+        let rules_data = RefCell::new(&mut rules_data as &mut [u8]);
+        let rules = ScoringRules::new(&rules_data, 0).unwrap();
+        // 8.5 + 6 - 0.3 - 2
+        assert_eq!(rules.get_points(&score).unwrap(), 1220);
+
+        rules.set(&ScoringRules::football_preset(50));
+        assert_eq!(rules.get_points(&score).unwrap(), 1520);
+
+        rules.set(&ScoringRules::football_preset(100));
+        assert_eq!(rules.get_points(&score).unwrap(), 1820);
+
+        rules.set_weight(Stat::Receptions, 0);
+        rules.set_weight(Stat::ReceivingYards, 0);
+        rules.set_weight(Stat::ReceivingTouchdowns, 0);
+        rules.set_weight(Stat::RushingYards, 0);
+        rules.set_weight(Stat::FumblesLost, 0);
+        assert_eq!(rules.get_points(&score).unwrap(), 0);
+
+        // Every stat and weight at its extreme sums past the i32 range
+        for i in 0..STATS_COUNT {
+            let stat = Stat::try_from(i).unwrap();
+            score.set_stat(stat, i16::MIN);
+            rules.set_weight(stat, i16::MIN);
+        }
+        assert_eq!(
+            rules.get_points(&score).unwrap(),
+            STATS_COUNT as i64 * (i16::MIN as i64 * i16::MIN as i64)
+        );
+    }
+}
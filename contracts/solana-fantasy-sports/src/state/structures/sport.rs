@@ -0,0 +1,52 @@
+//! State transition types
+
+use crate::state::*;
+use num_enum::TryFromPrimitive;
+
+/// Sport a player pool and its leagues belong to.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum Sport {
+    /// Not yet initialized
+    Uninitialized,
+    /// American football, RB to DEF positions
+    Football,
+    /// Basketball, PG to C positions
+    Basketball,
+}
+// `#[default]` on a variant needs a newer compiler than the BPF toolchain ships
+#[allow(clippy::derivable_impls)]
+impl Default for Sport {
+    fn default() -> Self {
+        Sport::Uninitialized
+    }
+}
+impl Sport {
+    /// Number of weeks in a regular season
+    pub fn get_games_count(&self) -> u8 {
+        match self {
+            Sport::Uninitialized => 0,
+            Sport::Football => FOOTBALL_GAMES_COUNT,
+            Sport::Basketball => BASKETBALL_GAMES_COUNT,
+        }
+    }
+
+    pub fn has_position(&self, position: Position) -> bool {
+        match self {
+            Sport::Uninitialized => false,
+            Sport::Football => matches!(
+                position,
+                Position::RB
+                    | Position::WR
+                    | Position::QB
+                    | Position::TE
+                    | Position::K
+                    | Position::DEF
+            ),
+            Sport::Basketball => matches!(
+                position,
+                Position::PG | Position::SG | Position::SF | Position::PF | Position::C
+            ),
+        }
+    }
+}
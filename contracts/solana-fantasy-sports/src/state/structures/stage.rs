@@ -6,6 +6,8 @@ use num_enum::TryFromPrimitive;
 #[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
 pub enum Stage {
     Uninitialized,
+    /// Root is initialized, players are being seeded
+    DraftSelection,
     SeasonOpen,
     SeasonComplete,
 }
@@ -0,0 +1,30 @@
+//! State transition types
+
+use num_enum::TryFromPrimitive;
+
+/// Raw stat categories reported by the oracle for a player's week.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum Stat {
+    PassingYards,
+    PassingTouchdowns,
+    Interceptions,
+    RushingYards,
+    RushingTouchdowns,
+    Receptions,
+    ReceivingYards,
+    ReceivingTouchdowns,
+    FumblesLost,
+    FieldGoals,
+    ExtraPoints,
+    Sacks,
+    DefensiveInterceptions,
+    DefensiveTouchdowns,
+
+    Points,
+    Rebounds,
+    Assists,
+    Steals,
+    Blocks,
+    Turnovers,
+}
@@ -1,6 +1,7 @@
 //! State transition types
 use crate::state::*;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use byteorder::{ByteOrder, LittleEndian};
 use solana_program::{
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
@@ -20,7 +21,12 @@ impl<'a> UserState<'a> {
         + TEAM_NAME_LEN
         + PUB_KEY_LEN
         + 1
-        + 1;
+        + 1
+        + 1
+        + 1
+        + 1
+        + 8
+        + 4;
     fn slice<'b>(
         &self,
         data: &'b mut [u8],
@@ -32,6 +38,11 @@ impl<'a> UserState<'a> {
         &'b mut [u8; PUB_KEY_LEN],
         &'b mut [u8; 1],
         &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 1],
+        &'b mut [u8; 8],
+        &'b mut [u8; 4],
     ) {
         mut_array_refs![
             array_mut_ref![data, self.offset, UserState::LEN],
@@ -41,7 +52,12 @@ impl<'a> UserState<'a> {
             TEAM_NAME_LEN,
             PUB_KEY_LEN,
             1,
-            1
+            1,
+            1,
+            1,
+            1,
+            8,
+            4
         ]
     }
 
@@ -92,6 +108,43 @@ impl<'a> UserState<'a> {
         self.slice(&mut self.data.borrow_mut()).6[0] = value as u8;
     }
 
+    pub fn get_wins(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).7[0]
+    }
+    pub fn set_wins(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).7[0] = value;
+    }
+
+    pub fn get_losses(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).8[0]
+    }
+    pub fn set_losses(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).8[0] = value;
+    }
+
+    pub fn get_ties(&self) -> u8 {
+        self.slice(&mut self.data.borrow_mut()).9[0]
+    }
+    pub fn set_ties(&self, value: u8) {
+        self.slice(&mut self.data.borrow_mut()).9[0] = value;
+    }
+
+    /// Points scored in settled head-to-head weeks, in hundredths of a point
+    pub fn get_points_for(&self) -> i64 {
+        LittleEndian::read_i64(self.slice(&mut self.data.borrow_mut()).10)
+    }
+    pub fn set_points_for(&self, value: i64) {
+        LittleEndian::write_i64(self.slice(&mut self.data.borrow_mut()).10, value);
+    }
+
+    /// Budget left to bid with in an auction draft
+    pub fn get_budget(&self) -> u32 {
+        LittleEndian::read_u32(self.slice(&mut self.data.borrow_mut()).11)
+    }
+    pub fn set_budget(&self, value: u32) {
+        LittleEndian::write_u32(self.slice(&mut self.data.borrow_mut()).11, value);
+    }
+
     pub fn copy_to(&self, to: &Self) {
         let mut dst = to.data.borrow_mut();
         let mut src = self.data.borrow_mut();
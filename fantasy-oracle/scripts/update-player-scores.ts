@@ -1,32 +1,54 @@
-import { calculateScore } from '../src/calculate-score';
-import { connection, sfsFn, wallet } from './commons';
+import { PublicKey } from '@solana/web3.js';
+import { fetchPlayerStats, ScoreRaw } from '../src/calculate-score';
+import { connection, programId, rootPublicKey, wallet } from './commons';
+import { SFS } from '../../sdk/sfs';
+import { PlayerScore } from '../../sdk/instruction';
+import { Stat, STATS_COUNT } from '../../sdk/state';
 
-interface SdkScore {
-  playerId: number;
-  playerScore: number;
+function toStatLine(playerGameStats: ScoreRaw | undefined): number[] {
+  const stats: number[] = Array(STATS_COUNT).fill(0);
+  if (!playerGameStats) {
+    return stats;
+  }
+  stats[Stat.PassingYards] = playerGameStats.PassingYards;
+  stats[Stat.PassingTouchdowns] = playerGameStats.PassingTouchdowns;
+  stats[Stat.Interceptions] = playerGameStats.PassingInterceptions;
+  stats[Stat.RushingYards] = playerGameStats.RushingYards;
+  stats[Stat.RushingTouchdowns] = playerGameStats.RushingTouchdowns;
+  stats[Stat.Receptions] = playerGameStats.Receptions;
+  stats[Stat.ReceivingYards] = playerGameStats.ReceivingYards;
+  stats[Stat.ReceivingTouchdowns] = playerGameStats.ReceivingTouchdowns;
+  stats[Stat.FumblesLost] = playerGameStats.FumblesLost;
+  stats[Stat.FieldGoals] = playerGameStats.FieldGoalsMade;
+  stats[Stat.ExtraPoints] = playerGameStats.ExtraPointsMade;
+  stats[Stat.Sacks] = playerGameStats.Sacks;
+  stats[Stat.DefensiveInterceptions] = playerGameStats.Interceptions;
+  stats[Stat.DefensiveTouchdowns] = playerGameStats.DefensiveTouchdowns;
+  // The API reports fractional values for some players, the program takes whole i16 counts
+  return stats.map((x) => Math.min(Math.max(Math.round(x ?? 0), -32768), 32767));
 }
 
 (async () => {
-  const sfs = await sfsFn();
+  const [bank] = await PublicKey.findProgramAddress([Buffer.from([0])], programId);
+  const sfs = new SFS(connection, rootPublicKey, programId, bank);
 
   console.log('Fetching players list from root');
   const root = await sfs.getRootInfo();
   const idArr = root.players.filter((p) => p.isInitialized).map((p) => p.externalId);
 
   console.log('ongoing week', root.currentWeek + 1);
-  console.log('Calculating scores for week', root.currentWeek);
-  const { scoresArr } = await calculateScore(idArr, root.currentWeek);
-  console.log('calculated scores', scoresArr);
+  console.log('Fetching stats for week', root.currentWeek);
+  const { statsArr } = await fetchPlayerStats(idArr, root.currentWeek);
 
-  const scores: SdkScore[] = [];
+  const scores: PlayerScore[] = [];
 
-  for (const [index, score] of scoresArr.entries()) {
+  for (const [index, playerGameStats] of statsArr.entries()) {
     scores.push({
       playerId: index + 1,
-      playerScore: score,
+      stats: toStatLine(playerGameStats),
     });
-    // console.log(`Score of player id ${index + 1}: ${score}`);
   }
+  console.log('collected stat lines', scores);
 
   console.log(`Sending score update tx`);
   await sfs.updatePlayerScores(wallet, scores);
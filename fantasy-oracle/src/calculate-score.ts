@@ -1,6 +1,6 @@
 import { axios, AxiosResponse } from './axios';
 
-interface ScoreRaw {
+export interface ScoreRaw {
   GameKey: string; // '202010122',
   PlayerID: number; // 20981,
   SeasonType: number; // 1,
@@ -181,8 +181,8 @@ interface ScoreRaw {
   }>; // []
 }
 
-export async function calculateScore(playerExternalIds: number[], week: number) {
-  console.log('number of players for calculating score', playerExternalIds.length);
+export async function fetchPlayerStats(playerExternalIds: number[], week: number) {
+  console.log('number of players for fetching stats', playerExternalIds.length);
 
   const response: AxiosResponse<ScoreRaw[]> = await axios.get(
     `https://api.sportsdata.io/v3/nfl/stats/json/PlayerGameStatsByWeek/2020/${week}?key=80f05fefe3ea4b2c81c0b6b0289beed9`
@@ -207,348 +207,12 @@ export async function calculateScore(playerExternalIds: number[], week: number)
     })
   );
 
-  let totalScore = 0;
-  let scoresArr: Array<number> = Array(playerExternalIds.length).fill(0);
+  // Fantasy points are computed on chain from the league's scoring rules,
+  // so only the raw stat lines are collected here
+  let statsArr: Array<ScoreRaw | undefined> = Array(playerExternalIds.length).fill(undefined);
 
   for (const playerGameStats of playerGameStatsArr) {
     const playerId = playerGameStats.PlayerID;
-    // https://www.espn.in/fantasy/football/ffl/story?page=fflrulesstandardscoring
-    /**
-     * Calculation for Offense STARTS here
-     */
-    let playerScore = 0;
-
-    let offensePositions = ['QB', 'RB', 'WR', 'TE'];
-    if (offensePositions.includes(playerGameStats.Position)) {
-      /**
-       * 6 pts per rushing or receiving TD
-       */
-      {
-        const rushings = playerGameStats.RushingTouchdowns;
-        const receivings = playerGameStats.ReceivingTouchdowns;
-
-        playerScore += (rushings + receivings) * 6;
-      }
-
-      /**
-       * 6 pts for player returning kick/punt for TD
-       */
-      {
-        const returningKicksPlunts =
-          playerGameStats.PuntReturnTouchdowns + playerGameStats.KickReturnTouchdowns;
-
-        playerScore += returningKicksPlunts * 6;
-      }
-
-      /**
-       * 6 pts for player returning or recovering a fumble for TD
-       */
-      {
-        const fumbles =
-          playerGameStats.FumbleReturnTouchdowns +
-          (playerGameStats.OffensiveFumbleRecoveryTouchdowns ?? 0); //+
-
-        playerScore += fumbles * 6;
-      }
-
-      /**
-       * 4 pts per passing TD
-       */
-      {
-        const passingTd = playerGameStats.PassingTouchdowns;
-
-        playerScore += passingTd * 4;
-      }
-
-      /**
-       * 2 pts per rushing or receiving 2 pt conversion (note: teams do not receive points for yardage gained during the conversion)
-       */
-      {
-        const rushings =
-          playerGameStats.RushingAttempts +
-          playerGameStats.RushingLong +
-          playerGameStats.RushingTouchdowns +
-          playerGameStats.RushingYards +
-          playerGameStats.RushingYardsPerAttempt;
-
-        const receivings =
-          playerGameStats.ReceivingLong +
-          playerGameStats.ReceivingTargets +
-          playerGameStats.ReceivingTouchdowns +
-          playerGameStats.ReceivingYards +
-          playerGameStats.ReceivingYardsPerReception +
-          playerGameStats.ReceivingYardsPerTarget;
-
-        playerScore += (rushings + receivings) * 2;
-      }
-
-      /**
-       * 2 pts per passing 2 pt conversion
-       */
-      {
-        const passing =
-          playerGameStats.PassingAttempts +
-          playerGameStats.PassingCompletions +
-          playerGameStats.PassingInterceptions +
-          playerGameStats.PassingLong +
-          playerGameStats.PassingSackYards +
-          playerGameStats.PassingSacks +
-          playerGameStats.PassingTouchdowns +
-          playerGameStats.PassingYards;
-
-        playerScore += passing * 2;
-      }
-
-      /**
-       * 1 pt per 10 yards rushing or receiving
-       */
-      {
-        const rushings = playerGameStats.RushingYards;
-
-        const receivings = playerGameStats.ReceivingYards;
-
-        playerScore += Math.floor((rushings + receivings) / 10) * 1;
-      }
-
-      /**
-       * 1 pt per 25 yards passing
-       */
-      {
-        const passing = playerGameStats.PassingYards;
-
-        playerScore += Math.floor(passing / 25) * 1;
-      }
-
-      /**
-       *  Calculation for Offense Bonous Points
-       */
-      {
-        /**
-         * 2 pts per rushing or receiving TD of 40 yards or more
-         */
-        {
-          const rushings = playerGameStats.RushingYards;
-          const receivings = playerGameStats.ReceivingYards;
-
-          playerScore += Math.floor((rushings + receivings) / 40) * 2;
-        }
-
-        /**
-         * 2 pts per passing TD of 40 yards or more (note: the player must score a touchdown to score the points)
-         */
-        {
-          const passing = playerGameStats.PassingYards;
-
-          playerScore += Math.floor(passing / 40) * 1;
-        }
-      }
-
-      /**
-       *  Calculation for Offense Penalty Points
-       */
-      {
-        /**
-         * -2 pts per intercepted pass
-         */
-        {
-          const passing = playerGameStats.PassingInterceptions;
-
-          playerScore += passing * -2;
-        }
-
-        /**
-         * -2 pts per fumble lost
-         */
-        {
-          const fumbles = playerGameStats.FumblesLost;
-
-          playerScore += fumbles * 6;
-        }
-      }
-    }
-    /**
-     * Calculation for Offense ENDS here
-     */
-
-    /**
-     * Calculation for Kickers (K) STARTS here
-     */
-    let kickersPositions = ['K'];
-    if (kickersPositions.includes(playerGameStats.Position)) {
-      /**
-       * 5 pts per 50+ yard FG made
-       */
-      {
-        const fg = playerGameStats.FieldGoalsMade50Plus;
-        playerScore += fg * 5;
-      }
-
-      /**
-       * 4 pts per 40-49 yard FG made
-       */
-      {
-        const fg = playerGameStats.FieldGoalsMade40to49;
-        playerScore += fg * 4;
-      }
-
-      /**
-       * 3 pts per FG made, 39 yards or less
-       */
-      {
-        const fg =
-          playerGameStats.FieldGoalsMade0to19 +
-          playerGameStats.FieldGoalsMade20to29 +
-          playerGameStats.FieldGoalsMade30to39;
-
-        playerScore += fg * 3;
-      }
-
-      /**
-       * 2 pts per rushing, passing, or receiving 2 pt conversion
-       */
-      {
-        const rushings = playerGameStats.RushingAttempts;
-        const receivings =
-          playerGameStats.ReceivingLong +
-          playerGameStats.ReceivingTargets +
-          playerGameStats.ReceivingTouchdowns;
-
-        const passing = playerGameStats.PassingAttempts;
-
-        playerScore += (rushings + passing + receivings) * 2;
-      }
-
-      /**
-       * 1 pt per Extra Point made
-       */
-      {
-        const extraPoint = playerGameStats.ExtraPointsMade;
-
-        playerScore += extraPoint * 1;
-      }
-
-      /**
-       * Kickers Penalty Points
-       */
-
-      /**
-       * -2 pts per missed FG (0-39 yds)
-       */
-      {
-        // i think there can be different/specific props here outside of interface
-        const missedFG = 0;
-        // playerGameStats.FieldGoalPercentage +
-        // playerGameStats.FieldGoalReturnTouchdowns +
-        // playerGameStats.FieldGoalReturnYards +
-        // playerGameStats.FieldGoalsAttempted +
-        // playerGameStats.FieldGoalsHadBlocked +
-        // playerGameStats.FieldGoalsLongestMade +
-        // playerGameStats.FieldGoalsMade +
-        // playerGameStats.FieldGoalsMade0to19 +
-        // playerGameStats.FieldGoalsMade20to29 +
-        // playerGameStats.FieldGoalsMade30to39; //+
-        // playerGameStats.FieldGoalsMade40to49 +
-        // playerGameStats.FieldGoalsMade50Plus +
-        // playerGameStats.FantasyPointsDraftKings;
-        // playerGameStats.fg
-
-        playerScore += missedFG * -2;
-      }
-
-      /**
-       * -1 pt per missed FG (40-49 yds)
-       * (note: a missed FG includes any attempt that is blocked, deflected, etc.)
-       */
-      {
-        // i think there can be different/specific props here outside of interface
-        const missedFG = 0;
-        // playerGameStats.FieldGoalPercentage +
-        // playerGameStats.FieldGoalReturnTouchdowns +
-        // playerGameStats.FieldGoalReturnYards +
-        // playerGameStats.FieldGoalsAttempted +
-        // playerGameStats.FieldGoalsHadBlocked +
-        // playerGameStats.FieldGoalsLongestMade +
-        // playerGameStats.FieldGoalsMade +
-        // playerGameStats.FieldGoalsMade0to19 +
-        // playerGameStats.FieldGoalsMade20to29 +
-        // playerGameStats.FieldGoalsMade30to39 +
-        // playerGameStats.FieldGoalsMade40to49 +
-        // playerGameStats.FieldGoalsMade50Plus +
-        // playerGameStats.FantasyPointsDraftKings;
-
-        playerScore += missedFG * -1;
-      }
-    }
-    /**
-     * Calculation for Kickers (K) ENDS here
-     */
-
-    /**
-     * Defensive/Special Teams (D) STARTS here
-     */
-    let defensivePositions = ['D'];
-    if (defensivePositions.includes(playerGameStats.Position)) {
-      /**
-       * 3 pts per defensive or special teams TD
-       */
-      {
-        const td = playerGameStats.DefensiveTouchdowns + playerGameStats.SpecialTeamsTouchdowns;
-
-        playerScore += td * 3;
-      }
-
-      /**
-       * 2 pts per interception
-       */
-      {
-        const interceptions = playerGameStats.Interceptions;
-
-        playerScore += interceptions * 2;
-      }
-
-      /**
-       * 2 pts per fumble recovery (Note: includes a fumble by the opposing team out of the end zone)
-       */
-      {
-        const fumbleRecovery =
-          playerGameStats.FumblesOwnRecoveries + playerGameStats.FumblesRecovered;
-
-        playerScore += fumbleRecovery * 2;
-      }
-
-      /**
-       * 2 pts per blocked punt, PAT, or FG (Note: a deflected kick of any kind does not receive points)
-       */
-      {
-        const blocked =
-          playerGameStats.BlockedKicks +
-          playerGameStats.FieldGoalsHadBlocked +
-          playerGameStats.PuntsHadBlocked;
-
-        playerScore += blocked * 2;
-      }
-
-      /**
-       * 2 pts per safety
-       */
-      {
-        const safety = playerGameStats.Safeties; //+ playerGameStats.SafetiesAllowed;
-
-        playerScore += safety * 2;
-      }
-
-      /**
-       * 1 pt per sack
-       */
-      {
-        const sack = playerGameStats.Sacks;
-        playerScore += sack * 1;
-      }
-    }
-    /**
-     * Defensive/Special Teams (D) ENDS here
-     */
-    totalScore += playerScore;
 
     const index = playerExternalIds.indexOf(playerId);
     if (index === -1) {
@@ -558,11 +222,8 @@ export async function calculateScore(playerExternalIds: number[], week: number)
       throw new Error(`Player of index ${index} with id ${playerId} not available`);
     }
 
-    if (playerScore < 0) {
-      playerScore = 0;
-    }
-    scoresArr[index] = playerScore ?? 0;
+    statsArr[index] = playerGameStats;
   }
 
-  return { totalScore, scoresArr };
+  return { statsArr };
 }
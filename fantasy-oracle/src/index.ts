@@ -1,5 +1,5 @@
 import { getPlayerList } from './players-list';
-import { calculateScore } from './calculate-score';
+import { fetchPlayerStats } from './calculate-score';
 
 (async () => {
   const players = await getPlayerList();
@@ -1,25 +1,46 @@
 import {
   PublicKey,
   TransactionInstruction,
+  SYSVAR_CLOCK_PUBKEY,
   SYSVAR_RENT_PUBKEY,
   SystemProgram,
 } from '@solana/web3.js';
 
 import * as Layout from './util/layout';
 import { BufferLayout } from './util/layout';
-import { ACTIVE_PLAYERS_COUNT, NUM_POSITIONS, TEAM_NAME_MAX_SYMBOLS } from './state';
+import {
+  NUM_POSITIONS,
+  TEAM_NAME_MAX_SYMBOLS,
+  TEAM_PLAYERS_CAPACITY,
+  FLEX_SLOTS_CAPACITY,
+  CompositionRule,
+  CompositionRuleLayout,
+  DraftType,
+  FlexSlot,
+  FlexSlotLayout,
+  JoinAccess,
+  LeagueType,
+  Sport,
+} from './state';
 import {
   Position,
   MAX_PLAYERS_PER_INSTRUCTION,
+  MAX_SCORES_PER_INSTRUCTION,
   LEAGUE_NAME_MAX_SYMBOLS,
   LEAGUE_USERS_CAPACITY,
+  STATS_COUNT,
 } from './state';
 
+export const SYSVAR_SLOT_HASHES_PUBKEY = new PublicKey(
+  'SysvarS1otHashes111111111111111111111111111'
+);
+
 enum Command {
   Uninitialized,
   AddPlayers,
   InitializeRoot,
-  SeedDraftSelection,
+  /// Tag of the removed SeedDraftSelection, the pick order is drawn per league now
+  Unused,
   StartSeason,
   CreateLeague,
   JoinLeague,
@@ -31,16 +52,80 @@ enum Command {
   UpdatePlayerScore,
   IncrementWeek,
   ClaimReward,
+  CompleteSeason,
+  UpdatePlayerScores,
+  CorrectPlayerScore,
+  SettleWeek,
+  SettlePlayoffRound,
+  SetProtocolFee,
+  WithdrawFees,
+  LeaveLeague,
+  CancelLeague,
+  RefundUnfilledLeague,
+  AutoPick,
+  SetDraftQueue,
+  NominatePlayer,
+  PlaceBid,
+  AddDropPlayer,
+  DrawPickOrder,
 }
 
 export type Player = {
   externalId: number;
   position: Position;
+  /// Draft rank of the player, the auto-pick takes the best ranked one
+  rank: number;
 };
 
 export const PlayerLayout: typeof BufferLayout.Structure = BufferLayout.struct([
   BufferLayout.u16('externalId'),
   BufferLayout.u8('position'),
+  BufferLayout.u16('rank'),
+]);
+
+export type LeagueSettings = {
+  name: string;
+  bid: number | Layout.u64;
+  usersLimit: number;
+  teamName: string;
+  /// Number of lineup slots of every position but `Uninitialized`
+  positions: number[];
+  flexSlots: FlexSlot[];
+  activePlayersCount: number;
+  benchPlayersCount: number;
+  compositionRules: CompositionRule[];
+  /// Points per unit of every `Stat`, in hundredths of a point
+  scoringRules: number[];
+  leagueType: LeagueType;
+  playoffTeamsCount: number;
+  /// Share of the pot for every place in basis points, e.g. 6000, 3000, 1000
+  payouts: number[];
+  /// SPL token mint of the bid, default for native lamports
+  mint: PublicKey;
+  /// Unix timestamp after which an unfilled league can be refunded, zero for none
+  fillDeadline: number;
+  joinAccess: JoinAccess;
+  /// Public key of the password account of a league with `JoinAccess.Password`,
+  /// see `SFS.getPasswordAccount`
+  passwordKey: PublicKey;
+  /// Wallets allowed to join a league with `JoinAccess.AllowList`
+  allowList: PublicKey[];
+  /// Seconds a user has to make a pick before it can be auto-picked, zero for no timer
+  pickTimeLimit: number;
+  draftType: DraftType;
+  auctionBudget: number;
+  auctionWindow: number;
+};
+
+export type PlayerScore = {
+  playerId: number;
+  /// Raw stat line indexed by `Stat`
+  stats: number[];
+};
+
+export const PlayerScoreLayout: typeof BufferLayout.Structure = BufferLayout.struct([
+  BufferLayout.u16('playerId'),
+  BufferLayout.seq(BufferLayout.s16(), STATS_COUNT, 'stats'),
 ]);
 
 export class SfsInstruction {
@@ -50,12 +135,19 @@ export class SfsInstruction {
    * @param programId SFS program account
    * @param root SFS root account
    * @param oracleAuthority Oracle authority
+   * @param currentWeek A current week of season
+   * @param adminAuthority Authority managing the player pool and the season
+   * @param sport Sport of the season
+   * @param scoreCorrectionWeeks Past weeks whose scores the oracle can still correct
    */
   static createInitializeRootInstruction(
     programId: PublicKey,
     root: PublicKey,
     oracleAuthority: PublicKey,
-    currentWeek: number
+    currentWeek: number,
+    adminAuthority: PublicKey,
+    sport: Sport,
+    scoreCorrectionWeeks: number
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
@@ -65,6 +157,9 @@ export class SfsInstruction {
       BufferLayout.u8('instruction'),
       Layout.publicKey('oracleAuthority'),
       BufferLayout.u8('currentWeek'),
+      Layout.publicKey('adminAuthority'),
+      BufferLayout.u8('sport'),
+      BufferLayout.u8('scoreCorrectionWeeks'),
     ]);
     let data = Buffer.alloc(commandDataLayout.span);
     {
@@ -73,6 +168,9 @@ export class SfsInstruction {
           instruction: Command.InitializeRoot,
           oracleAuthority,
           currentWeek,
+          adminAuthority,
+          sport,
+          scoreCorrectionWeeks,
         },
         data
       );
@@ -92,13 +190,18 @@ export class SfsInstruction {
    * @param programId SFS program account
    * @param root SFS root account
    * @param players players
+   * @param admin Admin authority
    */
   static createAddPlayersInstruction(
     programId: PublicKey,
     root: PublicKey,
-    players: Player[]
+    players: Player[],
+    admin: PublicKey
   ): TransactionInstruction {
-    let keys = [{ pubkey: root, isSigner: false, isWritable: true }];
+    let keys = [
+      { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: admin, isSigner: true, isWritable: false },
+    ];
     const commandDataLayout = BufferLayout.struct([
       BufferLayout.u8('instruction'),
       BufferLayout.u8('length'),
@@ -124,28 +227,27 @@ export class SfsInstruction {
   }
 
   /**
-   * Construct an SeedDraftSelection instruction
+   * Construct a StartSeason instruction
    *
    * @param programId SFS program account
    * @param root SFS root account
-   * @param pickOrder pick order seed
+   * @param admin Admin authority
    */
-  static createSeedDraftSelectionInstruction(
+  static createStartSeasonInstruction(
     programId: PublicKey,
     root: PublicKey,
-    pickOrder: number[]
+    admin: PublicKey
   ): TransactionInstruction {
-    let keys = [{ pubkey: root, isSigner: false, isWritable: true }];
-    const commandDataLayout = BufferLayout.struct([
-      BufferLayout.u8('instruction'),
-      BufferLayout.seq(BufferLayout.u8(), LEAGUE_USERS_CAPACITY, 'pickOrder'),
-    ]);
+    let keys = [
+      { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: admin, isSigner: true, isWritable: false },
+    ];
+    const commandDataLayout = BufferLayout.struct([BufferLayout.u8('instruction')]);
     let data = Buffer.alloc(commandDataLayout.span);
     {
       const encodeLength = commandDataLayout.encode(
         {
-          instruction: Command.SeedDraftSelection,
-          pickOrder,
+          instruction: Command.StartSeason,
         },
         data
       );
@@ -160,23 +262,26 @@ export class SfsInstruction {
 
   /**
    * Construct an CreateLeague instruction
+   *
+   * @param league League account derived from the root and the index of the new league
+   * @param vault League vault derived from the root and the index of the new league
    */
   static createCreateLeagueInstruction(
     programId: PublicKey,
     root: PublicKey,
-    bank: PublicKey,
-    name: string,
-    bid: number | Layout.u64,
-    usersLimit: number,
-    teamName: string,
-    owner: PublicKey,
-    positions: number[]
+    league: PublicKey,
+    vault: PublicKey,
+    settings: LeagueSettings,
+    owner: PublicKey
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: league, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: true },
-      { pubkey: bank, isSigner: false, isWritable: true },
+      { pubkey: vault, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([
       BufferLayout.u8('instruction'),
@@ -185,6 +290,23 @@ export class SfsInstruction {
       BufferLayout.u8('usersLimit'),
       Layout.utf16FixedString(TEAM_NAME_MAX_SYMBOLS, 'teamName'),
       BufferLayout.seq(BufferLayout.u8(), NUM_POSITIONS - 1, 'positions'),
+      BufferLayout.seq(FlexSlotLayout, FLEX_SLOTS_CAPACITY, 'flexSlots'),
+      BufferLayout.u8('activePlayersCount'),
+      BufferLayout.u8('benchPlayersCount'),
+      BufferLayout.seq(CompositionRuleLayout, NUM_POSITIONS - 1, 'compositionRules'),
+      BufferLayout.seq(BufferLayout.s16(), STATS_COUNT, 'scoringRules'),
+      BufferLayout.u8('leagueType'),
+      BufferLayout.u8('playoffTeamsCount'),
+      BufferLayout.seq(BufferLayout.u16(), LEAGUE_USERS_CAPACITY, 'payouts'),
+      Layout.publicKey('mint'),
+      BufferLayout.ns64('fillDeadline'),
+      BufferLayout.u8('joinAccess'),
+      Layout.publicKey('passwordKey'),
+      BufferLayout.seq(Layout.publicKey(), LEAGUE_USERS_CAPACITY, 'allowList'),
+      BufferLayout.u32('pickTimeLimit'),
+      BufferLayout.u8('draftType'),
+      BufferLayout.u32('auctionBudget'),
+      BufferLayout.u32('auctionWindow'),
     ]);
 
     let data = Buffer.alloc(commandDataLayout.span);
@@ -192,11 +314,7 @@ export class SfsInstruction {
       const encodeLength = commandDataLayout.encode(
         {
           instruction: Command.CreateLeague,
-          name,
-          bid,
-          usersLimit,
-          teamName,
-          positions,
+          ...settings,
         },
         data
       );
@@ -211,21 +329,31 @@ export class SfsInstruction {
 
   /**
    * Construct an JoinLeague instruction
+   *
+   * @param passwordKey Password account of a league with `JoinAccess.Password`, it signs the
+   * transaction too
    */
   static createJoinLeagueInstruction(
     programId: PublicKey,
     root: PublicKey,
-    bank: PublicKey,
+    league: PublicKey,
+    vault: PublicKey,
     leagueIndex: number,
     teamName: string,
-    owner: PublicKey
+    owner: PublicKey,
+    passwordKey?: PublicKey
   ): TransactionInstruction {
     let keys = [
-      { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: root, isSigner: false, isWritable: false },
+      { pubkey: league, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: true },
-      { pubkey: bank, isSigner: false, isWritable: true },
+      { pubkey: vault, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
     ];
+    if (passwordKey) {
+      keys.push({ pubkey: passwordKey, isSigner: true, isWritable: false });
+    }
     const commandDataLayout = BufferLayout.struct([
       BufferLayout.u8('instruction'),
       BufferLayout.u16('leagueIndex'),
@@ -251,12 +379,51 @@ export class SfsInstruction {
     });
   }
 
+  /**
+   * Construct a DrawPickOrder instruction
+   */
+  static createDrawPickOrderInstruction(
+    programId: PublicKey,
+    root: PublicKey,
+    league: PublicKey,
+    leagueIndex: number
+  ): TransactionInstruction {
+    let keys = [
+      { pubkey: root, isSigner: false, isWritable: false },
+      { pubkey: league, isSigner: false, isWritable: true },
+      { pubkey: SYSVAR_SLOT_HASHES_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+    ];
+    const commandDataLayout = BufferLayout.struct([
+      BufferLayout.u8('instruction'),
+      BufferLayout.u16('leagueIndex'),
+    ]);
+
+    let data = Buffer.alloc(commandDataLayout.span);
+    {
+      const encodeLength = commandDataLayout.encode(
+        {
+          instruction: Command.DrawPickOrder,
+          leagueIndex,
+        },
+        data
+      );
+    }
+
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+
   /**
    * Construct an PickPlayer instruction
    */
   static createPickPlayerInstruction(
     programId: PublicKey,
     root: PublicKey,
+    league: PublicKey,
     leagueIndex: number,
     userId: number,
     playerId: number,
@@ -264,7 +431,9 @@ export class SfsInstruction {
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: league, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([
       BufferLayout.u8('instruction'),
@@ -294,25 +463,24 @@ export class SfsInstruction {
   }
   /**
    * Construct an UpdatePlayerScore instruction
+   *
+   * @param stats Raw stat line of the current week indexed by `Stat`
    */
   static createUpdatePlayerScoreInstruction(
     programId: PublicKey,
     root: PublicKey,
-    bank: PublicKey,
     playerId: number,
-    playerScore: number,
+    stats: number[],
     owner: PublicKey
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: false },
-      { pubkey: bank, isSigner: false, isWritable: true },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([
       BufferLayout.u8('instruction'),
       BufferLayout.u16('playerId'),
-      BufferLayout.u16('playerScore'),
+      BufferLayout.seq(BufferLayout.s16(), STATS_COUNT, 'stats'),
     ]);
 
     let data = Buffer.alloc(commandDataLayout.span);
@@ -321,7 +489,7 @@ export class SfsInstruction {
         {
           instruction: Command.UpdatePlayerScore,
           playerId,
-          playerScore,
+          stats,
         },
         data
       );
@@ -334,19 +502,55 @@ export class SfsInstruction {
     });
   }
   /**
-   * Construct an IncrementWeek instruction
+   * Construct an UpdatePlayerScores instruction
+   *
+   * @param scores Up to `MAX_SCORES_PER_INSTRUCTION` raw stat lines of the current week
    */
-  static createIncrementWeekInstruction(
+  static createUpdatePlayerScoresInstruction(
     programId: PublicKey,
     root: PublicKey,
-    bank: PublicKey,
+    scores: PlayerScore[],
     owner: PublicKey
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: false },
-      { pubkey: bank, isSigner: false, isWritable: true },
-      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ];
+    const commandDataLayout = BufferLayout.struct([
+      BufferLayout.u8('instruction'),
+      BufferLayout.u8('length'),
+      BufferLayout.seq(PlayerScoreLayout, MAX_SCORES_PER_INSTRUCTION, 'scores'),
+    ]);
+
+    let data = Buffer.alloc(commandDataLayout.span);
+    {
+      const encodeLength = commandDataLayout.encode(
+        {
+          instruction: Command.UpdatePlayerScores,
+          length: scores.length,
+          scores,
+        },
+        data
+      );
+    }
+
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+  /**
+   * Construct an IncrementWeek instruction
+   */
+  static createIncrementWeekInstruction(
+    programId: PublicKey,
+    root: PublicKey,
+    admin: PublicKey
+  ): TransactionInstruction {
+    let keys = [
+      { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: admin, isSigner: true, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([BufferLayout.u8('instruction')]);
 
@@ -372,6 +576,7 @@ export class SfsInstruction {
   static createUpdateLineupInstruction(
     programId: PublicKey,
     root: PublicKey,
+    league: PublicKey,
     leagueIndex: number,
     userId: number,
     week: number,
@@ -380,14 +585,15 @@ export class SfsInstruction {
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: league, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([
       BufferLayout.u8('instruction'),
-      BufferLayout.seq(BufferLayout.u16(), ACTIVE_PLAYERS_COUNT, 'activePlayers'),
+      BufferLayout.seq(BufferLayout.u16(), TEAM_PLAYERS_CAPACITY, 'activePlayers'),
       BufferLayout.u16('leagueIndex'),
       BufferLayout.u8('userId'),
-      BufferLayout.u16('week'),
+      BufferLayout.u8('week'),
     ]);
 
     let data = Buffer.alloc(commandDataLayout.span);
@@ -416,6 +622,7 @@ export class SfsInstruction {
   static createProposeSwapInstruction(
     programId: PublicKey,
     root: PublicKey,
+    league: PublicKey,
     leagueIndex: number,
     proposingUserId: number,
     acceptingUserId: number,
@@ -425,6 +632,7 @@ export class SfsInstruction {
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: league, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([
@@ -463,6 +671,7 @@ export class SfsInstruction {
   static createAcceptSwapInstruction(
     programId: PublicKey,
     root: PublicKey,
+    league: PublicKey,
     leagueIndex: number,
     acceptingUserId: number,
     proposingUserId: number,
@@ -472,6 +681,7 @@ export class SfsInstruction {
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: league, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([
@@ -510,6 +720,7 @@ export class SfsInstruction {
   static createRejectSwapInstruction(
     programId: PublicKey,
     root: PublicKey,
+    league: PublicKey,
     leagueIndex: number,
     acceptingUserId: number,
     proposingUserId: number,
@@ -519,6 +730,7 @@ export class SfsInstruction {
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: league, isSigner: false, isWritable: true },
       { pubkey: owner, isSigner: true, isWritable: false },
     ];
     const commandDataLayout = BufferLayout.struct([
@@ -553,16 +765,22 @@ export class SfsInstruction {
   }
   /**
    * Construct an ClaimReward instruction
+   *
+   * @param winners The paid users from the first place down
    */
   static createClaimRewardInstruction(
     programId: PublicKey,
     root: PublicKey,
+    league: PublicKey,
+    vault: PublicKey,
     bank: PublicKey,
     leagueIndex: number,
     winners: PublicKey[]
   ): TransactionInstruction {
     let keys = [
       { pubkey: root, isSigner: false, isWritable: true },
+      { pubkey: league, isSigner: false, isWritable: true },
+      { pubkey: vault, isSigner: false, isWritable: true },
       { pubkey: bank, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ...winners.map((x) => ({ pubkey: x, isSigner: false, isWritable: true })),
@@ -1,20 +1,25 @@
 import { Account, PublicKey, SystemProgram, Transaction } from '@solana/web3.js';
+import nacl from 'tweetnacl';
 import type { Connection } from '@solana/web3.js';
 
 import { sendAndConfirmTransaction } from './util/send-and-confirm-transaction';
 import {
   MAX_PLAYERS_PER_INSTRUCTION,
-  Player,
   Root,
   RootLayout,
-  LEAGUE_USERS_CAPACITY,
-  GAMES_COUNT,
   League,
-  ACTIVE_PLAYERS_COUNT,
-  MAX_PLAYERS_SCORES_PER_TRANSACTION,
+  LeagueLayout,
+  MAX_SCORES_PER_INSTRUCTION,
+  Score,
+  Sport,
+  VAULT_SEED,
 } from './state';
-import { SfsInstruction, Player as PlayerInit } from './instruction';
-import { u64 } from './util/layout';
+import {
+  SfsInstruction,
+  Player as PlayerInit,
+  PlayerScore,
+  LeagueSettings,
+} from './instruction';
 
 // The address of the special mint for wrapped native token.
 export const NATIVE_MINT: PublicKey = new PublicKey('So11111111111111111111111111111111111111112');
@@ -56,15 +61,76 @@ export class SFS {
     return await connection.getMinimumBalanceForRentExemption(RootLayout.span);
   }
 
+  /**
+   * Derive the address of a league account from the root and the league index
+   *
+   * @param programId SFS programId
+   * @param root Public key of the root account
+   * @param leagueIndex Index of league
+   */
+  static async findLeagueAddress(
+    programId: PublicKey,
+    root: PublicKey,
+    leagueIndex: number
+  ): Promise<PublicKey> {
+    const [address] = await PublicKey.findProgramAddress(
+      [root.toBuffer(), SFS.encodeLeagueIndex(leagueIndex)],
+      programId
+    );
+    return address;
+  }
+
+  /**
+   * Derive the address of the escrow vault of a league from the root and the league index
+   *
+   * @param programId SFS programId
+   * @param root Public key of the root account
+   * @param leagueIndex Index of league
+   */
+  static async findVaultAddress(
+    programId: PublicKey,
+    root: PublicKey,
+    leagueIndex: number
+  ): Promise<PublicKey> {
+    const [address] = await PublicKey.findProgramAddress(
+      [Buffer.from(VAULT_SEED), root.toBuffer(), SFS.encodeLeagueIndex(leagueIndex)],
+      programId
+    );
+    return address;
+  }
+
+  private static encodeLeagueIndex(leagueIndex: number): Buffer {
+    const buffer = Buffer.alloc(2);
+    buffer.writeUInt16LE(leagueIndex, 0);
+    return buffer;
+  }
+
+  /**
+   * Derives the account a password league is joined with from the league address and the
+   * password. Its public key is the `passwordKey` of the league and it signs every join,
+   * so the password itself never goes on chain.
+   *
+   * @param league Address of a league with `JoinAccess.Password`
+   * @param password Password of the league
+   */
+  static getPasswordAccount(league: PublicKey, password: string): Account {
+    const seed = nacl
+      .hash(Buffer.concat([league.toBuffer(), Buffer.from(password, 'utf8')]))
+      .slice(0, nacl.sign.seedLength);
+    return new Account(nacl.sign.keyPair.fromSeed(seed).secretKey);
+  }
+
   /**
    * Create and initialize a root state account.
    *
    * @param connection The connection to use
-   * @param payer Fee payer for transaction
+   * @param payer Fee payer for transaction, becomes the admin authority
    * @param oracleAuthority Account that will control scores
-   * @param players An array of player external ids and their positions
+   * @param players An array of player external ids, their positions and ranks
    * @param currentWeek A current week of season. 0 - week before season starts
    * @param programId Id of deployed SFS program
+   * @param sport Sport of the season
+   * @param scoreCorrectionWeeks Past weeks whose scores the oracle can still correct
    * @return SFS object for the newly created root
    */
   static async initializeRoot(
@@ -73,7 +139,9 @@ export class SFS {
     oracleAuthority: PublicKey,
     players: PlayerInit[],
     currentWeek: number,
-    programId: PublicKey
+    programId: PublicKey,
+    sport: Sport = Sport.Football,
+    scoreCorrectionWeeks: number = 0
   ): Promise<SFS> {
     const rootAccount = new Account();
     const [bank, _] = await PublicKey.findProgramAddress([Buffer.from([0])], programId);
@@ -82,17 +150,32 @@ export class SFS {
     // Allocate memory for the account
     const balanceNeeded = await SFS.getMinBalanceRentForExemptRoot(connection);
 
-    let transaction = new Transaction().add(
-      SystemProgram.createAccount({
-        fromPubkey: payer.publicKey,
-        newAccountPubkey: rootAccount.publicKey,
-        lamports: balanceNeeded,
-        space: RootLayout.span,
-        programId,
-      })
-    );
+    // The root is initialized in the same transaction that creates it,
+    // otherwise another party could initialize it first
+    let transaction = new Transaction()
+      .add(
+        SystemProgram.createAccount({
+          fromPubkey: payer.publicKey,
+          newAccountPubkey: rootAccount.publicKey,
+          lamports: balanceNeeded,
+          space: RootLayout.span,
+          programId,
+        })
+      )
+      .add(
+        SfsInstruction.createInitializeRootInstruction(
+          programId,
+          rootAccount.publicKey,
+          oracleAuthority,
+          currentWeek,
+          payer.publicKey,
+          sport,
+          scoreCorrectionWeeks
+        )
+      );
 
-    await sendAndConfirmTransaction('Create account', connection, transaction, payer, rootAccount);
+    console.log('Initialize root');
+    await sendAndConfirmTransaction('Initialize root', connection, transaction, payer, rootAccount);
 
     for (let i = 0; i < players.length / MAX_PLAYERS_PER_INSTRUCTION; i++) {
       // console.log(`Add players ${i * MAX_PLAYERS_PER_INSTRUCTION}-${(i+1) * MAX_PLAYERS_PER_INSTRUCTION} of ${players.length}`);
@@ -106,7 +189,8 @@ export class SFS {
         SfsInstruction.createAddPlayersInstruction(
           programId,
           rootAccount.publicKey,
-          players.slice(i * MAX_PLAYERS_PER_INSTRUCTION, (i + 1) * MAX_PLAYERS_PER_INSTRUCTION)
+          players.slice(i * MAX_PLAYERS_PER_INSTRUCTION, (i + 1) * MAX_PLAYERS_PER_INSTRUCTION),
+          payer.publicKey
         )
       );
 
@@ -120,80 +204,52 @@ export class SFS {
       );
     }
 
-    const pickOrder = Array.from({ length: LEAGUE_USERS_CAPACITY })
-      .map((_, i) => i + 1)
-      .sort(() => 0.5 - Math.random());
-
     transaction = new Transaction().add(
-      SfsInstruction.createSeedDraftSelectionInstruction(
-        programId,
-        rootAccount.publicKey,
-        pickOrder
-      )
-    );
-    console.log('Seed draft selection');
-    await sendAndConfirmTransaction('Seed draft selection', connection, transaction, payer);
-
-    transaction = new Transaction().add(
-      SfsInstruction.createInitializeRootInstruction(
-        programId,
-        rootAccount.publicKey,
-        oracleAuthority,
-        currentWeek
-      )
+      SfsInstruction.createStartSeasonInstruction(programId, rootAccount.publicKey, payer.publicKey)
     );
 
-    console.log('Initialize root');
-    await sendAndConfirmTransaction('Initialize root', connection, transaction, payer);
+    console.log('Start season');
+    await sendAndConfirmTransaction('Start season', connection, transaction, payer);
 
     return sfs;
   }
 
   /**
-   * Create a new league.
+   * Create a new league in its own account.
    *
-   * @param owner User account to pay the fee, will be the first user joined
-   * @param name A name for the new league
-   * @param bid A bid amount in lamports
-   * @param usersLimit Number of users in league
-   * @param teamName A name of current user's team
+   * @param owner User account to pay the bid and the league rent, will be the first user joined
+   * @param settings Settings of the new league
+   * @param password Password of a league with `JoinAccess.Password`, sets its `passwordKey`
    * @return Index of the created league
    */
   async createLeague(
     owner: Account,
-    name: string,
-    bid: number | u64,
-    usersLimit: number,
-    teamName: string,
-    positions: number[]
+    settings: LeagueSettings,
+    password?: string
   ): Promise<number> {
+    const root = await this.getRootInfo();
+    const leagueIndex = root.leaguesCount;
+    const league = await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex);
+    if (password !== undefined) {
+      settings = {
+        ...settings,
+        passwordKey: SFS.getPasswordAccount(league, password).publicKey,
+      };
+    }
     const transaction = new Transaction();
     transaction.add(
       SfsInstruction.createCreateLeagueInstruction(
         this.programId,
         this.publicKey,
-        this.bank,
-        name,
-        bid,
-        usersLimit,
-        teamName,
-        owner.publicKey,
-        positions
+        league,
+        await SFS.findVaultAddress(this.programId, this.publicKey, leagueIndex),
+        settings,
+        owner.publicKey
       )
     );
-    const rootInfo = await this.connection.getAccountInfo(this.publicKey);
-    if (rootInfo === null) {
-      throw new Error('Failed to find root account');
-    }
     await sendAndConfirmTransaction('Create league', this.connection, transaction, owner);
 
-    const root = await this.getRootInfo();
-    for (let i = root.leaguesCount; i >= 0; i--) {
-      if (root.leagues[i].userStates.some((x) => x.pubKey.equals(owner.publicKey))) {
-        return i;
-      }
-    }
-    throw new Error('Created league is not found');
+    return leagueIndex;
   }
 
   /**
@@ -202,22 +258,53 @@ export class SFS {
    * @param owner User account that will join the league and pay the bid
    * @param leagueIndex Index of league to join
    * @param teamName A name of current user's team
-   * @return Public key of the new empty account
+   * @param password Password of a league with `JoinAccess.Password`
    */
-  async joinLeague(owner: Account, leagueIndex: number, teamName: string): Promise<void> {
+  async joinLeague(
+    owner: Account,
+    leagueIndex: number,
+    teamName: string,
+    password?: string
+  ): Promise<void> {
+    const league = await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex);
+    const passwordAccount =
+      password !== undefined ? SFS.getPasswordAccount(league, password) : undefined;
     const transaction = new Transaction();
     transaction.add(
       SfsInstruction.createJoinLeagueInstruction(
         this.programId,
         this.publicKey,
-        this.bank,
+        league,
+        await SFS.findVaultAddress(this.programId, this.publicKey, leagueIndex),
         leagueIndex,
         teamName,
-        owner.publicKey
+        owner.publicKey,
+        passwordAccount?.publicKey
       )
     );
 
-    await sendAndConfirmTransaction('Join league', this.connection, transaction, owner);
+    const signers = passwordAccount ? [owner, passwordAccount] : [owner];
+    await sendAndConfirmTransaction('Join league', this.connection, transaction, ...signers);
+  }
+
+  /**
+   * Draw the pick order of a full league, anyone can send it.
+   *
+   * @param leagueIndex Index of a full league
+   * @param sender Account to pay the transaction fee
+   */
+  async drawPickOrder(leagueIndex: number, sender: Account): Promise<void> {
+    const transaction = new Transaction();
+    transaction.add(
+      SfsInstruction.createDrawPickOrderInstruction(
+        this.programId,
+        this.publicKey,
+        await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex),
+        leagueIndex
+      )
+    );
+
+    await sendAndConfirmTransaction('Draw pick order', this.connection, transaction, sender);
   }
 
   /**
@@ -239,6 +326,7 @@ export class SFS {
       SfsInstruction.createPickPlayerInstruction(
         this.programId,
         this.publicKey,
+        await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex),
         leagueIndex,
         userId,
         playerId,
@@ -269,6 +357,7 @@ export class SFS {
       SfsInstruction.createUpdateLineupInstruction(
         this.programId,
         this.publicKey,
+        await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex),
         leagueIndex,
         userId,
         week,
@@ -303,6 +392,7 @@ export class SFS {
       SfsInstruction.createProposeSwapInstruction(
         this.programId,
         this.publicKey,
+        await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex),
         leagueIndex,
         proposingUserId,
         acceptingUserId,
@@ -337,6 +427,7 @@ export class SFS {
       SfsInstruction.createAcceptSwapInstruction(
         this.programId,
         this.publicKey,
+        await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex),
         leagueIndex,
         acceptingUserId,
         proposingUserId,
@@ -371,6 +462,7 @@ export class SFS {
       SfsInstruction.createRejectSwapInstruction(
         this.programId,
         this.publicKey,
+        await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex),
         leagueIndex,
         acceptingUserId,
         proposingUserId,
@@ -406,48 +498,89 @@ export class SFS {
     return rootInfo;
   }
 
+  /**
+   * Retrieve latest state information of a league
+   *
+   * @param leagueIndex Index of league
+   * @returns League state
+   */
+  async getLeagueInfo(leagueIndex: number): Promise<League> {
+    const address = await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex);
+    const info = await this.connection.getAccountInfo(address);
+    if (info === null) {
+      throw new Error('Failed to find league account');
+    }
+    if (!info.owner.equals(this.programId)) {
+      throw new Error(`Invalid league owner: ${JSON.stringify(info.owner)}`);
+    }
+    if (info.data.length != LeagueLayout.span) {
+      throw new Error(`Invalid league size`);
+    }
+
+    return LeagueLayout.decode(Buffer.from(info.data));
+  }
+
+  /**
+   * Calculates fantasy points of a stat line the same way the program does
+   *
+   * @param score Score of a player for a week
+   * @param scoringRules Points per unit of every `Stat` of the league, in hundredths of a point
+   * @returns fantasy points in hundredths of a point
+   */
+  static getPoints(score: Score, scoringRules: number[]) {
+    return score.stats.reduce((sum, stat, i) => sum + stat * scoringRules[i], 0);
+  }
+
   /**
    * Calculates scores for the certain user for the certain week
    *
    * @param root Root state
-   * @param leagueIndex Index of league
+   * @param league League state
    * @param userId A 1-based id of user in league
    * @param week Week to calculate the scores
    * @returns score
    */
-  static getWeekScores(root: Root, leagueIndex: number, userId: number, week: number) {
-    let league = root.leagues[leagueIndex];
-    let score = league.userStates[userId - 1].lineups[week - 1]?.reduce((sum, playerId) => {
-      return sum + root.players[playerId - 1].scores[week - 1].score1;
-    }, 0);
-    return Math.round(score ?? 0);
+  static getWeekScores(root: Root, league: League, userId: number, week: number) {
+    let score = league.userStates[userId - 1].lineups[week - 1]
+      ?.filter((playerId) => playerId !== 0)
+      .reduce((sum, playerId) => {
+        return (
+          sum + SFS.getPoints(root.players[playerId - 1].scores[week - 1], league.scoringRules)
+        );
+      }, 0);
+    return (score ?? 0) / 100;
   }
 
   /**
    * Calculates scores for each league user for all weeks
    *
    * @param root Root state
-   * @param leagueIndex Index of league
+   * @param league League state
    * @returns array of users and their scores and states
    */
-  static getUserScores(root: Root, leagueIndex: number) {
-    let league = root.leagues[leagueIndex];
+  static getUserScores(root: Root, league: League) {
     return league.userStates.slice(0, league.userStateCount).map((userState, i) => {
       const score = userState.lineups.slice(league.startWeek - 1).reduce(
         (sum, lineup, lineupIndex) =>
           sum +
-          lineup.reduce((sum2, playerId) => {
-            return (
-              sum2 + root.players[playerId - 1].scores[lineupIndex + league.startWeek - 1].score1
-            );
-          }, 0),
+          lineup
+            .filter((playerId) => playerId !== 0)
+            .reduce((sum2, playerId) => {
+              return (
+                sum2 +
+                SFS.getPoints(
+                  root.players[playerId - 1].scores[lineupIndex + league.startWeek - 1],
+                  league.scoringRules
+                )
+              );
+            }, 0),
         0
       );
 
       return {
         userId: i + 1,
         userState,
-        score: Math.round(score),
+        score: score / 100,
       };
     });
   }
@@ -456,10 +589,10 @@ export class SFS {
    * Returns list of winners for the league
    *
    * @param root
-   * @param leagueIndex
+   * @param league
    */
-  static getWinners(root: Root, leagueIndex: number) {
-    const scores = SFS.getUserScores(root, leagueIndex);
+  static getWinners(root: Root, league: League) {
+    const scores = SFS.getUserScores(root, league);
     const maxScore = scores.reduce((max, x) => (x.score > max ? x.score : max), 0);
     return scores.filter((x) => x.score === maxScore);
   }
@@ -468,38 +601,29 @@ export class SFS {
    * Update player scores for current week
    *
    * @param owner An oracle's account
-   * @param scores Array of players and their scores
+   * @param scores Array of players and their raw stat lines
    */
-  async updatePlayerScores(
-    owner: Account,
-    scores: { playerId: number; playerScore: number }[]
-  ): Promise<void> {
-    for (let i = 0; i < scores.length / MAX_PLAYERS_SCORES_PER_TRANSACTION; i++) {
+  async updatePlayerScores(owner: Account, scores: PlayerScore[]): Promise<void> {
+    for (let i = 0; i < scores.length / MAX_SCORES_PER_INSTRUCTION; i++) {
       console.log(
-        `Uploading scores ${i * MAX_PLAYERS_SCORES_PER_TRANSACTION} of ${Math.min(
+        `Uploading scores ${i * MAX_SCORES_PER_INSTRUCTION} of ${Math.min(
           scores.length - 1,
-          (i + 1) * MAX_PLAYERS_SCORES_PER_TRANSACTION
+          (i + 1) * MAX_SCORES_PER_INSTRUCTION
         )}`
       );
       const transaction = new Transaction();
-      scores
-        .slice(i * MAX_PLAYERS_SCORES_PER_TRANSACTION, (i + 1) * MAX_PLAYERS_SCORES_PER_TRANSACTION)
-        .forEach((x) => {
-          transaction.add(
-            SfsInstruction.createUpdatePlayerScoreInstruction(
-              this.programId,
-              this.publicKey,
-              this.bank,
-              x.playerId,
-              x.playerScore,
-              owner.publicKey
-            )
-          );
-        });
+      transaction.add(
+        SfsInstruction.createUpdatePlayerScoresInstruction(
+          this.programId,
+          this.publicKey,
+          scores.slice(i * MAX_SCORES_PER_INSTRUCTION, (i + 1) * MAX_SCORES_PER_INSTRUCTION),
+          owner.publicKey
+        )
+      );
       await sendAndConfirmTransaction(
-        `Uploading scores ${i * MAX_PLAYERS_SCORES_PER_TRANSACTION} of ${Math.min(
+        `Uploading scores ${i * MAX_SCORES_PER_INSTRUCTION} of ${Math.min(
           scores.length - 1,
-          (i + 1) * MAX_PLAYERS_SCORES_PER_TRANSACTION
+          (i + 1) * MAX_SCORES_PER_INSTRUCTION
         )}`,
         this.connection,
         transaction,
@@ -511,17 +635,12 @@ export class SFS {
   /**
    * Increment current week number
    *
-   * @param owner An oracle's account
+   * @param owner The admin's account
    */
   async incrementWeek(owner: Account): Promise<void> {
     const transaction = new Transaction();
     transaction.add(
-      SfsInstruction.createIncrementWeekInstruction(
-        this.programId,
-        this.publicKey,
-        this.bank,
-        owner.publicKey
-      )
+      SfsInstruction.createIncrementWeekInstruction(this.programId, this.publicKey, owner.publicKey)
     );
 
     await sendAndConfirmTransaction('Increment week', this.connection, transaction, owner);
@@ -531,7 +650,7 @@ export class SFS {
    * Withdraw the reward to the winners.
    *
    * @param leagueIndex Index of league
-   * @param winners Array of the winners
+   * @param winners The paid users from the first place down
    * @param sender Account to pay the transaction fee
    */
   async claimReward(leagueIndex: number, winners: PublicKey[], sender: Account): Promise<void> {
@@ -540,6 +659,8 @@ export class SFS {
       SfsInstruction.createClaimRewardInstruction(
         this.programId,
         this.publicKey,
+        await SFS.findLeagueAddress(this.programId, this.publicKey, leagueIndex),
+        await SFS.findVaultAddress(this.programId, this.publicKey, leagueIndex),
         this.bank,
         leagueIndex,
         winners
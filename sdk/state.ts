@@ -3,10 +3,12 @@ import { PublicKey } from '@solana/web3.js';
 
 import * as Layout from './util/layout';
 
-export const MAX_PLAYERS_SCORES_PER_TRANSACTION = 50;
+export const MAX_SCORES_PER_INSTRUCTION = 20;
 export const MAX_PLAYERS_PER_INSTRUCTION = 255;
 export const PLAYERS_CAPACITY = 100;
 export const GAMES_COUNT = 17;
+export const GAMES_CAPACITY = 24; //Longest season of all sports
+export const STATS_COUNT = 20; //Number of Stat variants
 
 export const ACTIVE_PLAYERS_COUNT = 3;
 export const BENCH_PLAYERS_COUNT = 3;
@@ -15,9 +17,10 @@ export const TEAM_PLAYERS_COUNT = ACTIVE_PLAYERS_COUNT + BENCH_PLAYERS_COUNT;
 export const TEAM_PLAYERS_CAPACITY = 10; //10 is arbitrary. This should be used instead of TEAM_PLAYERS_COUNT since active/bench players # will vary between leagues
 export const NUM_POSITIONS = 12; //Number of possible positions including unitialized
 
-export const LEAGUES_CAPACITY = 10;
+export const FLEX_SLOTS_CAPACITY = 2;
 export const LEAGUE_USERS_CAPACITY = Math.floor(PLAYERS_CAPACITY / TEAM_PLAYERS_CAPACITY);
 export const SWAP_PROPOSALS_CAPACITY = 20;
+export const PLAYOFF_TEAMS_CAPACITY = 8;
 
 export const LEAGUE_NAME_LEN = 256;
 export const LEAGUE_NAME_MAX_SYMBOLS = 128;
@@ -27,6 +30,8 @@ export const TEAM_NAME_MAX_SYMBOLS = 128;
 
 export const PUB_KEY_LEN = 32;
 
+export const VAULT_SEED = 'vault';
+
 export enum Position {
   Uninitialized,
   RB,
@@ -53,12 +58,85 @@ export enum Position {
   C,
 }
 
+export enum Sport {
+  Uninitialized,
+  Football,
+  Basketball,
+}
+
+/// Raw stat categories reported by the oracle for a player's week.
+export enum Stat {
+  PassingYards,
+  PassingTouchdowns,
+  Interceptions,
+  RushingYards,
+  RushingTouchdowns,
+  Receptions,
+  ReceivingYards,
+  ReceivingTouchdowns,
+  FumblesLost,
+  FieldGoals,
+  ExtraPoints,
+  Sacks,
+  DefensiveInterceptions,
+  DefensiveTouchdowns,
+
+  Points,
+  Rebounds,
+  Assists,
+  Steals,
+  Blocks,
+  Turnovers,
+}
+
+/// Football scoring table in hundredths of a point per unit of every `Stat`,
+/// `receptionWeight` is 0 for standard, 50 for half-PPR and 100 for PPR leagues
+export function footballScoringRules(receptionWeight: number): number[] {
+  const weights: number[] = Array(STATS_COUNT).fill(0);
+  weights[Stat.PassingYards] = 4;
+  weights[Stat.PassingTouchdowns] = 400;
+  weights[Stat.Interceptions] = -200;
+  weights[Stat.RushingYards] = 10;
+  weights[Stat.RushingTouchdowns] = 600;
+  weights[Stat.Receptions] = receptionWeight;
+  weights[Stat.ReceivingYards] = 10;
+  weights[Stat.ReceivingTouchdowns] = 600;
+  weights[Stat.FumblesLost] = -200;
+  weights[Stat.FieldGoals] = 300;
+  weights[Stat.ExtraPoints] = 100;
+  weights[Stat.Sacks] = 100;
+  weights[Stat.DefensiveInterceptions] = 200;
+  weights[Stat.DefensiveTouchdowns] = 600;
+  return weights;
+}
+
 export enum Stage {
   Uninitialized,
+  /// Root is initialized, players are being seeded
+  DraftSelection,
   SeasonOpen,
   SeasonComplete,
 }
 
+/// How the winner of a league is decided.
+export enum LeagueType {
+  TotalPoints,
+  HeadToHead,
+}
+
+/// Who can join a league.
+export enum JoinAccess {
+  Public,
+  Password,
+  AllowList,
+}
+
+/// How the users of a league draft their teams.
+export enum DraftType {
+  Snake,
+  Auction,
+}
+
 export type SwapProposal = {
   givePlayerId: number;
   wantPlayerId: number;
@@ -80,13 +158,20 @@ export type UserState = {
   pubKey: PublicKey;
   isLineupSet: boolean;
   isInitialized: boolean;
+  wins: number;
+  losses: number;
+  ties: number;
+  /// Points scored in settled head-to-head weeks, in hundredths of a point
+  pointsFor: number;
+  /// Budget left to bid with in an auction draft
+  budget: number;
 };
 
 export const UserStateLayout: typeof BufferLayout.Structure = BufferLayout.struct([
-  BufferLayout.seq(BufferLayout.u16(), TEAM_PLAYERS_COUNT, 'userPlayers'),
+  BufferLayout.seq(BufferLayout.u16(), TEAM_PLAYERS_CAPACITY, 'userPlayers'),
   BufferLayout.seq(
-    BufferLayout.seq(BufferLayout.u16(), ACTIVE_PLAYERS_COUNT),
-    GAMES_COUNT,
+    BufferLayout.seq(BufferLayout.u16(), TEAM_PLAYERS_CAPACITY),
+    GAMES_CAPACITY,
     'lineups'
   ),
   BufferLayout.u8('swapProposalsCount'),
@@ -95,6 +180,60 @@ export const UserStateLayout: typeof BufferLayout.Structure = BufferLayout.struc
   Layout.publicKey('pubKey'),
   Layout.boolean('isLineupSet'),
   Layout.boolean('isInitialized'),
+  BufferLayout.u8('wins'),
+  BufferLayout.u8('losses'),
+  BufferLayout.u8('ties'),
+  BufferLayout.ns64('pointsFor'),
+  BufferLayout.u32('budget'),
+]);
+
+export type Bracket = {
+  /// Number of teams advancing to the playoff, 0 when the league has no playoff
+  teamsCount: number;
+  settledRounds: number;
+  /// User ids by seed
+  seeds: number[];
+  /// User ids winning every game, round by round
+  winners: number[];
+};
+
+export const BracketLayout: typeof BufferLayout.Structure = BufferLayout.struct([
+  BufferLayout.u8('teamsCount'),
+  BufferLayout.u8('settledRounds'),
+  BufferLayout.seq(BufferLayout.u8(), PLAYOFF_TEAMS_CAPACITY, 'seeds'),
+  BufferLayout.seq(BufferLayout.u8(), PLAYOFF_TEAMS_CAPACITY - 1, 'winners'),
+]);
+
+export type CompositionRule = {
+  min: number;
+  max: number;
+};
+
+export const CompositionRuleLayout: typeof BufferLayout.Structure = BufferLayout.struct([
+  BufferLayout.u8('min'),
+  BufferLayout.u8('max'),
+]);
+
+export type FlexSlot = {
+  number: number;
+  /// Bit N is set when `Position` N can fill the slot
+  positions: number;
+};
+
+export const FlexSlotLayout: typeof BufferLayout.Structure = BufferLayout.struct([
+  BufferLayout.u8('number'),
+  BufferLayout.u16('positions'),
+]);
+
+export type PositionOptions = {
+  /// Number of lineup slots of every position but `Uninitialized`
+  positions: number[];
+  flexSlots: FlexSlot[];
+};
+
+export const PositionOptionsLayout: typeof BufferLayout.Structure = BufferLayout.struct([
+  BufferLayout.seq(BufferLayout.u8(), NUM_POSITIONS - 1, 'positions'),
+  BufferLayout.seq(FlexSlotLayout, FLEX_SLOTS_CAPACITY, 'flexSlots'),
 ]);
 
 export type League = {
@@ -107,6 +246,38 @@ export type League = {
   startWeek: number;
   isRewardClaimed: boolean;
   isInitialized: boolean;
+  activePlayersCount: number;
+  benchPlayersCount: number;
+  sport: Sport;
+  leagueType: LeagueType;
+  lastSettledWeek: number;
+  /// Share of the pot for every place in basis points
+  payouts: number[];
+  feeBps: number;
+  /// SPL token mint of the bid, default for native lamports
+  mint: PublicKey;
+  tokenVault: PublicKey;
+  fillDeadline: number;
+  joinAccess: JoinAccess;
+  passwordKey: PublicKey;
+  allowList: PublicKey[];
+  pickTimeLimit: number;
+  pickStartedAt: number;
+  draftType: DraftType;
+  auctionBudget: number;
+  auctionWindow: number;
+  nominatedPlayerId: number;
+  highBid: number;
+  highBidderId: number;
+  auctionDeadline: number;
+  fillSlot: u64;
+  /// User ids in the draft order of the first round, drawn once the league is full
+  pickOrder: number[];
+  bracket: Bracket;
+  /// Points per unit of every `Stat`, in hundredths of a point
+  scoringRules: number[];
+  compositionRules: CompositionRule[];
+  positionOptions: PositionOptions;
 };
 
 export const LeagueLayout: typeof BufferLayout.Structure = BufferLayout.struct([
@@ -119,53 +290,104 @@ export const LeagueLayout: typeof BufferLayout.Structure = BufferLayout.struct([
   BufferLayout.u8('startWeek'),
   Layout.boolean('isRewardClaimed'),
   Layout.boolean('isInitialized'),
+  BufferLayout.u8('activePlayersCount'),
+  BufferLayout.u8('benchPlayersCount'),
+  BufferLayout.u8('sport'),
+  BufferLayout.u8('leagueType'),
+  BufferLayout.u8('lastSettledWeek'),
+  BufferLayout.seq(BufferLayout.u16(), LEAGUE_USERS_CAPACITY, 'payouts'),
+  BufferLayout.u16('feeBps'),
+  Layout.publicKey('mint'),
+  Layout.publicKey('tokenVault'),
+  BufferLayout.ns64('fillDeadline'),
+  BufferLayout.u8('joinAccess'),
+  Layout.publicKey('passwordKey'),
+  BufferLayout.seq(Layout.publicKey(), LEAGUE_USERS_CAPACITY, 'allowList'),
+  BufferLayout.u32('pickTimeLimit'),
+  BufferLayout.ns64('pickStartedAt'),
+  BufferLayout.u8('draftType'),
+  BufferLayout.u32('auctionBudget'),
+  BufferLayout.u32('auctionWindow'),
+  BufferLayout.u16('nominatedPlayerId'),
+  BufferLayout.u32('highBid'),
+  BufferLayout.u8('highBidderId'),
+  BufferLayout.ns64('auctionDeadline'),
+  Layout.uint64('fillSlot'),
+  BufferLayout.seq(BufferLayout.u8(), LEAGUE_USERS_CAPACITY, 'pickOrder'),
+  BracketLayout.replicate('bracket'),
+  BufferLayout.seq(BufferLayout.s16(), STATS_COUNT, 'scoringRules'),
+  BufferLayout.seq(CompositionRuleLayout, NUM_POSITIONS - 1, 'compositionRules'),
+  PositionOptionsLayout.replicate('positionOptions'),
 ]);
 
 export type Score = {
-  score1: number;
+  /// Raw stat line indexed by `Stat`
+  stats: number[];
   isInitialized: boolean;
+  correctionsCount: number;
+  /// Bit mask of the stats changed by corrections
+  correctedStats: number;
+  /// Value of every corrected stat before its last correction, not the original oracle value
+  previousStats: number[];
 };
 
 export const ScoreLayout: typeof BufferLayout.Structure = BufferLayout.struct([
-  BufferLayout.u16('score1'),
+  BufferLayout.seq(BufferLayout.s16(), STATS_COUNT, 'stats'),
   Layout.boolean('isInitialized'),
+  BufferLayout.u8('correctionsCount'),
+  BufferLayout.u32('correctedStats'),
+  BufferLayout.seq(BufferLayout.s16(), STATS_COUNT, 'previousStats'),
 ]);
 
 export type Player = {
   scores: Score[];
   externalId: number;
   position: Position;
+  sport: Sport;
   isInitialized: boolean;
+  rank: number;
 };
 
 export const PlayerLayout: typeof BufferLayout.Structure = BufferLayout.struct([
-  BufferLayout.seq(ScoreLayout, GAMES_COUNT, 'scores'),
+  BufferLayout.seq(ScoreLayout, GAMES_CAPACITY, 'scores'),
   BufferLayout.u16('externalId'),
   BufferLayout.u8('position'),
+  BufferLayout.u8('sport'),
   Layout.boolean('isInitialized'),
+  BufferLayout.u16('rank'),
 ]);
 
 export type Root = {
   /// An address of an account that stores the latest state.
   playersCount: number;
   players: Player[];
-  /// Leagues
-  leaguesCount: number;
-  leagues: League[];
-  pickOrder: number[];
   stage: Stage;
   currentWeek: number;
   /// Oracle authority used to supply game scores.
   oracleAuthority: PublicKey;
+  /// Authority allowed to manage the player pool and the season.
+  adminAuthority: PublicKey;
+  /// Number of league accounts created so far, also the index of the next league
+  leaguesCount: number;
+  sport: Sport;
+  scoreCorrectionWeeks: number;
+  /// Protocol fee taken from the pot of every league in basis points
+  feeBps: number;
+  treasury: PublicKey;
+  accruedFees: u64;
 };
 
 export const RootLayout: typeof BufferLayout.Structure = BufferLayout.struct([
   BufferLayout.u16('playersCount'),
   BufferLayout.seq(PlayerLayout, PLAYERS_CAPACITY, 'players'),
-  BufferLayout.u16('leaguesCount'),
-  BufferLayout.seq(LeagueLayout, LEAGUES_CAPACITY, 'leagues'),
-  BufferLayout.seq(BufferLayout.u8(), LEAGUE_USERS_CAPACITY, 'pickOrder'),
   BufferLayout.u8('stage'),
   BufferLayout.u8('currentWeek'),
   Layout.publicKey('oracleAuthority'),
+  Layout.publicKey('adminAuthority'),
+  BufferLayout.u16('leaguesCount'),
+  BufferLayout.u8('sport'),
+  BufferLayout.u8('scoreCorrectionWeeks'),
+  BufferLayout.u16('feeBps'),
+  Layout.publicKey('treasury'),
+  Layout.uint64('accruedFees'),
 ]);
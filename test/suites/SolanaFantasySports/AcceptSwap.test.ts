@@ -8,7 +8,7 @@ export const AcceptSwap = () =>
   describe('Accept swap', () => {
     it('throws on invalid owner', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const givePlayerId = league.userStates[0].swapProposals[0].givePlayerId;
       const wantPlayerId = league.userStates[0].swapProposals[0].wantPlayerId;
@@ -20,7 +20,7 @@ export const AcceptSwap = () =>
     });
     it('Accept swap', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const givePlayerId = league.userStates[0].swapProposals[0].givePlayerId;
       const wantPlayerId = league.userStates[0].swapProposals[0].wantPlayerId;
@@ -28,7 +28,7 @@ export const AcceptSwap = () =>
       await global.sfs.acceptSwap(global.secondAccount, 0, 2, 1, wantPlayerId, givePlayerId);
 
       root = await global.sfs.getRootInfo();
-      league = root.leagues[0];
+      league = await global.sfs.getLeagueInfo(0);
 
       strictEqual(
         league.userStates[0].swapProposalsCount,
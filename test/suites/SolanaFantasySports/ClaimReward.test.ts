@@ -11,7 +11,8 @@ export const ClaimReward = () =>
   describe('ClaimReward', () => {
     it('throws on reward claim before season end', async () => {
       let root = await global.sfs.getRootInfo();
-      let winners = SFS.getWinners(root, 0);
+      let league = await global.sfs.getLeagueInfo(0);
+      let winners = SFS.getWinners(root, league);
 
       await throwsAsync(
         () =>
@@ -25,13 +26,14 @@ export const ClaimReward = () =>
     });
     it('Claims rewards', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       for (let i = root.currentWeek; i <= GAMES_COUNT; i++) {
         await global.sfs.incrementWeek(global.payerAccount);
       }
 
-      let winners = SFS.getWinners(root, 0);
+      root = await global.sfs.getRootInfo();
+      let winners = SFS.getWinners(root, league);
 
       const balancesBefore = await Promise.all(
         winners.map((x) => global.connection.getBalance(x.userState.pubKey))
@@ -48,7 +50,7 @@ export const ClaimReward = () =>
       );
 
       root = await global.sfs.getRootInfo();
-      league = root.leagues[0];
+      league = await global.sfs.getLeagueInfo(0);
 
       deepStrictEqual(
         balancesAfter,
@@ -1,28 +1,67 @@
 import { PublicKey } from '@solana/web3.js';
 import { ok, strictEqual } from 'assert';
 import { u64 } from '../../../sdk/util/layout';
+import { SFS } from '../../../sdk/sfs';
+import {
+  ACTIVE_PLAYERS_COUNT,
+  BENCH_PLAYERS_COUNT,
+  DraftType,
+  JoinAccess,
+  LeagueType,
+  NUM_POSITIONS,
+  Position,
+  footballScoringRules,
+} from '../../../sdk/state';
 
 export const CreateLeague = () =>
   describe('Create league', () => {
     it('creates league', async () => {
-      const bank = (global.sfs as any).bank as PublicKey;
+      const root = await global.sfs.getRootInfo();
+      const vault = await SFS.findVaultAddress(
+        global.solanaFantasySportsPPK,
+        (global.sfs as any).publicKey as PublicKey,
+        root.leaguesCount
+      );
 
-      const balanceBefore = await global.connection.getBalance(bank);
+      const balanceBefore = await global.connection.getBalance(vault);
 
-      const leagueIndex = await global.sfs.createLeague(
-        global.firstAccount,
-        'Test League',
-        1 * 10 ** 9,
-        2,
-        'Test Team'
-      );
+      // Every test player is a running back
+      const positions = Array(NUM_POSITIONS - 1).fill(0);
+      positions[Position.RB - 1] = ACTIVE_PLAYERS_COUNT;
+      const compositionRules = Array.from({ length: NUM_POSITIONS - 1 }, () => ({ min: 0, max: 0 }));
+      compositionRules[Position.RB - 1].max = ACTIVE_PLAYERS_COUNT + BENCH_PLAYERS_COUNT;
+
+      const leagueIndex = await global.sfs.createLeague(global.firstAccount, {
+        name: 'Test League',
+        bid: 1 * 10 ** 9,
+        usersLimit: 2,
+        teamName: 'Test Team',
+        positions,
+        flexSlots: [],
+        activePlayersCount: ACTIVE_PLAYERS_COUNT,
+        benchPlayersCount: BENCH_PLAYERS_COUNT,
+        compositionRules,
+        scoringRules: footballScoringRules(0),
+        leagueType: LeagueType.TotalPoints,
+        playoffTeamsCount: 0,
+        payouts: [10000],
+        mint: new PublicKey(0),
+        fillDeadline: 0,
+        joinAccess: JoinAccess.Public,
+        passwordKey: new PublicKey(0),
+        allowList: [],
+        pickTimeLimit: 0,
+        draftType: DraftType.Snake,
+        auctionBudget: 0,
+        auctionWindow: 0,
+      });
 
-      const balanceAfter = await global.connection.getBalance(bank);
+      const balanceAfter = await global.connection.getBalance(vault);
 
       console.log(`League index ${leagueIndex}`);
 
       const root = await global.sfs.getRootInfo();
-      const league = root.leagues[leagueIndex];
+      const league = await global.sfs.getLeagueInfo(leagueIndex);
 
       strictEqual(league.userStateCount, 1, 'should be 1 player joined');
       strictEqual(league.isInitialized, true, 'league should be initialized');
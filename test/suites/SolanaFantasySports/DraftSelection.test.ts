@@ -7,12 +7,9 @@ export const DraftSelection = () =>
     let usersCount = 2;
 
     const getContext = async (i: number) => {
-      const root = await global.sfs.getRootInfo();
-      const league = root.leagues[0];
+      const league = await global.sfs.getLeagueInfo(0);
 
-      const pickOrder = root.pickOrder;
-
-      const reducedPickOrder = pickOrder.filter((x) => x <= usersCount);
+      const reducedPickOrder = league.pickOrder.slice(0, usersCount);
 
       const pickOrderForSmallerTeam = Array.from({
         length: TEAM_PLAYERS_COUNT * usersCount,
@@ -32,7 +29,12 @@ export const DraftSelection = () =>
       return { league, pickOrderForSmallerTeam, round, expectedPick, playersToPick };
     };
 
-    it(``, async () => {
+    it('draws pick order once the league is full', async () => {
+      // The draw is postponed when the slot after the fill is no longer in the slot hashes
+      while ((await global.sfs.getLeagueInfo(0)).pickOrder[0] === 0) {
+        await global.sfs.drawPickOrder(0, global.payerAccount);
+      }
+
       const { pickOrderForSmallerTeam } = await getContext(0);
       console.log('expected pick order:');
       for (let i = 0; i < TEAM_PLAYERS_COUNT; i++) {
@@ -85,8 +87,7 @@ export const DraftSelection = () =>
           expectedPick,
           playersToPick[i]
         );
-        const root = await global.sfs.getRootInfo();
-        const league = root.leagues[0];
+        const league = await global.sfs.getLeagueInfo(0);
 
         strictEqual(league.currentPick, i + 1, 'should increase current pick');
         strictEqual(
@@ -1,9 +1,8 @@
 import { Account } from '@solana/web3.js';
 import { Player } from '../../../sdk/instruction';
 import { SFS } from '../../../sdk/sfs';
-import { Position, PLAYERS_CAPACITY, Stage, LEAGUE_USERS_CAPACITY } from '../../../sdk/state';
+import { Position, PLAYERS_CAPACITY, Stage } from '../../../sdk/state';
 import { strictEqual, ok } from 'assert';
-import { hasDuplicates } from '../../helpers';
 
 const rootAccount = new Account();
 
@@ -16,6 +15,7 @@ export const InitializeRoot = () =>
         (_, i): Player => ({
           externalId: i,
           position: Position.RB,
+          rank: i + 1,
         })
       );
 
@@ -44,12 +44,5 @@ export const InitializeRoot = () =>
       strictEqual(root.playersCount, players.length, 'players count should match');
       strictEqual(root.stage, Stage.SeasonOpen, 'stage should be correct');
       strictEqual(root.leaguesCount, 0, 'should be no leagues yet');
-
-      ok(
-        root.pickOrder.every((x) => x > 0 && x < LEAGUE_USERS_CAPACITY + 1),
-        'all pick order values should be in range of users'
-      );
-
-      ok(!hasDuplicates(root.pickOrder), 'pick order should not contain duplicates');
     });
   });
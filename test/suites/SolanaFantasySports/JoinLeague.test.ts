@@ -2,6 +2,7 @@ import { PublicKey } from '@solana/web3.js';
 import { ok, strictEqual, throws, doesNotThrow, fail } from 'assert';
 import { u64 } from '../../../sdk/util/layout';
 import { throwsAsync } from '../../helpers';
+import { SFS } from '../../../sdk/sfs';
 
 export const JoinLeague = () =>
   describe('Join league', () => {
@@ -12,16 +13,20 @@ export const JoinLeague = () =>
       );
     });
     it('joins league', async () => {
-      const bank = (global.sfs as any).bank as PublicKey;
+      const vault = await SFS.findVaultAddress(
+        global.solanaFantasySportsPPK,
+        (global.sfs as any).publicKey as PublicKey,
+        0
+      );
 
-      const balanceBefore = await global.connection.getBalance(bank);
+      const balanceBefore = await global.connection.getBalance(vault);
 
       await global.sfs.joinLeague(global.secondAccount, 0, 'Test Team');
 
-      const balanceAfter = await global.connection.getBalance(bank);
+      const balanceAfter = await global.connection.getBalance(vault);
 
       const root = await global.sfs.getRootInfo();
-      const league = root.leagues[0];
+      const league = await global.sfs.getLeagueInfo(0);
 
       strictEqual(league.userStateCount, 2, 'should be 2 player joined');
       strictEqual(balanceAfter - balanceBefore, league.bid.toNumber(), 'should transfer funds');
@@ -8,7 +8,7 @@ export const ProposeSwap = () =>
   describe('ProposeSwap', () => {
     it('throws on invalid owner', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const givePlayerId =
         league.userStates[0].userPlayers.find(
@@ -26,7 +26,7 @@ export const ProposeSwap = () =>
     });
     it('throws on proposal with active players', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const givePlayerId = league.userStates[0].lineups[root.currentWeek - 1][0];
       const wantPlayerId = league.userStates[1].userPlayers[0];
@@ -38,7 +38,7 @@ export const ProposeSwap = () =>
     });
     it('throws on proposal with players not owned', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const givePlayerId = league.userStates[1].userPlayers[1];
       const wantPlayerId = league.userStates[1].userPlayers[0];
@@ -50,7 +50,7 @@ export const ProposeSwap = () =>
     });
     it('Propose swap', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const givePlayerId =
         league.userStates[0].userPlayers.find(
@@ -64,7 +64,7 @@ export const ProposeSwap = () =>
       await global.sfs.proposeSwap(global.firstAccount, 0, 1, 2, givePlayerId, wantPlayerId);
 
       root = await global.sfs.getRootInfo();
-      league = root.leagues[0];
+      league = await global.sfs.getLeagueInfo(0);
 
       strictEqual(league.userStates[0].swapProposalsCount, 1, 'should add swap proposal count');
       deepStrictEqual(
@@ -80,7 +80,7 @@ export const ProposeSwap = () =>
     });
     it('Propose second swap', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const givePlayerId =
         league.userStates[0].userPlayers
@@ -94,7 +94,7 @@ export const ProposeSwap = () =>
       await global.sfs.proposeSwap(global.firstAccount, 0, 1, 2, givePlayerId, wantPlayerId);
 
       root = await global.sfs.getRootInfo();
-      league = root.leagues[0];
+      league = await global.sfs.getLeagueInfo(0);
 
       strictEqual(league.userStates[0].swapProposalsCount, 2, 'should add swap proposal count');
       deepStrictEqual(
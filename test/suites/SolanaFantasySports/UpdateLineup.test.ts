@@ -8,42 +8,53 @@ export const UpdateLineup = () =>
   describe('Update lineup', () => {
     it('updates lineup for first user', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const newLineup = league.userStates[0].userPlayers
+        .filter((x) => x !== 0)
         .sort(() => 0.5 - Math.random())
         .slice(0, ACTIVE_PLAYERS_COUNT);
 
       await global.sfs.updateLineup(global.firstAccount, 0, 1, 2, newLineup);
 
       root = await global.sfs.getRootInfo();
-      league = root.leagues[0];
+      league = await global.sfs.getLeagueInfo(0);
 
-      deepStrictEqual(league.userStates[0].lineups[1], newLineup, 'should correctly update lineup');
+      deepStrictEqual(
+        league.userStates[0].lineups[1].slice(0, ACTIVE_PLAYERS_COUNT),
+        newLineup,
+        'should correctly update lineup'
+      );
       deepStrictEqual(league.userStates[0].isLineupSet, true, 'should set isLineupSet to true');
     });
     it('updates lineup for second user', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const newLineup = league.userStates[1].userPlayers
+        .filter((x) => x !== 0)
         .sort(() => 0.5 - Math.random())
         .slice(0, ACTIVE_PLAYERS_COUNT);
 
       await global.sfs.updateLineup(global.secondAccount, 0, 2, 2, newLineup);
 
       root = await global.sfs.getRootInfo();
-      league = root.leagues[0];
+      league = await global.sfs.getLeagueInfo(0);
 
-      deepStrictEqual(league.userStates[1].lineups[1], newLineup, 'should correctly update lineup');
+      deepStrictEqual(
+        league.userStates[1].lineups[1].slice(0, ACTIVE_PLAYERS_COUNT),
+        newLineup,
+        'should correctly update lineup'
+      );
       deepStrictEqual(league.userStates[1].isLineupSet, true, 'should set isLineupSet to true');
       deepStrictEqual(league.startWeek, 2, 'should set start week');
     });
     it('throws on update with players not owned', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const newLineup = league.userStates[0].userPlayers
+        .filter((x) => x !== 0)
         .sort(() => 0.5 - Math.random())
         .slice(0, ACTIVE_PLAYERS_COUNT);
 
@@ -54,9 +65,10 @@ export const UpdateLineup = () =>
     });
     it('throws on past week lineup update', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const newLineup = league.userStates[0].userPlayers
+        .filter((x) => x !== 0)
         .sort(() => 0.5 - Math.random())
         .slice(0, ACTIVE_PLAYERS_COUNT);
 
@@ -67,9 +79,10 @@ export const UpdateLineup = () =>
     });
     it('throws on duplicate players', async () => {
       let root = await global.sfs.getRootInfo();
-      let league = root.leagues[0];
+      let league = await global.sfs.getLeagueInfo(0);
 
       const newLineup = league.userStates[0].userPlayers
+        .filter((x) => x !== 0)
         .sort(() => 0.5 - Math.random())
         .slice(0, ACTIVE_PLAYERS_COUNT);
 
@@ -1,6 +1,7 @@
 import { PublicKey } from '@solana/web3.js';
 import { deepStrictEqual, ok, strictEqual } from 'assert';
 import { u64 } from '../../../sdk/util/layout';
+import { STATS_COUNT } from '../../../sdk/state';
 
 export const UpdatePlayerScore = () =>
   describe('Update Player Score', () => {
@@ -26,7 +27,7 @@ export const UpdatePlayerScore = () =>
 
       const scores = rootBefore.players.map((player, index) => ({
         playerId: index + 1,
-        playerScore: Math.round(Math.random() * 100),
+        stats: Array.from({ length: STATS_COUNT }, () => Math.round(Math.random() * 100)),
       }));
 
       await global.sfs.updatePlayerScores(global.payerAccount, scores);
@@ -36,7 +37,7 @@ export const UpdatePlayerScore = () =>
       const root = await global.sfs.getRootInfo();
       const scoresAfter = root.players.map((p, index) => ({
         playerId: index + 1,
-        playerScore: p.scores[root.currentWeek - 1].score1,
+        stats: p.scores[root.currentWeek - 1].stats,
       }));
       deepStrictEqual(scores, scoresAfter, 'score should be set');
     });